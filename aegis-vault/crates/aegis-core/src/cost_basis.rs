@@ -0,0 +1,75 @@
+// Average-cost bookkeeping for one depositor, in base-asset units. Deposits
+// add what the vault credited; withdrawals release cost pro rata to the
+// shares burned and realize the difference against what was paid out.
+//
+// Shares move freely between wallets, so the tracked count can drift from the
+// holder's balance. `sync` realigns it before each operation: shares that
+// left take their average cost with them (nothing is realized), and shares
+// that arrived enter at NAV when first seen, the best on-chain estimate of
+// what they were worth on receipt.
+use crate::math::mul_div_floor;
+use crate::shares::assets_for_shares;
+
+/// 1.000000 assets per share, the scale of `avg_entry_price_e6`
+pub const PRICE_SCALE: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CostBasis {
+    /// Shares the cost below is spread over
+    pub shares: u64,
+    /// Assets paid for `shares`
+    pub cost: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    /// Sum over withdrawals of assets paid out minus the cost released
+    pub realized_pnl: i64,
+}
+
+impl CostBasis {
+    /// Realign with the holder's share `balance`, valuing shares that arrived
+    /// by transfer at the vault's current NAV. `None` on overflow.
+    pub fn sync(&mut self, balance: u64, total_shares: u64, total_assets: u64) -> Option<()> {
+        if balance < self.shares {
+            self.cost = mul_div_floor(self.cost, balance, self.shares)?;
+        } else if balance > self.shares {
+            let arrived = assets_for_shares(balance - self.shares, total_shares, total_assets)?;
+            self.cost = self.cost.checked_add(arrived)?;
+        }
+        self.shares = balance;
+        Some(())
+    }
+
+    /// Record `assets` credited for `minted` shares
+    pub fn deposit(&mut self, assets: u64, minted: u64) -> Option<()> {
+        self.shares = self.shares.checked_add(minted)?;
+        self.cost = self.cost.checked_add(assets)?;
+        self.total_deposited = self.total_deposited.checked_add(assets)?;
+        Some(())
+    }
+
+    /// Record `burned` shares redeemed for `assets`. Cost is released pro
+    /// rata, rounded down, so a full exit releases exactly what is left.
+    pub fn withdraw(&mut self, burned: u64, assets: u64) -> Option<()> {
+        let released = match burned == self.shares {
+            true => self.cost,
+            false => mul_div_floor(self.cost, burned, self.shares)?,
+        };
+        let shares = self.shares.checked_sub(burned)?;
+        let pnl = i64::try_from(assets as i128 - released as i128).ok()?;
+        self.realized_pnl = self.realized_pnl.checked_add(pnl)?;
+        self.total_withdrawn = self.total_withdrawn.checked_add(assets)?;
+        self.shares = shares;
+        self.cost -= released;
+        Some(())
+    }
+
+    /// Gain or loss on the shares still held if they are worth `value`
+    pub fn unrealized_pnl(&self, value: u64) -> Option<i64> {
+        i64::try_from(value as i128 - self.cost as i128).ok()
+    }
+
+    /// Average assets paid per share, scaled by `PRICE_SCALE`; 0 without shares
+    pub fn avg_entry_price_e6(&self) -> u64 {
+        mul_div_floor(self.cost, PRICE_SCALE, self.shares).unwrap_or(0)
+    }
+}
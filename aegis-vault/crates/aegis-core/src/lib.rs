@@ -0,0 +1,27 @@
+// Aegis core — the vault's decision logic with no Solana or Anchor types.
+// The on-chain program calls into this crate, and keepers, the simulator and
+// the front-end backend link the same code, so off-chain and on-chain
+// decisions are bit-identical. `no_std` unless the `wasm` feature is enabled.
+//
+// JS package:
+//   cargo rustc -p aegis-core --lib --release --features wasm \
+//     --target wasm32-unknown-unknown --crate-type cdylib
+//   wasm-bindgen --target nodejs target/wasm32-unknown-unknown/release/aegis_core.wasm
+#![cfg_attr(not(feature = "wasm"), no_std)]
+
+pub mod cost_basis;
+pub mod math;
+pub mod peg;
+pub mod position;
+pub mod shares;
+pub mod state_machine;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use cost_basis::*;
+pub use math::*;
+pub use peg::*;
+pub use position::*;
+pub use shares::*;
+pub use state_machine::*;
@@ -0,0 +1,19 @@
+// Fixed-point helpers: `a × b / c` through a u128 intermediate, with the
+// rounding direction chosen by the caller rather than implied by `/`.
+
+/// `⌊a × b / c⌋`; `None` when `c` is zero or the result exceeds u64
+pub fn mul_div_floor(a: u64, b: u64, c: u64) -> Option<u64> {
+    if c == 0 {
+        return None;
+    }
+    u64::try_from(a as u128 * b as u128 / c as u128).ok()
+}
+
+/// `⌈a × b / c⌉`; `None` when `c` is zero or the result exceeds u64
+pub fn mul_div_ceil(a: u64, b: u64, c: u64) -> Option<u64> {
+    if c == 0 {
+        return None;
+    }
+    let product = a as u128 * b as u128;
+    u64::try_from(product.div_ceil(c as u128)).ok()
+}
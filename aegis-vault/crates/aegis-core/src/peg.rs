@@ -0,0 +1,41 @@
+// Peg and confidence metrics. Prices are fixed-point with 6 decimals
+// ($1.00 = 1_000_000); all results are basis points, saturating at u16::MAX.
+
+/// $1.00 in 6 decimals
+pub const PEG_TARGET: i64 = 1_000_000;
+
+/// Calculate peg deviation in basis points.
+/// With `conf`, uses the worst edge of the band (price ± conf), i.e. |$1 − price| + conf.
+pub fn calculate_peg_bps(price: i64, conf: Option<u64>) -> u16 {
+    let target = PEG_TARGET as i128;
+    let deviation = (target - price as i128).unsigned_abs() + conf.unwrap_or(0) as u128;
+    ((deviation * 10_000) / target as u128).min(u16::MAX as u128) as u16
+}
+
+/// Deviation of the collateral/debt cross rate from 1:1, in bps of the debt price.
+/// With `confs` (collateral, debt), uses the worst edges of both bands.
+/// u16::MAX if the debt price is ≤ 0.
+pub fn calculate_cross_rate_bps(collateral_price: i64, debt_price: i64, confs: Option<(u64, u64)>) -> u16 {
+    if debt_price <= 0 {
+        return u16::MAX;
+    }
+    let (collateral_conf, debt_conf) = confs.unwrap_or((0, 0));
+    let deviation = (collateral_price as i128 - debt_price as i128).unsigned_abs()
+        + collateral_conf as u128
+        + debt_conf as u128;
+    ((deviation * 10_000) / debt_price as u128).min(u16::MAX as u128) as u16
+}
+
+/// Peg deviation driving a two-asset vault: the worst of the collateral leg's
+/// peg, the debt leg's peg and the collateral/debt cross rate.
+pub fn two_asset_peg_bps(collateral_peg_bps: u16, debt_peg_bps: u16, cross_rate_bps: u16) -> u16 {
+    collateral_peg_bps.max(debt_peg_bps).max(cross_rate_bps)
+}
+
+/// Confidence interval width in basis points of price (u16::MAX if price ≤ 0).
+pub fn calculate_conf_bps(price: i64, conf: u64) -> u16 {
+    if price <= 0 {
+        return u16::MAX;
+    }
+    ((conf as u128 * 10_000) / price as u128).min(u16::MAX as u128) as u16
+}
@@ -0,0 +1,35 @@
+// Leveraged position metrics from supplied and borrowed totals.
+
+/// Current equity = supplied − borrowed (saturating to prevent underflow)
+pub fn equity(total_supplied: u64, total_borrowed: u64) -> u64 {
+    total_supplied.saturating_sub(total_borrowed)
+}
+
+/// Assets backing the vault's shares (NAV): venue equity plus `idle` cash
+/// held by the vault, which includes the buffer kept back from the venue.
+/// `None` on overflow rather than saturating, which would underprice shares.
+pub fn total_assets(total_supplied: u64, total_borrowed: u64, idle: u64) -> Option<u64> {
+    equity(total_supplied, total_borrowed).checked_add(idle)
+}
+
+/// Current leverage in basis points (10000 = 1.00x); 0 without equity,
+/// saturating at u16::MAX (6.55x)
+pub fn leverage_bps(total_supplied: u64, total_borrowed: u64) -> u16 {
+    let eq = equity(total_supplied, total_borrowed);
+    if eq == 0 {
+        return 0;
+    }
+    ((total_supplied as u128 * 10_000) / eq as u128).min(u16::MAX as u128) as u16
+}
+
+/// Returns the target borrow amount for a desired leverage level.
+pub fn calculate_target_borrow(total_supplied: u64, target_leverage_bps: u16) -> u64 {
+    let leverage = target_leverage_bps as u128;
+    let supplied = total_supplied as u128;
+    ((supplied * leverage.saturating_sub(10_000)) / leverage) as u64
+}
+
+/// Returns the amount to repay to reduce leverage by a given percentage.
+pub fn calculate_repay_amount(total_borrowed: u64, reduction_pct: u8) -> u64 {
+    ((total_borrowed as u128 * reduction_pct as u128) / 100) as u64
+}
@@ -0,0 +1,46 @@
+// Vault share accounting. Every conversion rounds in the vault's favour, so
+// the remainder stays with existing holders and no sequence of small
+// operations can extract dust:
+//   shares minted for a deposit          floor (`shares_for_deposit`)
+//   assets paid out for burned shares    floor (`assets_for_shares`)
+//   shares burned for an exact payout    ceil  (`shares_for_withdrawal`)
+// The first deposit also locks `DEAD_SHARES`, so the supply never returns to
+// zero and a donation can't inflate the share price of a near-empty vault.
+use crate::math::{mul_div_ceil, mul_div_floor};
+
+/// Shares minted for depositing `amount`: 1:1 into a vault without shares,
+/// otherwise pro rata to equity, rounded down. `None` on overflow, or when
+/// shares exist but equity is zero.
+pub fn shares_for_deposit(amount: u64, total_shares: u64, equity: u64) -> Option<u64> {
+    if total_shares == 0 {
+        return Some(amount); // 1:1 for first deposit
+    }
+    mul_div_floor(amount, total_shares, equity)
+}
+
+/// Shares the first deposit into a vault locks forever: counted in the
+/// supply, owned by nobody. Donating `d` to inflate the price then costs the
+/// attacker `d` to move it by at most `d / DEAD_SHARES` per share.
+pub const DEAD_SHARES: u64 = 1_000;
+
+/// Of `minted` shares, the ones credited to the depositor: all of them,
+/// except on the first deposit into a vault without shares, which keeps back
+/// `DEAD_SHARES`. `None` when that first deposit doesn't exceed them.
+pub fn depositor_shares(minted: u64, total_shares: u64) -> Option<u64> {
+    if total_shares > 0 {
+        return Some(minted);
+    }
+    minted.checked_sub(DEAD_SHARES).filter(|&shares| shares > 0)
+}
+
+/// Assets paid out for burning `shares`, pro rata to equity, rounded down.
+/// `None` when no shares are outstanding or on overflow.
+pub fn assets_for_shares(shares: u64, total_shares: u64, equity: u64) -> Option<u64> {
+    mul_div_floor(shares, equity, total_shares)
+}
+
+/// Shares burned to pay out exactly `assets`, rounded up. `None` when there
+/// is no equity or on overflow; the caller checks the holder has enough.
+pub fn shares_for_withdrawal(assets: u64, total_shares: u64, equity: u64) -> Option<u64> {
+    mul_div_ceil(assets, total_shares, equity)
+}
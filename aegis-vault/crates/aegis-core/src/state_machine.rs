@@ -0,0 +1,89 @@
+// Vault state machine (PRD §4): IDLE / LOOP / CONTRACT / EXIT / PANIC.
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[repr(u8)]
+pub enum VaultState {
+    Idle,
+    Loop,
+    Contract,
+    Exit,
+    Panic,
+}
+
+/// Per-vault limits the state machine compares against
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Thresholds {
+    pub peg_warn_bps: u16,
+    pub peg_exit_bps: u16,
+    pub peg_panic_bps: u16,
+    pub hf_floor_bps: u16,
+    pub stale_grace_slots: u64,
+}
+
+/// Conditions observed at rebalance time
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Conditions {
+    /// Deviation driving escalation (normally the TWAP deviation)
+    pub peg_bps: u16,
+    /// Worst deviation over the TWAP window; LOOP requires it to be healthy
+    pub peg_window_max_bps: u16,
+    /// Slots the oracle has been stale, if it is
+    pub oracle_stale_for: Option<u64>,
+    pub oracle_quorum_lost: bool,
+    pub oracle_unreliable: bool,
+    pub health_factor_bps: u16,
+}
+
+/// Determine the vault's current state based on conditions.
+pub fn determine_vault_state(conditions: &Conditions, thresholds: &Thresholds) -> VaultState {
+    let Conditions {
+        peg_bps,
+        peg_window_max_bps,
+        oracle_stale_for,
+        oracle_quorum_lost,
+        oracle_unreliable,
+        health_factor_bps,
+    } = *conditions;
+
+    // PANIC: Oracle disagreement (lost quorum) or severe depeg
+    if oracle_quorum_lost || peg_bps > thresholds.peg_panic_bps {
+        return VaultState::Panic;
+    }
+
+    // EXIT: Major depeg, or staleness outlasting the grace period
+    if peg_bps > thresholds.peg_exit_bps
+        || matches!(oracle_stale_for, Some(slots) if slots > thresholds.stale_grace_slots)
+    {
+        return VaultState::Exit;
+    }
+
+    // CONTRACT: Stale oracle within grace — last price still reasonable (PRD §7.3)
+    if oracle_stale_for.is_some() {
+        return VaultState::Contract;
+    }
+
+    // CONTRACT: Confidence band too wide to trust the peg reading
+    if oracle_unreliable {
+        return VaultState::Contract;
+    }
+
+    // CONTRACT: Moderate depeg or low health factor
+    if peg_bps > thresholds.peg_warn_bps || health_factor_bps < thresholds.hf_floor_bps {
+        return VaultState::Contract;
+    }
+
+    // LOOP: Healthy conditions
+    if peg_bps < thresholds.peg_warn_bps
+        && peg_window_max_bps < thresholds.peg_warn_bps
+        && health_factor_bps >= thresholds.hf_floor_bps
+    {
+        return VaultState::Loop;
+    }
+
+    // Default: IDLE
+    VaultState::Idle
+}
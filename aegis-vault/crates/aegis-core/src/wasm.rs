@@ -0,0 +1,93 @@
+// JS bindings (feature `wasm`). Thin wrappers over the core functions so the
+// front-end backend and keeper bots get the program's exact arithmetic.
+use wasm_bindgen::prelude::*;
+use crate::{Conditions, Thresholds, VaultState};
+
+#[wasm_bindgen(js_name = Thresholds)]
+pub struct JsThresholds(Thresholds);
+
+#[wasm_bindgen(js_class = Thresholds)]
+impl JsThresholds {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        peg_warn_bps: u16,
+        peg_exit_bps: u16,
+        peg_panic_bps: u16,
+        hf_floor_bps: u16,
+        stale_grace_slots: u64,
+    ) -> JsThresholds {
+        JsThresholds(Thresholds {
+            peg_warn_bps,
+            peg_exit_bps,
+            peg_panic_bps,
+            hf_floor_bps,
+            stale_grace_slots,
+        })
+    }
+}
+
+#[wasm_bindgen(js_name = determineVaultState)]
+pub fn determine_vault_state(
+    thresholds: &JsThresholds,
+    peg_bps: u16,
+    peg_window_max_bps: u16,
+    oracle_stale_for: Option<u64>,
+    oracle_quorum_lost: bool,
+    oracle_unreliable: bool,
+    health_factor_bps: u16,
+) -> VaultState {
+    let conditions = Conditions {
+        peg_bps,
+        peg_window_max_bps,
+        oracle_stale_for,
+        oracle_quorum_lost,
+        oracle_unreliable,
+        health_factor_bps,
+    };
+    crate::determine_vault_state(&conditions, &thresholds.0)
+}
+
+#[wasm_bindgen(js_name = calculatePegBps)]
+pub fn calculate_peg_bps(price: i64, conf: Option<u64>) -> u16 {
+    crate::calculate_peg_bps(price, conf)
+}
+
+#[wasm_bindgen(js_name = calculateConfBps)]
+pub fn calculate_conf_bps(price: i64, conf: u64) -> u16 {
+    crate::calculate_conf_bps(price, conf)
+}
+
+#[wasm_bindgen(js_name = equity)]
+pub fn equity(total_supplied: u64, total_borrowed: u64) -> u64 {
+    crate::equity(total_supplied, total_borrowed)
+}
+
+#[wasm_bindgen(js_name = totalAssets)]
+pub fn total_assets(total_supplied: u64, total_borrowed: u64, idle: u64) -> Option<u64> {
+    crate::total_assets(total_supplied, total_borrowed, idle)
+}
+
+#[wasm_bindgen(js_name = leverageBps)]
+pub fn leverage_bps(total_supplied: u64, total_borrowed: u64) -> u16 {
+    crate::leverage_bps(total_supplied, total_borrowed)
+}
+
+#[wasm_bindgen(js_name = sharesForDeposit)]
+pub fn shares_for_deposit(amount: u64, total_shares: u64, equity: u64) -> Option<u64> {
+    crate::shares_for_deposit(amount, total_shares, equity)
+}
+
+#[wasm_bindgen(js_name = depositorShares)]
+pub fn depositor_shares(minted: u64, total_shares: u64) -> Option<u64> {
+    crate::depositor_shares(minted, total_shares)
+}
+
+#[wasm_bindgen(js_name = assetsForShares)]
+pub fn assets_for_shares(shares: u64, total_shares: u64, equity: u64) -> Option<u64> {
+    crate::assets_for_shares(shares, total_shares, equity)
+}
+
+#[wasm_bindgen(js_name = sharesForWithdrawal)]
+pub fn shares_for_withdrawal(assets: u64, total_shares: u64, equity: u64) -> Option<u64> {
+    crate::shares_for_withdrawal(assets, total_shares, equity)
+}
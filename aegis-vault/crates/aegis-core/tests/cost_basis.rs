@@ -0,0 +1,83 @@
+use aegis_core::CostBasis;
+
+fn deposited(assets: u64, shares: u64) -> CostBasis {
+    let mut basis = CostBasis::default();
+    basis.deposit(assets, shares).unwrap();
+    basis
+}
+
+#[test]
+fn deposits_accumulate_at_cost() {
+    let mut basis = deposited(1_000, 1_000);
+    basis.deposit(1_100, 1_000).unwrap();
+    assert_eq!(basis.shares, 2_000);
+    assert_eq!(basis.cost, 2_100);
+    assert_eq!(basis.total_deposited, 2_100);
+    assert_eq!(basis.avg_entry_price_e6(), 1_050_000);
+}
+
+#[test]
+fn withdrawals_realize_against_average_cost() {
+    let mut basis = deposited(2_100, 2_000);
+    // Half the shares at NAV 1.20: 1,200 out against 1,050 of cost
+    basis.withdraw(1_000, 1_200).unwrap();
+    assert_eq!(basis.realized_pnl, 150);
+    assert_eq!(basis.cost, 1_050);
+    assert_eq!(basis.total_withdrawn, 1_200);
+    // Losses realize negative
+    basis.withdraw(1_000, 900).unwrap();
+    assert_eq!(basis.realized_pnl, 0);
+    assert_eq!((basis.shares, basis.cost), (0, 0));
+}
+
+#[test]
+fn full_exit_releases_the_rounding_remainder() {
+    let mut basis = deposited(1_000, 3);
+    basis.withdraw(1, 333).unwrap();
+    assert_eq!(basis.cost, 667);
+    basis.withdraw(2, 667).unwrap();
+    assert_eq!(basis.cost, 0);
+    assert_eq!(basis.realized_pnl, 0);
+}
+
+#[test]
+fn withdrawing_more_than_tracked_fails_without_side_effects() {
+    let mut basis = deposited(1_000, 1_000);
+    assert_eq!(basis.withdraw(1_001, 1_001), None);
+    assert_eq!(basis, deposited(1_000, 1_000));
+}
+
+#[test]
+fn sync_moves_cost_out_with_sent_shares() {
+    let mut basis = deposited(1_000, 1_000);
+    basis.sync(250, 10_000, 20_000).unwrap();
+    assert_eq!((basis.shares, basis.cost), (250, 250));
+    assert_eq!(basis.realized_pnl, 0);
+    basis.sync(0, 10_000, 20_000).unwrap();
+    assert_eq!((basis.shares, basis.cost), (0, 0));
+}
+
+#[test]
+fn sync_costs_received_shares_at_nav() {
+    let mut basis = deposited(1_000, 1_000);
+    // 500 more shares at NAV 2.00
+    basis.sync(1_500, 10_000, 20_000).unwrap();
+    assert_eq!((basis.shares, basis.cost), (1_500, 2_000));
+    assert_eq!(basis.total_deposited, 1_000);
+    // A holder who never deposited starts from NAV too
+    let mut basis = CostBasis::default();
+    basis.sync(100, 10_000, 20_000).unwrap();
+    assert_eq!(basis.cost, 200);
+}
+
+#[test]
+fn unrealized_pnl_is_signed() {
+    let basis = deposited(1_000, 1_000);
+    assert_eq!(basis.unrealized_pnl(1_250), Some(250));
+    assert_eq!(basis.unrealized_pnl(800), Some(-200));
+}
+
+#[test]
+fn empty_position_has_no_entry_price() {
+    assert_eq!(CostBasis::default().avg_entry_price_e6(), 0);
+}
@@ -0,0 +1,33 @@
+use aegis_core::{mul_div_ceil, mul_div_floor};
+
+#[test]
+fn exact_quotients_agree() {
+    assert_eq!(mul_div_floor(6, 5, 3), Some(10));
+    assert_eq!(mul_div_ceil(6, 5, 3), Some(10));
+    assert_eq!(mul_div_floor(0, 5, 3), Some(0));
+    assert_eq!(mul_div_ceil(0, 5, 3), Some(0));
+}
+
+#[test]
+fn remainders_round_in_the_requested_direction() {
+    assert_eq!(mul_div_floor(7, 5, 3), Some(11));
+    assert_eq!(mul_div_ceil(7, 5, 3), Some(12));
+    assert_eq!(mul_div_floor(1, 1, 10_000), Some(0));
+    assert_eq!(mul_div_ceil(1, 1, 10_000), Some(1));
+}
+
+#[test]
+fn intermediate_product_does_not_overflow() {
+    assert_eq!(mul_div_floor(u64::MAX, u64::MAX, u64::MAX), Some(u64::MAX));
+    assert_eq!(mul_div_ceil(u64::MAX, u64::MAX - 1, u64::MAX), Some(u64::MAX - 1));
+}
+
+#[test]
+fn zero_divisor_and_oversized_results_fail() {
+    assert_eq!(mul_div_floor(1, 1, 0), None);
+    assert_eq!(mul_div_ceil(1, 1, 0), None);
+    assert_eq!(mul_div_floor(u64::MAX, 2, 1), None);
+    // ⌊(2^64 − 1) × 2 / 2⌋ fits, the ceiling of one more does not
+    assert_eq!(mul_div_floor(u64::MAX, 2, 2), Some(u64::MAX));
+    assert_eq!(mul_div_ceil(u64::MAX, 3, 2), None);
+}
@@ -0,0 +1,38 @@
+use aegis_core::{calculate_conf_bps, calculate_cross_rate_bps, calculate_peg_bps, two_asset_peg_bps};
+
+#[test]
+fn peg_deviation_is_symmetric_around_one_dollar() {
+    assert_eq!(calculate_peg_bps(1_000_000, None), 0);
+    assert_eq!(calculate_peg_bps(999_000, None), 10);
+    assert_eq!(calculate_peg_bps(1_001_000, None), 10);
+}
+
+#[test]
+fn peg_deviation_uses_the_worst_conf_edge() {
+    assert_eq!(calculate_peg_bps(999_000, Some(1_500)), 25);
+    assert_eq!(calculate_peg_bps(1_000_000, Some(0)), 0);
+}
+
+#[test]
+fn peg_deviation_saturates() {
+    assert_eq!(calculate_peg_bps(0, None), 10_000);
+    assert_eq!(calculate_peg_bps(i64::MAX, None), u16::MAX);
+    assert_eq!(calculate_peg_bps(i64::MIN, Some(u64::MAX)), u16::MAX);
+}
+
+#[test]
+fn conf_bps() {
+    assert_eq!(calculate_conf_bps(1_000_000, 1_000), 10);
+    assert_eq!(calculate_conf_bps(0, 1), u16::MAX);
+    assert_eq!(calculate_conf_bps(-5, 0), u16::MAX);
+    assert_eq!(calculate_conf_bps(1, u64::MAX), u16::MAX);
+}
+
+#[test]
+fn cross_rate() {
+    assert_eq!(calculate_cross_rate_bps(1_000_000, 997_000, None), 30);
+    assert_eq!(calculate_cross_rate_bps(995_000, 995_000, None), 0);
+    assert_eq!(calculate_cross_rate_bps(1_000_000, 1_000_000, Some((500, 500))), 10);
+    assert_eq!(calculate_cross_rate_bps(1_000_000, 0, None), u16::MAX);
+    assert_eq!(two_asset_peg_bps(8, 8, 16), 16);
+}
@@ -0,0 +1,167 @@
+use aegis_core::{
+    assets_for_shares, calculate_repay_amount, calculate_target_borrow, depositor_shares, equity,
+    leverage_bps, shares_for_deposit, shares_for_withdrawal, total_assets, DEAD_SHARES,
+};
+
+#[test]
+fn first_deposit_mints_one_to_one() {
+    assert_eq!(shares_for_deposit(1_000_000_000, 0, 0), Some(1_000_000_000));
+    assert_eq!(shares_for_deposit(0, 0, 0), Some(0));
+}
+
+#[test]
+fn first_deposit_locks_dead_shares() {
+    assert_eq!(
+        depositor_shares(1_000_000_000, 0),
+        Some(1_000_000_000 - DEAD_SHARES)
+    );
+    assert_eq!(depositor_shares(DEAD_SHARES + 1, 0), Some(1));
+    assert_eq!(depositor_shares(DEAD_SHARES, 0), None);
+    assert_eq!(depositor_shares(1, 0), None);
+    // Later deposits keep everything they mint
+    assert_eq!(depositor_shares(1, DEAD_SHARES), Some(1));
+}
+
+#[test]
+fn dead_shares_cap_a_donation_attack() {
+    // The attacker seeds the smallest first deposit, then donates 10,000 USDC
+    let seed = DEAD_SHARES + 1;
+    let donation = 10_000_000_000u64;
+    let attacker = depositor_shares(seed, 0).unwrap();
+    let (total_shares, equity) = (seed, seed + donation);
+
+    // A 1,000 USDC victim loses under one share's worth, not their deposit
+    let victim = 1_000_000_000u64;
+    let shares = shares_for_deposit(victim, total_shares, equity).unwrap();
+    let (total_shares, equity) = (total_shares + shares, equity + victim);
+    let redeemable = assets_for_shares(shares, total_shares, equity).unwrap();
+    assert!(victim - redeemable <= 2 + donation / DEAD_SHARES);
+
+    // and the attacker recovers far less than they donated
+    let recovered = assets_for_shares(attacker, total_shares, equity).unwrap();
+    assert!(recovered < donation / 100);
+}
+
+#[test]
+fn later_deposits_are_pro_rata_rounded_down() {
+    // 1000 shares backed by 1100 equity: 100 deposited → 90.9 shares
+    assert_eq!(shares_for_deposit(100, 1_000, 1_100), Some(90));
+    assert_eq!(shares_for_deposit(1_000, 1_000, 1_000), Some(1_000));
+}
+
+#[test]
+fn deposit_into_shares_without_equity_fails() {
+    assert_eq!(shares_for_deposit(100, 1_000, 0), None);
+}
+
+#[test]
+fn deposit_overflow_fails() {
+    assert_eq!(shares_for_deposit(u64::MAX, u64::MAX, 1), None);
+}
+
+#[test]
+fn withdrawals_are_pro_rata_rounded_down() {
+    assert_eq!(assets_for_shares(91, 1_000, 1_100), Some(100));
+    assert_eq!(assets_for_shares(1_000, 1_000, 1_100), Some(1_100));
+    assert_eq!(assets_for_shares(1, 3, 2), Some(0));
+}
+
+#[test]
+fn withdrawal_without_shares_fails() {
+    assert_eq!(assets_for_shares(1, 0, 1_000), None);
+}
+
+#[test]
+fn round_trip_never_pays_out_more_than_deposited() {
+    for total_shares in [1u64, 7, 1_000, 999_999] {
+        for equity in [1u64, 3, 1_001, 1_000_000] {
+            for amount in [0u64, 1, 2, 99, 12_345] {
+                let shares = shares_for_deposit(amount, total_shares, equity).unwrap();
+                let out = assets_for_shares(shares, total_shares + shares, equity + amount).unwrap();
+                assert!(out <= amount, "{amount} {total_shares} {equity}");
+            }
+        }
+    }
+}
+
+#[test]
+fn exact_withdrawals_round_shares_up() {
+    // 100 out of 1100 equity over 1000 shares: 90.9 → 91 shares burned
+    assert_eq!(shares_for_withdrawal(100, 1_000, 1_100), Some(91));
+    assert_eq!(shares_for_withdrawal(1_100, 1_000, 1_100), Some(1_000));
+    assert_eq!(shares_for_withdrawal(0, 1_000, 1_100), Some(0));
+    assert_eq!(shares_for_withdrawal(1, 1_000, 0), None);
+}
+
+#[test]
+fn repeated_tiny_deposits_cannot_extract_dust() {
+    // 1.1 shares per unit: each 1-unit deposit mints 1 share instead of 1.1
+    let (mut total_shares, mut assets) = (1_100u64, 1_000u64);
+    let mut minted = 0;
+    for _ in 0..10_000 {
+        let shares = shares_for_deposit(1, total_shares, assets).unwrap();
+        total_shares += shares;
+        assets += 1;
+        minted += shares;
+    }
+    assert!(minted <= shares_for_deposit(10_000, 1_100, 1_000).unwrap());
+    assert!(assets_for_shares(minted, total_shares, assets).unwrap() <= 10_000);
+}
+
+#[test]
+fn repeated_tiny_withdrawals_cannot_extract_dust() {
+    // 1.999 units per share: redeeming one share at a time pays 1 each
+    let (mut total_shares, mut assets) = (1_000u64, 1_999u64);
+    let mut paid = 0;
+    for _ in 0..500 {
+        let out = assets_for_shares(1, total_shares, assets).unwrap();
+        total_shares -= 1;
+        assets -= out;
+        paid += out;
+    }
+    assert!(paid <= assets_for_shares(500, 1_000, 1_999).unwrap());
+
+    // Exact 1-unit payouts at 1.1 shares per unit burn 2 shares each
+    let (mut total_shares, mut assets) = (1_100u64, 1_000u64);
+    let mut burned = 0;
+    for _ in 0..500 {
+        let shares = shares_for_withdrawal(1, total_shares, assets).unwrap();
+        total_shares -= shares;
+        assets -= 1;
+        burned += shares;
+    }
+    assert!(burned >= shares_for_withdrawal(500, 1_100, 1_000).unwrap());
+}
+
+#[test]
+fn equity_and_leverage() {
+    assert_eq!(equity(1_500, 500), 1_000);
+    assert_eq!(equity(500, 1_500), 0);
+
+    // Idle cash counts towards NAV alongside venue equity
+    assert_eq!(total_assets(1_500, 500, 300), Some(1_300));
+    assert_eq!(total_assets(0, 0, 1_000), Some(1_000));
+    assert_eq!(total_assets(u64::MAX, 0, 1), None);
+
+    assert_eq!(leverage_bps(1_500, 500), 15_000);
+    assert_eq!(leverage_bps(1_000, 0), 10_000);
+    assert_eq!(leverage_bps(0, 0), 0);
+    assert_eq!(leverage_bps(500, 1_500), 0);
+    // 10x saturates
+    assert_eq!(leverage_bps(10_000, 9_000), u16::MAX);
+}
+
+#[test]
+fn leverage_sizing() {
+    // 1.5x on 1500 supplied borrows 500
+    assert_eq!(calculate_target_borrow(1_500, 15_000), 500);
+    assert_eq!(calculate_target_borrow(1_000, 10_000), 0);
+    assert_eq!(
+        leverage_bps(3_000, calculate_target_borrow(3_000, 20_000)),
+        20_000
+    );
+
+    assert_eq!(calculate_repay_amount(1_000, 50), 500);
+    assert_eq!(calculate_repay_amount(1_001, 25), 250);
+    assert_eq!(calculate_repay_amount(u64::MAX, 100), u64::MAX);
+}
@@ -0,0 +1,126 @@
+use aegis_core::{determine_vault_state, Conditions, Thresholds, VaultState};
+
+const PRD: Thresholds = Thresholds {
+    peg_warn_bps: 10,
+    peg_exit_bps: 25,
+    peg_panic_bps: 50,
+    hf_floor_bps: 24_000,
+    stale_grace_slots: 750,
+};
+
+const HEALTHY: Conditions = Conditions {
+    peg_bps: 0,
+    peg_window_max_bps: 0,
+    oracle_stale_for: None,
+    oracle_quorum_lost: false,
+    oracle_unreliable: false,
+    health_factor_bps: 31_200,
+};
+
+fn state(conditions: Conditions) -> VaultState {
+    determine_vault_state(&conditions, &PRD)
+}
+
+/// LOOP (0) < IDLE (1) < CONTRACT (2) < EXIT (3) < PANIC (4)
+fn severity(state: VaultState) -> u8 {
+    match state {
+        VaultState::Loop => 0,
+        VaultState::Idle => 1,
+        VaultState::Contract => 2,
+        VaultState::Exit => 3,
+        VaultState::Panic => 4,
+    }
+}
+
+const PEGS: [u16; 12] = [0, 5, 9, 10, 11, 24, 25, 26, 49, 50, 51, u16::MAX];
+const STALE: [Option<u64>; 5] = [None, Some(0), Some(749), Some(750), Some(751)];
+const HFS: [u16; 4] = [0, 23_999, 24_000, 31_200];
+
+/// Every combination of the boundary values above
+fn all_conditions() -> impl Iterator<Item = Conditions> {
+    PEGS.into_iter().flat_map(|peg_bps| {
+        PEGS.into_iter().flat_map(move |peg_window_max_bps| {
+            STALE.into_iter().flat_map(move |oracle_stale_for| {
+                [false, true].into_iter().flat_map(move |oracle_quorum_lost| {
+                    [false, true].into_iter().flat_map(move |oracle_unreliable| {
+                        HFS.into_iter().map(move |health_factor_bps| Conditions {
+                            peg_bps,
+                            peg_window_max_bps,
+                            oracle_stale_for,
+                            oracle_quorum_lost,
+                            oracle_unreliable,
+                            health_factor_bps,
+                        })
+                    })
+                })
+            })
+        })
+    })
+}
+
+#[test]
+fn prd_transitions() {
+    assert_eq!(state(HEALTHY), VaultState::Loop);
+    assert_eq!(state(Conditions { peg_bps: 10, ..HEALTHY }), VaultState::Idle);
+    assert_eq!(state(Conditions { peg_bps: 11, ..HEALTHY }), VaultState::Contract);
+    assert_eq!(state(Conditions { peg_bps: 26, ..HEALTHY }), VaultState::Exit);
+    assert_eq!(state(Conditions { peg_bps: 51, ..HEALTHY }), VaultState::Panic);
+    assert_eq!(state(Conditions { health_factor_bps: 23_999, ..HEALTHY }), VaultState::Contract);
+    assert_eq!(state(Conditions { oracle_unreliable: true, ..HEALTHY }), VaultState::Contract);
+    assert_eq!(state(Conditions { peg_window_max_bps: 30, ..HEALTHY }), VaultState::Idle);
+    assert_eq!(state(Conditions { oracle_stale_for: Some(750), ..HEALTHY }), VaultState::Contract);
+    assert_eq!(state(Conditions { oracle_stale_for: Some(751), ..HEALTHY }), VaultState::Exit);
+    assert_eq!(state(Conditions { oracle_quorum_lost: true, ..HEALTHY }), VaultState::Panic);
+}
+
+#[test]
+fn panic_exactly_on_lost_quorum_or_severe_depeg() {
+    for c in all_conditions() {
+        let expect_panic = c.oracle_quorum_lost || c.peg_bps > PRD.peg_panic_bps;
+        assert_eq!(state(c) == VaultState::Panic, expect_panic, "{c:?}");
+    }
+}
+
+#[test]
+fn loop_only_when_everything_is_healthy() {
+    for c in all_conditions() {
+        let healthy = c.peg_bps < PRD.peg_warn_bps
+            && c.peg_window_max_bps < PRD.peg_warn_bps
+            && c.oracle_stale_for.is_none()
+            && !c.oracle_quorum_lost
+            && !c.oracle_unreliable
+            && c.health_factor_bps >= PRD.hf_floor_bps;
+        assert_eq!(state(c) == VaultState::Loop, healthy, "{c:?}");
+    }
+}
+
+#[test]
+fn stale_oracle_never_loops_and_escalates_after_grace() {
+    for c in all_conditions() {
+        match c.oracle_stale_for {
+            Some(slots) if slots > PRD.stale_grace_slots => {
+                assert!(severity(state(c)) >= severity(VaultState::Exit), "{c:?}")
+            }
+            Some(_) => assert!(severity(state(c)) >= severity(VaultState::Contract), "{c:?}"),
+            None => {}
+        }
+    }
+}
+
+#[test]
+fn severity_is_monotonic_in_every_input() {
+    for c in all_conditions() {
+        let s = severity(state(c));
+        let worse = [
+            Conditions { peg_bps: c.peg_bps.saturating_add(1), ..c },
+            Conditions { peg_window_max_bps: c.peg_window_max_bps.saturating_add(1), ..c },
+            Conditions { oracle_stale_for: Some(c.oracle_stale_for.map_or(0, |s| s + 1)), ..c },
+            Conditions { oracle_quorum_lost: true, ..c },
+            Conditions { oracle_unreliable: true, ..c },
+            Conditions { health_factor_bps: c.health_factor_bps.saturating_sub(1), ..c },
+        ];
+        for w in worse {
+            assert!(severity(state(w)) >= s, "{c:?} → {w:?}");
+        }
+    }
+}
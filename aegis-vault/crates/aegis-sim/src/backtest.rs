@@ -0,0 +1,265 @@
+// Historical backtest: replays a timestamped price series and a lending-rate
+// series through the same vault model as the simulator. Each price sample is
+// an oracle publish (and a `record_observation` crank), the keeper calls
+// rebalance every cooldown — retrying reverts after `retry_slots` — and the
+// position accrues venue interest in between.
+//
+// Inputs (CSV with headers, timestamps in unix seconds, any order):
+//   prices: timestamp,price                   collateral price in debt-asset units
+//   rates:  timestamp,supply_apy,borrow_apy   decimals (0.05 = 5%), step function
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+use aegis_core::{leverage_bps, VaultState};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{VaultParams, SLOT_SECONDS};
+use crate::vault::{serialize_state, state_name, VaultModel, PRICE_SCALE};
+
+#[derive(Debug, Clone, Parser, Serialize)]
+#[command(name = "aegis-backtest", about = "Replay historical prices through the Aegis vault")]
+pub struct BacktestConfig {
+    /// Price CSV: timestamp,price
+    #[arg(long)]
+    pub prices: PathBuf,
+    /// Lending-rate CSV: timestamp,supply_apy,borrow_apy
+    #[arg(long)]
+    pub rates: PathBuf,
+
+    #[command(flatten)]
+    #[serde(flatten)]
+    pub vault: VaultParams,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct PricePoint {
+    pub timestamp: i64,
+    pub price: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct RatePoint {
+    pub timestamp: i64,
+    pub supply_apy: f64,
+    pub borrow_apy: f64,
+}
+
+fn read_series<T: for<'de> Deserialize<'de>>(path: &Path) -> io::Result<Vec<T>> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let rows = reader.deserialize().collect::<Result<Vec<T>, _>>()?;
+    if rows.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{} is empty", path.display())));
+    }
+    Ok(rows)
+}
+
+pub fn read_prices(path: &Path) -> io::Result<Vec<PricePoint>> {
+    let mut prices: Vec<PricePoint> = read_series(path)?;
+    prices.sort_by_key(|p| p.timestamp);
+    Ok(prices)
+}
+
+pub fn read_rates(path: &Path) -> io::Result<Vec<RatePoint>> {
+    let mut rates: Vec<RatePoint> = read_series(path)?;
+    rates.sort_by_key(|r| r.timestamp);
+    Ok(rates)
+}
+
+/// One rebalance decision: logged when the state changes or a rebalance lands
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineRow {
+    pub timestamp: i64,
+    pub slot: u64,
+    pub price: f64,
+    pub peg_bps: u16,
+    #[serde(serialize_with = "serialize_state")]
+    pub state: VaultState,
+    /// False when `rebalance` reverted (PANIC, or LOOP inside the re-expansion delay)
+    pub landed: bool,
+    pub equity: f64,
+    pub leverage_bps: u16,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StateTime {
+    pub seconds: f64,
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestSummary {
+    pub start: i64,
+    pub end: i64,
+    pub days: f64,
+    pub initial_equity: f64,
+    pub final_equity: f64,
+    /// Annualised return on starting equity
+    pub realized_apy: f64,
+    /// Largest peak-to-trough fall in equity, bps of the peak
+    pub max_drawdown_bps: u32,
+    pub time_in_state: BTreeMap<&'static str, StateTime>,
+    pub transitions: u32,
+    pub rebalances: u32,
+    pub liquidated: bool,
+    /// Slippage and flash fees paid, in debt-asset units
+    pub execution_cost: f64,
+}
+
+pub struct Backtest {
+    pub timeline: Vec<TimelineRow>,
+    pub summary: BacktestSummary,
+}
+
+/// Rate in effect at `timestamp`: the latest point at or before it
+fn rate_at(rates: &[RatePoint], timestamp: i64) -> (f64, f64) {
+    let index = rates.partition_point(|r| r.timestamp <= timestamp).saturating_sub(1);
+    rates.get(index).map_or((0.0, 0.0), |r| (r.supply_apy, r.borrow_apy))
+}
+
+/// Replay sorted `prices` and `rates` through a vault configured by `params`
+pub fn run_backtest(params: &VaultParams, prices: &[PricePoint], rates: &[RatePoint]) -> Backtest {
+    let start = prices.first().map_or(0, |p| p.timestamp);
+    let end = prices.last().map_or(start, |p| p.timestamp);
+    let slot_of = |timestamp: i64| ((timestamp - start) as f64 / SLOT_SECONDS) as u64;
+    let timestamp_of = |slot: u64| start + (slot as f64 * SLOT_SECONDS) as i64;
+    let to_e6 = |price: f64| (price * PRICE_SCALE).round() as i64;
+    let end_slot = slot_of(end);
+
+    let mut vault = VaultModel::new(params);
+    let mut now = 0u64;
+    let mut sample = 0usize;
+    let mut published = (0u64, to_e6(prices.first().map_or(1.0, |p| p.price)));
+
+    let initial_equity = vault.equity(published.1);
+    let mut peak_equity = initial_equity;
+    let mut max_drawdown_bps = 0u32;
+    let mut track_drawdown = |equity: i64| {
+        peak_equity = peak_equity.max(equity);
+        if peak_equity > 0 {
+            let drawdown = (peak_equity - equity).max(0) as u128 * 10_000 / peak_equity as u128;
+            max_drawdown_bps = max_drawdown_bps.max(drawdown.min(u32::MAX as u128) as u32);
+        }
+    };
+
+    let mut timeline = Vec::new();
+    let mut state_slots: BTreeMap<&'static str, u64> = BTreeMap::new();
+    let mut current: Option<(VaultState, u64)> = None;
+    let (mut transitions, mut rebalances) = (0u32, 0u32);
+    let mut next_rebalance = 0u64;
+
+    vault.record_observation(0, published.1, 0);
+    loop {
+        let next_sample = prices.get(sample + 1).map(|p| slot_of(p.timestamp));
+        let rebalance_due = next_rebalance <= end_slot
+            && match next_sample {
+                Some(slot) => next_rebalance <= slot,
+                None => true,
+            };
+        let target = match (rebalance_due, next_sample) {
+            (true, _) => next_rebalance,
+            (false, Some(slot)) => slot,
+            (false, None) => break,
+        };
+
+        let (supply_apy, borrow_apy) = rate_at(rates, timestamp_of(now));
+        vault.accrue(supply_apy, borrow_apy, target - now);
+        now = target;
+
+        if !rebalance_due {
+            // --- Oracle publish + crank ---
+            sample += 1;
+            published = (now, to_e6(prices[sample].price));
+            vault.record_observation(now, published.1, 0);
+            vault.check_liquidation(published.1);
+            track_drawdown(vault.equity(published.1));
+            continue;
+        }
+
+        // --- Keeper rebalance ---
+        let price = published.1;
+        let decision = vault.decide(now, price, now - published.0, price);
+        let state = decision.state;
+        let changed = !matches!(current, Some((s, _)) if s == state);
+        if let Some((previous, since)) = current {
+            *state_slots.entry(state_name(previous)).or_default() += now - since;
+            transitions += u32::from(changed);
+        }
+        current = Some((state, now));
+
+        let landed = !vault.reverts(state, now);
+        if landed {
+            vault.execute(&decision, now, price);
+            rebalances += 1;
+            next_rebalance = now + params.cooldown_slots;
+        } else {
+            next_rebalance = now + params.retry_slots.max(1);
+        }
+        if changed || landed {
+            timeline.push(TimelineRow {
+                timestamp: timestamp_of(now),
+                slot: now,
+                price: price as f64 / PRICE_SCALE,
+                peg_bps: decision.peg_bps,
+                state,
+                landed,
+                equity: vault.equity(price) as f64 / PRICE_SCALE,
+                leverage_bps: leverage_bps(vault.collateral_value(price), vault.borrowed),
+            });
+        }
+        track_drawdown(vault.equity(price));
+    }
+    if let Some((state, since)) = current {
+        *state_slots.entry(state_name(state)).or_default() += end_slot.saturating_sub(since);
+    }
+
+    let final_equity = vault.equity(published.1);
+    let days = (end - start) as f64 / 86_400.0;
+    let growth = final_equity as f64 / initial_equity.max(1) as f64;
+    let total_slots = state_slots.values().sum::<u64>().max(1) as f64;
+    Backtest {
+        timeline,
+        summary: BacktestSummary {
+            start,
+            end,
+            days,
+            initial_equity: initial_equity as f64 / PRICE_SCALE,
+            final_equity: final_equity as f64 / PRICE_SCALE,
+            realized_apy: if days > 0.0 && growth > 0.0 { growth.powf(365.0 / days) - 1.0 } else { 0.0 },
+            max_drawdown_bps,
+            time_in_state: state_slots
+                .into_iter()
+                .map(|(state, slots)| {
+                    let time = StateTime { seconds: slots as f64 * SLOT_SECONDS, share: slots as f64 / total_slots };
+                    (state, time)
+                })
+                .collect(),
+            transitions,
+            rebalances,
+            liquidated: vault.liquidated,
+            execution_cost: vault.execution_cost / PRICE_SCALE,
+        },
+    }
+}
+
+pub fn write_timeline(path: &Path, timeline: &[TimelineRow]) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(BufWriter::new(File::create(path)?));
+    for row in timeline {
+        writer.serialize(row)?;
+    }
+    writer.flush()
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    config: &'a BacktestConfig,
+    summary: &'a BacktestSummary,
+}
+
+/// Summary together with the configuration that produced it
+pub fn write_summary(path: &Path, config: &BacktestConfig, summary: &BacktestSummary) -> io::Result<()> {
+    let file = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(file, &Report { config, summary }).map_err(io::Error::from)
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use aegis_sim::backtest::{self, BacktestConfig};
+use clap::Parser;
+
+#[derive(Parser)]
+#[command(name = "aegis-backtest", about = "Replay historical prices through the Aegis vault")]
+struct Cli {
+    /// Directory for timeline.csv and summary.json
+    #[arg(long, default_value = "backtest-out")]
+    out_dir: PathBuf,
+
+    #[command(flatten)]
+    config: BacktestConfig,
+}
+
+fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+    let config = cli.config;
+
+    let prices = backtest::read_prices(&config.prices)?;
+    let rates = backtest::read_rates(&config.rates)?;
+    eprintln!("Replaying {} prices and {} rate points...", prices.len(), rates.len());
+    let result = backtest::run_backtest(&config.vault, &prices, &rates);
+
+    std::fs::create_dir_all(&cli.out_dir)?;
+    backtest::write_timeline(&cli.out_dir.join("timeline.csv"), &result.timeline)?;
+    backtest::write_summary(&cli.out_dir.join("summary.json"), &config, &result.summary)?;
+
+    println!("{}", serde_json::to_string_pretty(&result.summary)?);
+    eprintln!("Wrote {}", cli.out_dir.display());
+    Ok(())
+}
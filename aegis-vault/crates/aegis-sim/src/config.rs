@@ -0,0 +1,166 @@
+// Simulation parameters. Vault parameters default to `initialize_vault`'s
+// values so a run with no flags evaluates the configuration that ships.
+use aegis_core::Thresholds;
+use clap::{Args, Parser};
+use serde::Serialize;
+
+/// Solana slot time used to convert between slots and wall-clock time
+pub const SLOT_SECONDS: f64 = 0.4;
+
+pub const SLOTS_PER_DAY: f64 = 86_400.0 / SLOT_SECONDS;
+
+pub const SLOTS_PER_YEAR: f64 = 365.0 * SLOTS_PER_DAY;
+
+/// The vault and its lending venue, shared by the simulator and the backtest
+#[derive(Debug, Clone, Args, Serialize)]
+pub struct VaultParams {
+    // --- Position ---
+    /// Starting equity, in debt-asset units (USD)
+    #[arg(long, default_value_t = 1_000_000.0)]
+    pub initial_equity: f64,
+    /// Venue liquidation threshold: collateral value counted towards debt
+    #[arg(long, default_value_t = 8_500)]
+    pub liquidation_threshold_bps: u16,
+    /// Collateral bonus paid to the liquidator
+    #[arg(long, default_value_t = 500)]
+    pub liquidation_bonus_bps: u16,
+    #[arg(long, default_value_t = 0)]
+    pub flash_fee_bps: u16,
+
+    // --- Vault (as set by initialize_vault / set_* instructions) ---
+    #[arg(long, default_value_t = 15_000)]
+    pub max_leverage_bps: u16,
+    #[arg(long, default_value_t = 24_000)]
+    pub hf_floor_bps: u16,
+    #[arg(long, default_value_t = 10)]
+    pub peg_warn_bps: u16,
+    #[arg(long, default_value_t = 25)]
+    pub peg_exit_bps: u16,
+    #[arg(long, default_value_t = 50)]
+    pub peg_panic_bps: u16,
+    /// Oracle age after which it reads as stale
+    #[arg(long, default_value_t = 150)]
+    pub oracle_stale_slots: u64,
+    #[arg(long, default_value_t = 750)]
+    pub stale_grace_slots: u64,
+    #[arg(long, default_value_t = 30_000)]
+    pub cooldown_slots: u64,
+    #[arg(long, default_value_t = 30_000)]
+    pub reexpansion_delay_sec: u64,
+    #[arg(long, default_value_t = 750)]
+    pub twap_window_slots: u64,
+    #[arg(long, default_value_t = 25)]
+    pub observation_interval_slots: u64,
+
+    // --- Keeper and execution ---
+    /// Slots before the keeper resubmits a failed or reverted rebalance
+    #[arg(long, default_value_t = 25)]
+    pub retry_slots: u64,
+    /// Fixed slippage on every collateral/debt swap
+    #[arg(long, default_value_t = 5.0)]
+    pub base_slippage_bps: f64,
+    /// Additional slippage per million of debt-asset notional swapped
+    #[arg(long, default_value_t = 10.0)]
+    pub impact_bps_per_million: f64,
+}
+
+impl Default for VaultParams {
+    fn default() -> Self {
+        #[derive(Parser)]
+        struct Wrapper {
+            #[command(flatten)]
+            params: VaultParams,
+        }
+        Wrapper::parse_from(["aegis-sim"]).params
+    }
+}
+
+impl VaultParams {
+    pub fn thresholds(&self) -> Thresholds {
+        Thresholds {
+            peg_warn_bps: self.peg_warn_bps,
+            peg_exit_bps: self.peg_exit_bps,
+            peg_panic_bps: self.peg_panic_bps,
+            hf_floor_bps: self.hf_floor_bps,
+            stale_grace_slots: self.stale_grace_slots,
+        }
+    }
+
+    pub fn reexpansion_delay_slots(&self) -> u64 {
+        (self.reexpansion_delay_sec as f64 / SLOT_SECONDS) as u64
+    }
+}
+
+#[derive(Debug, Clone, Parser, Serialize)]
+#[command(name = "aegis-sim", about = "Monte Carlo unwind simulator for the Aegis vault")]
+pub struct SimConfig {
+    // --- Run ---
+    /// Number of simulated paths
+    #[arg(long, default_value_t = 10_000)]
+    pub paths: u32,
+    /// RNG seed; path `i` uses stream `i` of this seed
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+    /// Simulated time per path, in days
+    #[arg(long, default_value_t = 7.0)]
+    pub horizon_days: f64,
+
+    #[command(flatten)]
+    #[serde(flatten)]
+    pub vault: VaultParams,
+
+    // --- Collateral price (mean-reverting log price with downward jumps) ---
+    /// Annualised volatility of the collateral/debt price
+    #[arg(long, default_value_t = 0.01)]
+    pub volatility_annual: f64,
+    /// Half-life of reversion to the peg, in hours (0 = no reversion)
+    #[arg(long, default_value_t = 24.0)]
+    pub reversion_half_life_hours: f64,
+    /// Expected depeg jumps per day
+    #[arg(long, default_value_t = 0.05)]
+    pub jump_rate_per_day: f64,
+    /// Mean jump size (exponentially distributed)
+    #[arg(long, default_value_t = 40.0)]
+    pub jump_mean_bps: f64,
+
+    // --- Oracle ---
+    /// Expected oracle outages per day
+    #[arg(long, default_value_t = 0.5)]
+    pub oracle_outage_rate_per_day: f64,
+    /// Mean outage length (exponentially distributed)
+    #[arg(long, default_value_t = 600.0)]
+    pub oracle_outage_mean_slots: f64,
+
+    // --- Network ---
+    /// Probability that a keeper transaction fails to land
+    #[arg(long, default_value_t = 0.05)]
+    pub tx_fail_rate: f64,
+    /// Extra failure probability while the peg is past the warn threshold
+    #[arg(long, default_value_t = 0.15)]
+    pub stress_fail_rate: f64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self::parse_from(["aegis-sim"])
+    }
+}
+
+impl SimConfig {
+    pub fn horizon_slots(&self) -> u64 {
+        (self.horizon_days * SLOTS_PER_DAY) as u64
+    }
+
+    /// Log-price volatility per slot
+    pub fn volatility_per_slot(&self) -> f64 {
+        self.volatility_annual / SLOTS_PER_YEAR.sqrt()
+    }
+
+    /// Log-price reversion speed per slot
+    pub fn reversion_per_slot(&self) -> f64 {
+        if self.reversion_half_life_hours <= 0.0 {
+            return 0.0;
+        }
+        std::f64::consts::LN_2 / (self.reversion_half_life_hours * 3_600.0 / SLOT_SECONDS)
+    }
+}
@@ -0,0 +1,155 @@
+// One simulated path: a keeper calls rebalance every cooldown against a
+// stochastic market, and `record_observation` is cranked through each TWAP
+// window. Failed transactions (and PANIC, which the program rejects) leave
+// the cooldown untouched and the keeper resubmits after `retry_slots`.
+use aegis_core::VaultState;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
+
+use crate::config::SimConfig;
+use crate::market::Market;
+use crate::vault::{serialize_state, severity, VaultModel, PRICE_SCALE};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PathResult {
+    pub path: u32,
+    #[serde(serialize_with = "serialize_state")]
+    pub final_state: VaultState,
+    /// Most severe state the vault decided on
+    #[serde(serialize_with = "serialize_state")]
+    pub worst_state: VaultState,
+    pub transitions: u32,
+    pub rebalances: u32,
+    pub failed_txs: u32,
+    /// Equity lost over the horizon, in bps of the starting equity (negative = gain)
+    pub loss_bps: i64,
+    /// Slippage and flash fees paid, in debt-asset units
+    pub execution_cost: f64,
+    /// Longest de-leverage: first CONTRACT/EXIT/PANIC decision to zero debt, in slots
+    pub unwind_slots: Option<u64>,
+    pub liquidated: bool,
+    /// Worst spot deviation seen by the vault
+    pub max_peg_bps: u16,
+}
+
+struct Sim<'a> {
+    config: &'a SimConfig,
+    market: Market,
+    rng: ChaCha8Rng,
+    vault: VaultModel<'a>,
+}
+
+impl Sim<'_> {
+    /// Move the market to `slot`, cranking observations through the TWAP
+    /// window and checking for liquidation along the way
+    fn advance_to(&mut self, slot: u64) {
+        let window_start = slot.saturating_sub(self.config.vault.twap_window_slots);
+        if self.market.slot < window_start {
+            let gap = window_start - self.market.slot;
+            self.market.advance(gap, self.config, &mut self.rng);
+            self.after_step();
+        }
+        let interval = self.config.vault.observation_interval_slots.max(1);
+        while self.market.slot < slot && !self.vault.liquidated {
+            let step = interval.min(slot - self.market.slot);
+            self.market.advance(step, self.config, &mut self.rng);
+            self.after_step();
+        }
+    }
+
+    fn after_step(&mut self) {
+        let market = &self.market;
+        self.vault
+            .record_observation(market.slot, market.published_price, market.oracle_age());
+        self.vault.check_liquidation(market.price());
+    }
+}
+
+/// Run path `index` of a simulation seeded with `config.seed`
+pub fn run_path(config: &SimConfig, index: u32) -> PathResult {
+    let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+    rng.set_stream(index as u64);
+
+    let mut sim = Sim { config, market: Market::new(), rng, vault: VaultModel::new(&config.vault) };
+    sim.after_step();
+    let initial_equity = sim.vault.equity(sim.market.price());
+
+    let horizon = config.horizon_slots();
+    let mut next_rebalance = 0u64;
+    let mut previous_state: Option<VaultState> = None;
+    let mut worst_state = VaultState::Loop;
+    let (mut transitions, mut rebalances, mut failed_txs) = (0u32, 0u32, 0u32);
+    let mut max_peg_bps = 0u16;
+    let mut unwind_started: Option<u64> = None;
+    let mut unwind_slots: Option<u64> = None;
+
+    while next_rebalance <= horizon && !sim.vault.liquidated {
+        sim.advance_to(next_rebalance);
+        if sim.vault.liquidated {
+            break;
+        }
+        let slot = sim.market.slot;
+        let price = sim.market.price();
+
+        let decision = sim
+            .vault
+            .decide(slot, sim.market.published_price, sim.market.oracle_age(), price);
+        let state = decision.state;
+        max_peg_bps = max_peg_bps.max(decision.spot_peg_bps);
+        if severity(state) > severity(worst_state) {
+            worst_state = state;
+        }
+        if previous_state.is_some_and(|p| p != state) {
+            transitions += 1;
+        }
+        previous_state = Some(state);
+
+        // The unwind clock starts at the first de-risking decision, landed or not
+        if severity(state) > 0 && sim.vault.borrowed > 0 {
+            unwind_started.get_or_insert(slot);
+        }
+
+        // --- Submit: reverts and dropped transactions are retried ---
+        let fail_rate = config.tx_fail_rate
+            + if decision.peg_bps > config.vault.peg_warn_bps { config.stress_fail_rate } else { 0.0 };
+        let reverted = sim.vault.reverts(state, slot);
+        if reverted || sim.rng.gen::<f64>() < fail_rate {
+            failed_txs += u32::from(!reverted);
+            next_rebalance = slot + config.vault.retry_slots.max(1);
+            continue;
+        }
+        rebalances += 1;
+
+        sim.vault.execute(&decision, slot, price);
+        if state == VaultState::Loop {
+            unwind_started = None;
+        }
+        if sim.vault.borrowed == 0 {
+            if let Some(started) = unwind_started.take() {
+                unwind_slots = unwind_slots.max(Some(slot - started));
+            }
+        }
+        next_rebalance = slot + config.vault.cooldown_slots;
+    }
+
+    let equity = sim.vault.equity(sim.market.price());
+    PathResult {
+        path: index,
+        final_state: previous_state.unwrap_or(VaultState::Idle),
+        worst_state,
+        transitions,
+        rebalances,
+        failed_txs,
+        loss_bps: ((initial_equity as i128 - equity as i128) * 10_000 / initial_equity.max(1) as i128) as i64,
+        execution_cost: sim.vault.execution_cost / PRICE_SCALE,
+        unwind_slots,
+        liquidated: sim.vault.liquidated,
+        max_peg_bps,
+    }
+}
+
+/// Run every path of the simulation
+pub fn run(config: &SimConfig) -> Vec<PathResult> {
+    (0..config.paths).map(|i| run_path(config, i)).collect()
+}
@@ -0,0 +1,25 @@
+// Monte Carlo unwind simulator. Replaces simulation.py: instead of
+// re-encoding the vault's rules it runs aegis-core's state machine and
+// leverage math through stochastic price paths, oracle outages and keeper
+// transaction failures, so threshold changes can be evaluated before deploy.
+//
+//   cargo run -p aegis-sim --release -- --peg-exit-bps 30 --out-dir sim-out
+//
+// writes sim-out/paths.csv (one row per path) and sim-out/summary.json
+// (loss and unwind-time percentiles plus the configuration used).
+//
+//   cargo run -p aegis-sim --release --bin aegis-backtest -- \
+//     --prices usdc-2023-03.csv --rates kamino-rates.csv --out-dir backtest-out
+//
+// replays a historical depeg through the same vault model (see `backtest`).
+pub mod backtest;
+pub mod config;
+pub mod engine;
+pub mod market;
+pub mod report;
+pub mod vault;
+
+pub use config::{SimConfig, VaultParams};
+pub use engine::{run, run_path, PathResult};
+pub use report::Summary;
+pub use vault::VaultModel;
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use aegis_sim::{report, run, SimConfig, Summary};
+use clap::Parser;
+
+#[derive(Parser)]
+#[command(name = "aegis-sim", about = "Monte Carlo unwind simulator for the Aegis vault")]
+struct Cli {
+    /// Directory for paths.csv and summary.json
+    #[arg(long, default_value = "sim-out")]
+    out_dir: PathBuf,
+
+    #[command(flatten)]
+    config: SimConfig,
+}
+
+fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+    let config = cli.config;
+
+    eprintln!(
+        "Simulating {} paths over {} days (seed {})...",
+        config.paths, config.horizon_days, config.seed
+    );
+    let results = run(&config);
+    let summary = Summary::new(&results);
+
+    std::fs::create_dir_all(&cli.out_dir)?;
+    report::write_csv(&cli.out_dir.join("paths.csv"), &results)?;
+    report::write_json(&cli.out_dir.join("summary.json"), &config, &summary)?;
+
+    println!("{}", serde_json::to_string_pretty(&summary)?);
+    eprintln!("Wrote {}", cli.out_dir.display());
+    Ok(())
+}
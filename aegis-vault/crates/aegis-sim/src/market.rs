@@ -0,0 +1,89 @@
+// Stochastic collateral price and oracle availability.
+// The log price follows an Ornstein-Uhlenbeck process around the peg
+// (discretised exactly, so step size does not change the distribution) with
+// Poisson downward jumps; the oracle stops publishing during Poisson outages.
+use rand::Rng;
+use rand_distr::{Distribution, Exp, StandardNormal};
+
+use crate::config::{SimConfig, SLOTS_PER_DAY};
+
+pub struct Market {
+    pub slot: u64,
+    /// ln(price), 0 at the peg
+    log_price: f64,
+    /// Last slot the oracle published, and the price it published (6dp)
+    pub publish_slot: u64,
+    pub published_price: i64,
+    outage_until: u64,
+}
+
+impl Market {
+    pub fn new() -> Self {
+        Self { slot: 0, log_price: 0.0, publish_slot: 0, published_price: 1_000_000, outage_until: 0 }
+    }
+
+    /// Price in 6dp, as the program sees it after normalisation
+    pub fn price(&self) -> i64 {
+        (1_000_000.0 * self.log_price.exp()).round() as i64
+    }
+
+    /// Slots since the oracle last published
+    pub fn oracle_age(&self) -> u64 {
+        self.slot - self.publish_slot
+    }
+
+    /// Move the price and the oracle forward by `slots`
+    pub fn advance<R: Rng>(&mut self, slots: u64, config: &SimConfig, rng: &mut R) {
+        if slots == 0 {
+            return;
+        }
+        let previous_price = self.price();
+        let dt = slots as f64;
+        let sigma = config.volatility_per_slot();
+        let kappa = config.reversion_per_slot();
+        let z: f64 = StandardNormal.sample(rng);
+
+        self.log_price = if kappa > 0.0 {
+            let decay = (-kappa * dt).exp();
+            let std_dev = sigma * ((1.0 - decay * decay) / (2.0 * kappa)).sqrt();
+            self.log_price * decay + std_dev * z
+        } else {
+            self.log_price + sigma * dt.sqrt() * z
+        };
+
+        let jump_rate = config.jump_rate_per_day / SLOTS_PER_DAY;
+        if config.jump_mean_bps > 0.0 && occurs(jump_rate, dt, rng) {
+            let jump_bps = Exp::new(1.0 / config.jump_mean_bps).unwrap().sample(rng);
+            self.log_price += (1.0 - (jump_bps / 10_000.0).min(0.99)).ln();
+        }
+
+        let outage_start = self.slot;
+        self.slot += slots;
+
+        let outage_rate = config.oracle_outage_rate_per_day / SLOTS_PER_DAY;
+        if self.outage_until <= outage_start
+            && config.oracle_outage_mean_slots > 0.0
+            && occurs(outage_rate, dt, rng)
+        {
+            let length = Exp::new(1.0 / config.oracle_outage_mean_slots).unwrap().sample(rng);
+            self.publish_slot = outage_start;
+            self.published_price = previous_price;
+            self.outage_until = outage_start + length as u64;
+        }
+        if self.slot >= self.outage_until {
+            self.publish_slot = self.slot;
+            self.published_price = self.price();
+        }
+    }
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// At least one event of a Poisson process with `rate` per slot in `dt` slots
+fn occurs<R: Rng>(rate: f64, dt: f64, rng: &mut R) -> bool {
+    rate > 0.0 && rng.gen::<f64>() < 1.0 - (-rate * dt).exp()
+}
@@ -0,0 +1,125 @@
+// Loss and unwind-time distributions across paths, written as CSV (one row
+// per path) and JSON (the summary plus the configuration that produced it).
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::config::{SimConfig, SLOT_SECONDS};
+use crate::engine::PathResult;
+use crate::vault::state_name;
+use aegis_core::VaultState;
+
+/// Nearest-rank percentile of an ascending slice
+pub fn percentile<T: Copy>(sorted: &[T], pct: f64) -> Option<T> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Percentiles<T> {
+    pub count: usize,
+    pub mean: f64,
+    pub p50: Option<T>,
+    pub p90: Option<T>,
+    pub p95: Option<T>,
+    pub p99: Option<T>,
+    pub max: Option<T>,
+}
+
+impl<T: Copy + Ord + Into<f64>> Percentiles<T> {
+    pub fn new(mut values: Vec<T>) -> Self {
+        values.sort_unstable();
+        let mean = match values.len() {
+            0 => 0.0,
+            n => values.iter().map(|v| (*v).into()).sum::<f64>() / n as f64,
+        };
+        Self {
+            count: values.len(),
+            mean,
+            p50: percentile(&values, 50.0),
+            p90: percentile(&values, 90.0),
+            p95: percentile(&values, 95.0),
+            p99: percentile(&values, 99.0),
+            max: values.last().copied(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    pub paths: usize,
+    /// Equity loss over the horizon, bps of starting equity
+    pub loss_bps: Percentiles<i32>,
+    /// Unwind time of paths that fully de-levered, in slots
+    pub unwind_slots: Percentiles<u32>,
+    pub unwind_seconds_p99: Option<f64>,
+    /// Share of paths by the most severe state decided (LOOP: never de-risked)
+    pub worst_state_share: BTreeMap<&'static str, f64>,
+    pub liquidation_rate: f64,
+    pub mean_failed_txs: f64,
+    pub mean_execution_cost: f64,
+}
+
+impl Summary {
+    pub fn new(results: &[PathResult]) -> Self {
+        let n = results.len().max(1) as f64;
+        let share = |state: VaultState| {
+            let count = results.iter().filter(|r| r.worst_state == state).count();
+            (state_name(state), count as f64 / n)
+        };
+        let unwind_slots = Percentiles::new(
+            results
+                .iter()
+                .filter_map(|r| r.unwind_slots)
+                .map(|s| s.min(u32::MAX as u64) as u32)
+                .collect(),
+        );
+        Self {
+            paths: results.len(),
+            loss_bps: Percentiles::new(
+                results.iter().map(|r| r.loss_bps.clamp(i32::MIN as i64, i32::MAX as i64) as i32).collect(),
+            ),
+            unwind_seconds_p99: unwind_slots.p99.map(|s| s as f64 * SLOT_SECONDS),
+            unwind_slots,
+            worst_state_share: [
+                VaultState::Loop,
+                VaultState::Contract,
+                VaultState::Exit,
+                VaultState::Panic,
+            ]
+            .into_iter()
+            .map(share)
+            .collect(),
+            liquidation_rate: results.iter().filter(|r| r.liquidated).count() as f64 / n,
+            mean_failed_txs: results.iter().map(|r| r.failed_txs as f64).sum::<f64>() / n,
+            mean_execution_cost: results.iter().map(|r| r.execution_cost).sum::<f64>() / n,
+        }
+    }
+}
+
+/// One row per path
+pub fn write_csv(path: &Path, results: &[PathResult]) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(BufWriter::new(File::create(path)?));
+    for result in results {
+        writer.serialize(result)?;
+    }
+    writer.flush()
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    config: &'a SimConfig,
+    summary: &'a Summary,
+}
+
+/// Summary together with the configuration that produced it
+pub fn write_json(path: &Path, config: &SimConfig, summary: &Summary) -> io::Result<()> {
+    let file = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(file, &Report { config, summary }).map_err(io::Error::from)
+}
@@ -0,0 +1,255 @@
+// Off-chain model of the vault and its venue position. Decisions go through
+// aegis-core exactly as `rebalance` makes them: oracle tracking with a
+// last-known-good price, peg TWAP and window max from cranked observations,
+// `determine_vault_state`, and borrow/repay sizing from the core leverage math.
+//
+// The collateral is priced against a debt asset held at the peg, so every
+// lever/delever is a swap that pays slippage, and the venue liquidates when
+// the health factor drops below 1.0.
+use std::collections::VecDeque;
+
+use aegis_core::{
+    calculate_peg_bps, calculate_repay_amount, calculate_target_borrow, determine_vault_state,
+    Conditions, VaultState,
+};
+
+use crate::config::{VaultParams, SLOTS_PER_YEAR};
+
+pub const PRICE_SCALE: f64 = 1_000_000.0;
+
+pub fn state_name(state: VaultState) -> &'static str {
+    match state {
+        VaultState::Idle => "IDLE",
+        VaultState::Loop => "LOOP",
+        VaultState::Contract => "CONTRACT",
+        VaultState::Exit => "EXIT",
+        VaultState::Panic => "PANIC",
+    }
+}
+
+pub fn serialize_state<S: serde::Serializer>(state: &VaultState, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(state_name(*state))
+}
+
+/// How defensive a state is; IDLE and LOOP are both 0
+pub fn severity(state: VaultState) -> u8 {
+    match state {
+        VaultState::Idle | VaultState::Loop => 0,
+        VaultState::Contract => 1,
+        VaultState::Exit => 2,
+        VaultState::Panic => 3,
+    }
+}
+
+/// What one rebalance decided, and the inputs it decided on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decision {
+    pub state: VaultState,
+    pub oracle_stale_for: Option<u64>,
+    pub spot_peg_bps: u16,
+    pub peg_bps: u16,
+}
+
+/// Vault accounting in 6dp units: collateral supplied, debt borrowed
+pub struct VaultModel<'a> {
+    params: &'a VaultParams,
+    pub supplied: u64,
+    pub borrowed: u64,
+    observations: VecDeque<(u64, i64)>,
+    last_good_price: i64,
+    stale_since_slot: Option<u64>,
+    reexpansion_unlocked_at: u64,
+    /// Slippage and flash fees paid, in debt-asset 6dp units
+    pub execution_cost: f64,
+    pub liquidated: bool,
+}
+
+impl<'a> VaultModel<'a> {
+    /// Unlevered vault holding `initial_equity` of collateral at the peg
+    pub fn new(params: &'a VaultParams) -> Self {
+        Self {
+            params,
+            supplied: (params.initial_equity * PRICE_SCALE) as u64,
+            borrowed: 0,
+            observations: VecDeque::new(),
+            last_good_price: 0,
+            stale_since_slot: None,
+            reexpansion_unlocked_at: 0,
+            execution_cost: 0.0,
+            liquidated: false,
+        }
+    }
+
+    pub fn collateral_value(&self, price: i64) -> u64 {
+        (self.supplied as u128 * price.max(0) as u128 / PRICE_SCALE as u128) as u64
+    }
+
+    pub fn equity(&self, price: i64) -> i64 {
+        self.collateral_value(price) as i64 - self.borrowed as i64
+    }
+
+    /// Venue health factor at the true price (bps, saturating)
+    pub fn health_factor_bps(&self, price: i64) -> u16 {
+        if self.borrowed == 0 {
+            return u16::MAX;
+        }
+        let weighted = self.collateral_value(price) as u128 * self.params.liquidation_threshold_bps as u128;
+        (weighted / self.borrowed as u128).min(u16::MAX as u128) as u16
+    }
+
+    /// `record_observation` crank: append a fresh oracle price if one is due
+    pub fn record_observation(&mut self, slot: u64, published_price: i64, oracle_age: u64) {
+        let due = match self.observations.back() {
+            Some((last, _)) => slot >= last + self.params.observation_interval_slots,
+            None => true,
+        };
+        if due && oracle_age <= self.params.oracle_stale_slots {
+            self.observations.push_back((slot, published_price));
+        }
+        // Keep one sample from before the window: it is still in effect at its start
+        let window_start = slot.saturating_sub(self.params.twap_window_slots);
+        while self.observations.len() > 1 && self.observations[1].0 <= window_start {
+            self.observations.pop_front();
+        }
+    }
+
+    /// Venue liquidation: debt repaid from collateral plus the liquidator's bonus
+    pub fn check_liquidation(&mut self, price: i64) -> bool {
+        if self.borrowed == 0 || self.health_factor_bps(price) >= 10_000 {
+            return false;
+        }
+        let bonus = 1.0 + self.params.liquidation_bonus_bps as f64 / 10_000.0;
+        let seized = (self.borrowed as f64 * bonus * PRICE_SCALE / price.max(1) as f64) as u64;
+        self.supplied = self.supplied.saturating_sub(seized);
+        self.borrowed = 0;
+        self.liquidated = true;
+        true
+    }
+
+    /// Accrue venue interest over `slots` at the given APYs
+    pub fn accrue(&mut self, supply_apy: f64, borrow_apy: f64, slots: u64) {
+        let years = slots as f64 / SLOTS_PER_YEAR;
+        let grow = |amount: u64, apy: f64| (amount as f64 * ((1.0 + apy).ln() * years).exp()) as u64;
+        self.supplied = grow(self.supplied, supply_apy);
+        self.borrowed = grow(self.borrowed, borrow_apy);
+    }
+
+    /// Time-weighted price over the window, weighted as `logic::twap` does
+    fn twap(&self, now: u64) -> Option<i64> {
+        let window_start = now.saturating_sub(self.params.twap_window_slots);
+        let (mut sum, mut weight_sum) = (0i128, 0i128);
+        for (i, &(slot, price)) in self.observations.iter().enumerate() {
+            let end = self.observations.get(i + 1).map_or(now, |next| next.0).min(now);
+            let weight = end.saturating_sub(slot.max(window_start)) as i128;
+            sum += price as i128 * weight;
+            weight_sum += weight;
+        }
+        match weight_sum {
+            0 => self.observations.back().map(|&(_, price)| price),
+            w => Some((sum / w) as i64),
+        }
+    }
+
+    fn window_max_peg_bps(&self, now: u64) -> u16 {
+        let window_start = now.saturating_sub(self.params.twap_window_slots);
+        self.observations
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.observations.get(i + 1).map_or(now, |next| next.0) > window_start)
+            .map(|(_, &(_, price))| calculate_peg_bps(price, None))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The state `rebalance` would decide at `slot`, given the oracle's last
+    /// published price and its age; `price` is the venue's (true) price
+    pub fn decide(&mut self, slot: u64, published_price: i64, oracle_age: u64, price: i64) -> Decision {
+        let is_stale = oracle_age > self.params.oracle_stale_slots;
+        let oracle_stale_for = if is_stale {
+            let since = *self.stale_since_slot.get_or_insert(slot);
+            Some(slot - since)
+        } else {
+            self.stale_since_slot = None;
+            self.last_good_price = published_price;
+            None
+        };
+        let spot_peg_bps = if is_stale {
+            calculate_peg_bps(self.last_good_price, None)
+        } else {
+            calculate_peg_bps(published_price, None)
+        };
+        let peg_bps = self.twap(slot).map_or(spot_peg_bps, |p| calculate_peg_bps(p, None));
+        let peg_window_max_bps = self.window_max_peg_bps(slot).max(spot_peg_bps);
+
+        let state = determine_vault_state(
+            &Conditions {
+                peg_bps,
+                peg_window_max_bps,
+                oracle_stale_for,
+                oracle_quorum_lost: false,
+                oracle_unreliable: false,
+                health_factor_bps: self.health_factor_bps(price),
+            },
+            &self.params.thresholds(),
+        );
+        Decision { state, oracle_stale_for, spot_peg_bps, peg_bps }
+    }
+
+    /// PANIC, and LOOP inside the re-expansion delay, make `rebalance` revert
+    pub fn reverts(&self, state: VaultState, slot: u64) -> bool {
+        state == VaultState::Panic || (state == VaultState::Loop && slot < self.reexpansion_unlocked_at)
+    }
+
+    /// Apply a landed rebalance
+    pub fn execute(&mut self, decision: &Decision, slot: u64, price: i64) {
+        match decision.state {
+            VaultState::Loop => self.lever(price),
+            VaultState::Contract => {
+                self.reexpansion_unlocked_at = slot + self.params.reexpansion_delay_slots();
+                let reduction_pct = if decision.oracle_stale_for.is_some() { 25 } else { 50 };
+                self.delever(calculate_repay_amount(self.borrowed, reduction_pct), price);
+            }
+            VaultState::Exit => self.delever(self.borrowed, price),
+            VaultState::Idle | VaultState::Panic => {}
+        }
+    }
+
+    fn slippage(&self, notional: u64) -> f64 {
+        let bps = self.params.base_slippage_bps
+            + self.params.impact_bps_per_million * notional as f64 / (1_000_000.0 * PRICE_SCALE);
+        (bps / 10_000.0).min(1.0)
+    }
+
+    fn flash_fee(&self, amount: u64) -> f64 {
+        amount as f64 * self.params.flash_fee_bps as f64 / 10_000.0
+    }
+
+    /// Borrow up to the target leverage and swap the proceeds into collateral
+    fn lever(&mut self, price: i64) {
+        let equity = self.collateral_value(price).saturating_sub(self.borrowed);
+        let target_supplied = (equity as u128 * self.params.max_leverage_bps as u128 / 10_000) as u64;
+        let target_borrowed = calculate_target_borrow(target_supplied, self.params.max_leverage_bps);
+        let Some(extra) = target_borrowed.checked_sub(self.borrowed).filter(|e| *e > 0) else {
+            return;
+        };
+        let slippage = self.slippage(extra);
+        let cost = extra as f64 * slippage + self.flash_fee(extra);
+        self.borrowed += extra;
+        self.supplied += ((extra as f64 - cost) * PRICE_SCALE / price as f64).max(0.0) as u64;
+        self.execution_cost += cost;
+    }
+
+    /// Repay `repay` of debt by selling collateral
+    fn delever(&mut self, repay: u64, price: i64) {
+        let repay = repay.min(self.borrowed);
+        if repay == 0 {
+            return;
+        }
+        let slippage = self.slippage(repay);
+        let cost = repay as f64 * slippage / (1.0 - slippage).max(f64::EPSILON) + self.flash_fee(repay);
+        let sold = ((repay as f64 + cost) * PRICE_SCALE / price as f64) as u64;
+        self.supplied = self.supplied.saturating_sub(sold);
+        self.borrowed -= repay;
+        self.execution_cost += cost;
+    }
+}
@@ -0,0 +1,91 @@
+use aegis_core::VaultState;
+use aegis_sim::report::{percentile, Percentiles};
+use aegis_sim::{run, run_path, SimConfig, Summary, VaultParams};
+
+fn calm() -> SimConfig {
+    SimConfig {
+        paths: 20,
+        horizon_days: 2.0,
+        volatility_annual: 0.0,
+        jump_rate_per_day: 0.0,
+        oracle_outage_rate_per_day: 0.0,
+        tx_fail_rate: 0.0,
+        ..SimConfig::default()
+    }
+}
+
+fn crash() -> SimConfig {
+    SimConfig {
+        paths: 100,
+        horizon_days: 1.0,
+        jump_rate_per_day: 10.0,
+        jump_mean_bps: 200.0,
+        ..SimConfig::default()
+    }
+}
+
+#[test]
+fn same_seed_reproduces_the_path() {
+    let config = crash();
+    let a = run_path(&config, 7);
+    let b = run_path(&config, 7);
+    assert_eq!(a.loss_bps, b.loss_bps);
+    assert_eq!(a.unwind_slots, b.unwind_slots);
+    assert_eq!(a.transitions, b.transitions);
+}
+
+#[test]
+fn calm_market_stays_levered() {
+    for result in run(&calm()) {
+        assert_eq!(result.worst_state, VaultState::Loop);
+        assert_eq!(result.final_state, VaultState::Loop);
+        assert_eq!(result.unwind_slots, None);
+        assert!(!result.liquidated);
+        // Only the initial lever's slippage is lost
+        assert!((0..=5).contains(&result.loss_bps), "loss {}bps", result.loss_bps);
+    }
+}
+
+#[test]
+fn repeated_depegs_force_unwinds() {
+    let results = run(&crash());
+    let summary = Summary::new(&results);
+
+    let de_risked = results.iter().filter(|r| r.worst_state != VaultState::Loop).count();
+    assert!(de_risked * 10 >= results.len() * 9, "{de_risked} of {} de-risked", results.len());
+    assert!(summary.unwind_slots.count > 0);
+
+    let loss = &summary.loss_bps;
+    assert!(loss.p50 <= loss.p95 && loss.p95 <= loss.p99 && loss.p99 <= loss.max);
+}
+
+#[test]
+fn unwind_waits_out_failed_transactions() {
+    // Every de-risk decision is EXIT and half the transactions fail: unwinds
+    // complete, but on average only after retries
+    let base = crash();
+    let config = SimConfig {
+        vault: VaultParams { peg_exit_bps: 10, peg_panic_bps: 10_000, ..base.vault.clone() },
+        tx_fail_rate: 0.5,
+        stress_fail_rate: 0.0,
+        ..base
+    };
+    let results = run(&config);
+    let unwinds: Vec<u64> = results.iter().filter_map(|r| r.unwind_slots).collect();
+    assert!(!unwinds.is_empty());
+    assert!(unwinds.iter().all(|s| s % config.vault.retry_slots == 0));
+    assert!(unwinds.iter().any(|s| *s > 0));
+}
+
+#[test]
+fn percentiles_use_nearest_rank() {
+    let values: Vec<u32> = (1..=100).collect();
+    assert_eq!(percentile(&values, 50.0), Some(50));
+    assert_eq!(percentile(&values, 99.0), Some(99));
+    assert_eq!(percentile(&values, 100.0), Some(100));
+    assert_eq!(percentile(&[7u32], 1.0), Some(7));
+    assert_eq!(percentile::<u32>(&[], 50.0), None);
+
+    let summary = Percentiles::new(vec![3, 1, 2]);
+    assert_eq!((summary.count, summary.mean, summary.max), (3, 2.0, Some(3)));
+}
@@ -0,0 +1,79 @@
+// Typed helpers for programs that hold aegis-vault shares and deposit or
+// withdraw by CPI, on top of the Anchor-generated `aegis_vault::cpi` module
+// (published by the program's `cpi` feature).
+//
+// Nothing here creates accounts. Before the first `deposit`, create the
+// caller's USDC and share token accounts and, to track cost basis, call
+// `open_position` for the signing PDA; all of that can be paid for by any
+// wallet in a separate transaction. `withdraw` and `withdraw_assets` accept
+// the same PDA signer and pay any USDC account of the vault's mint.
+use anchor_lang::prelude::*;
+
+pub use aegis_vault::cpi::accounts::{DepositCpi, Withdraw};
+pub use aegis_vault::program::AegisVault;
+pub use aegis_vault::ID;
+
+/// Addresses of the vault's singleton accounts and per-owner positions
+pub mod pda {
+    use anchor_lang::prelude::Pubkey;
+
+    pub fn vault() -> Pubkey {
+        Pubkey::find_program_address(&[b"vault"], &aegis_vault::ID).0
+    }
+
+    pub fn vault_usdc() -> Pubkey {
+        Pubkey::find_program_address(&[b"vault-usdc"], &aegis_vault::ID).0
+    }
+
+    pub fn share_mint() -> Pubkey {
+        Pubkey::find_program_address(&[b"share-mint"], &aegis_vault::ID).0
+    }
+
+    /// `owner`'s `UserPosition`, which `open_position` creates
+    pub fn user_position(owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"user-position", owner.as_ref()], &aegis_vault::ID).0
+    }
+}
+
+/// Deposit `amount` of `accounts.source_usdc` for shares minted to
+/// `accounts.owner_shares`. `signer_seeds` sign for a PDA `authority`; pass
+/// `&[]` when it already signed the outer transaction.
+pub fn deposit<'info>(
+    aegis_vault_program: AccountInfo<'info>,
+    accounts: DepositCpi<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    aegis_vault::cpi::deposit_cpi(
+        CpiContext::new_with_signer(aegis_vault_program, accounts, signer_seeds),
+        amount,
+    )
+}
+
+/// Burn `shares` for their NAV, paid to `accounts.recipient_usdc`
+pub fn withdraw<'info>(
+    aegis_vault_program: AccountInfo<'info>,
+    accounts: Withdraw<'info>,
+    shares: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    aegis_vault::cpi::withdraw(
+        CpiContext::new_with_signer(aegis_vault_program, accounts, signer_seeds),
+        shares,
+    )
+}
+
+/// Receive exactly `usdc_amount`, burning at most `max_shares_in`
+pub fn withdraw_assets<'info>(
+    aegis_vault_program: AccountInfo<'info>,
+    accounts: Withdraw<'info>,
+    usdc_amount: u64,
+    max_shares_in: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    aegis_vault::cpi::withdraw_assets(
+        CpiContext::new_with_signer(aegis_vault_program, accounts, signer_seeds),
+        usdc_amount,
+        max_shares_in,
+    )
+}
@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::{Parser, ValueEnum};
+use mock_oracles::validator::write_account_json;
+use mock_oracles::{OracleFormat, OraclePrice};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    PythLegacy,
+    PythPriceUpdateV2,
+    Switchboard,
+}
+
+#[derive(Parser)]
+#[command(
+    name = "mock-oracle",
+    about = "Write a mock oracle account for solana-test-validator --account"
+)]
+struct Cli {
+    #[arg(long, value_enum)]
+    format: Format,
+
+    /// Price in USD, e.g. 0.9985
+    #[arg(long)]
+    price: f64,
+
+    /// Confidence (standard deviation for Switchboard) in USD
+    #[arg(long, default_value_t = 0.0)]
+    conf: f64,
+
+    /// Exponent the price is stored at
+    #[arg(long, default_value_t = -8, allow_hyphen_values = true)]
+    expo: i32,
+
+    /// Publish slot
+    #[arg(long, default_value_t = 0)]
+    slot: u64,
+
+    /// Publish unix time [default: now]
+    #[arg(long)]
+    timestamp: Option<i64>,
+
+    /// Account address [default: a fresh keypair's]
+    #[arg(long)]
+    address: Option<String>,
+
+    /// PriceUpdateV2 feed id, 64 hex chars [default: zeroes]
+    #[arg(long)]
+    feed_id: Option<String>,
+
+    /// Output JSON file
+    #[arg(long)]
+    out: PathBuf,
+}
+
+fn parse_feed_id(hex: &str) -> Result<[u8; 32], String> {
+    let hex = hex.trim_start_matches("0x");
+    if hex.len() != 64 {
+        return Err(format!("feed id must be 64 hex chars, got {}", hex.len()));
+    }
+    let mut id = [0u8; 32];
+    for (i, byte) in id.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).map_err(|e| e.to_string())?;
+    }
+    Ok(id)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    let format = match cli.format {
+        Format::PythLegacy => OracleFormat::PythLegacy,
+        Format::PythPriceUpdateV2 => OracleFormat::PythPriceUpdateV2 {
+            feed_id: cli
+                .feed_id
+                .as_deref()
+                .map(parse_feed_id)
+                .transpose()?
+                .unwrap_or_default(),
+        },
+        Format::Switchboard => OracleFormat::Switchboard,
+    };
+    let address = match cli.address {
+        Some(address) => Pubkey::from_str(&address)?,
+        None => Keypair::new().pubkey(),
+    };
+    let publish_time = match cli.timestamp {
+        Some(timestamp) => timestamp,
+        None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64,
+    };
+
+    let scale = 10f64.powi(-cli.expo);
+    let price = OraclePrice {
+        price: (cli.price * scale).round() as i64,
+        conf: (cli.conf * scale).round() as u64,
+        expo: cli.expo,
+        publish_slot: cli.slot,
+        publish_time,
+    };
+    write_account_json(&cli.out, &address, &format.account(&price))?;
+
+    println!("{address}");
+    eprintln!("Wrote {}", cli.out.display());
+    Ok(())
+}
@@ -0,0 +1,101 @@
+// Mock oracle accounts for tests and local validators. Each format is
+// serialized byte for byte as the on-chain program writes it, so the vault's
+// readers (and their hard-coded offsets) see exactly what mainnet serves:
+//
+//   legacy Pyth price account     pyth::legacy_price_account
+//   Pyth receiver PriceUpdateV2   pyth::price_update_v2
+//   Switchboard V2 aggregator     switchboard::aggregator
+//
+// Accounts go into program-test (`program_test`, feature "program-test") or
+// out as JSON for `solana-test-validator --account <ADDRESS> <FILE>`:
+//
+//   cargo run -p mock-oracles -- --format switchboard --price 0.9985 --out sb.json
+pub mod pyth;
+pub mod switchboard;
+pub mod validator;
+
+#[cfg(feature = "program-test")]
+pub mod program_test;
+
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+
+/// A published price: `price × 10^expo`, with `conf` on the same scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_slot: u64,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// `price` and `conf` in 6dp, the vault's own scale
+    pub fn from_e6(price: i64, conf: u64, publish_slot: u64, publish_time: i64) -> Self {
+        Self {
+            price,
+            conf,
+            expo: -6,
+            publish_slot,
+            publish_time,
+        }
+    }
+
+    /// The same price expressed at `expo`; truncates when digits are dropped
+    pub fn with_expo(self, expo: i32) -> Self {
+        let shift = self.expo - expo;
+        let scale = 10i64.pow(shift.unsigned_abs());
+        let (price, conf) = if shift >= 0 {
+            (self.price * scale, self.conf * scale as u64)
+        } else {
+            (self.price / scale, self.conf / scale as u64)
+        };
+        Self {
+            price,
+            conf,
+            expo,
+            ..self
+        }
+    }
+}
+
+/// Account layouts the builders can produce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleFormat {
+    PythLegacy,
+    PythPriceUpdateV2 { feed_id: [u8; 32] },
+    Switchboard,
+}
+
+impl OracleFormat {
+    /// Program that owns accounts of this format on mainnet
+    pub fn owner(self) -> Pubkey {
+        match self {
+            OracleFormat::PythLegacy => pyth::ORACLE_PROGRAM_ID,
+            OracleFormat::PythPriceUpdateV2 { .. } => pyth::RECEIVER_PROGRAM_ID,
+            OracleFormat::Switchboard => switchboard::PROGRAM_ID,
+        }
+    }
+
+    pub fn encode(self, price: &OraclePrice) -> Vec<u8> {
+        match self {
+            OracleFormat::PythLegacy => pyth::legacy_price_account(price),
+            OracleFormat::PythPriceUpdateV2 { feed_id } => pyth::price_update_v2(price, feed_id),
+            OracleFormat::Switchboard => switchboard::aggregator(price),
+        }
+    }
+
+    /// Rent-exempt account holding `price`
+    pub fn account(self, price: &OraclePrice) -> Account {
+        let data = self.encode(price);
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: self.owner(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+}
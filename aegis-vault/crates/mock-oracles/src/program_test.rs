@@ -0,0 +1,25 @@
+// Load mock oracles into solana-program-test: at genesis, or rewritten in
+// place between transactions to move the price or age the feed.
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{OracleFormat, OraclePrice};
+
+pub fn add_oracle(
+    program_test: &mut ProgramTest,
+    address: Pubkey,
+    format: OracleFormat,
+    price: &OraclePrice,
+) {
+    program_test.add_account(address, format.account(price));
+}
+
+pub fn set_oracle(
+    ctx: &mut ProgramTestContext,
+    address: &Pubkey,
+    format: OracleFormat,
+    price: &OraclePrice,
+) {
+    ctx.set_account(address, &AccountSharedData::from(format.account(price)));
+}
@@ -0,0 +1,73 @@
+// Pyth price accounts: the legacy push-oracle price account (read by the
+// vault through pyth-sdk-solana) and the pull-oracle PriceUpdateV2 account
+// posted by the Pyth receiver program.
+use pyth_sdk_solana::state::{AccountType, PriceAccount, PriceInfo, PriceStatus, MAGIC, VERSION_2};
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::OraclePrice;
+
+pub const ORACLE_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+pub const RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// sha256("account:PriceUpdateV2")[..8]
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+/// Space the receiver allocates: room for the larger `Partial` verification level
+pub const PRICE_UPDATE_V2_LEN: usize = 8 + 32 + 2 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 8;
+
+/// Legacy price account in `Trading` status. The previous price mirrors the
+/// current one so readers that fall back to it see the same value.
+pub fn legacy_price_account(price: &OraclePrice) -> Vec<u8> {
+    let account = PriceAccount {
+        magic: MAGIC,
+        ver: VERSION_2,
+        atype: AccountType::Price as u32,
+        size: std::mem::size_of::<PriceAccount>() as u32,
+        expo: price.expo,
+        num: 1,
+        num_qt: 1,
+        last_slot: price.publish_slot,
+        valid_slot: price.publish_slot,
+        timestamp: price.publish_time,
+        prev_slot: price.publish_slot,
+        prev_price: price.price,
+        prev_conf: price.conf,
+        prev_timestamp: price.publish_time,
+        agg: PriceInfo {
+            price: price.price,
+            conf: price.conf,
+            status: PriceStatus::Trading,
+            pub_slot: price.publish_slot,
+            ..PriceInfo::default()
+        },
+        ..PriceAccount::default()
+    };
+    bytemuck::bytes_of(&account).to_vec()
+}
+
+/// Fully verified PriceUpdateV2 for `feed_id`, posted in the publish slot.
+/// Borsh layout:
+///   discriminator        8
+///   write_authority      32
+///   verification_level   1 (`Full`)
+///   price_message        feed_id 32, price i64, conf u64, exponent i32,
+///                        publish_time i64, prev_publish_time i64,
+///                        ema_price i64, ema_conf u64
+///   posted_slot          8
+pub fn price_update_v2(price: &OraclePrice, feed_id: [u8; 32]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(PRICE_UPDATE_V2_LEN);
+    data.extend_from_slice(&PRICE_UPDATE_V2_DISCRIMINATOR);
+    data.extend_from_slice(Pubkey::default().as_ref()); // write_authority
+    data.push(1); // VerificationLevel::Full
+    data.extend_from_slice(&feed_id);
+    data.extend_from_slice(&price.price.to_le_bytes());
+    data.extend_from_slice(&price.conf.to_le_bytes());
+    data.extend_from_slice(&price.expo.to_le_bytes());
+    data.extend_from_slice(&price.publish_time.to_le_bytes());
+    data.extend_from_slice(&price.publish_time.to_le_bytes()); // prev_publish_time
+    data.extend_from_slice(&price.price.to_le_bytes()); // ema_price
+    data.extend_from_slice(&price.conf.to_le_bytes()); // ema_conf
+    data.extend_from_slice(&price.publish_slot.to_le_bytes()); // posted_slot
+    data.resize(PRICE_UPDATE_V2_LEN, 0);
+    data
+}
@@ -0,0 +1,76 @@
+// Switchboard V2 AggregatorAccountData. Offsets match the partial layout the
+// vault reads in `oracles/switchboard.rs`; everything else stays zeroed.
+//
+//   discriminator                 8    (offset 0)
+//   min_oracle_results            4    (offset 8 + 204)
+//   latest_confirmed_round        176  (offset 8 + 312)
+//     num_success                 +0   u32
+//     is_closed                   +8   bool
+//     round_open_slot             +16  u64
+//     round_open_timestamp        +24  i64
+//     result                      +32  SwitchboardDecimal (i128 mantissa, u32 scale)
+//     std_deviation               +52  SwitchboardDecimal
+//     min_response                +72  SwitchboardDecimal
+//     max_response                +92  SwitchboardDecimal
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::OraclePrice;
+
+pub const PROGRAM_ID: Pubkey = pubkey!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+
+/// sha256("account:AggregatorAccountData")[..8]
+pub const DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+/// Full account size, discriminator included
+pub const AGGREGATOR_LEN: usize = 3851;
+
+pub const MIN_ORACLE_RESULTS_OFFSET: usize = 8 + 204;
+pub const LATEST_ROUND_OFFSET: usize = 8 + 312;
+pub const ROUND_NUM_SUCCESS: usize = 0;
+pub const ROUND_IS_CLOSED: usize = 8;
+pub const ROUND_OPEN_SLOT: usize = 16;
+pub const ROUND_OPEN_TIMESTAMP: usize = 24;
+pub const ROUND_RESULT: usize = 32;
+pub const ROUND_STD_DEVIATION: usize = 52;
+pub const ROUND_MIN_RESPONSE: usize = 72;
+pub const ROUND_MAX_RESPONSE: usize = 92;
+
+/// `value × 10^expo` as a SwitchboardDecimal (mantissa, scale)
+pub fn decimal(value: i128, expo: i32) -> (i128, u32) {
+    if expo <= 0 {
+        (value, expo.unsigned_abs())
+    } else {
+        (value * 10i128.pow(expo as u32), 0)
+    }
+}
+
+fn write_decimal(data: &mut [u8], offset: usize, (mantissa, scale): (i128, u32)) {
+    data[offset..offset + 16].copy_from_slice(&mantissa.to_le_bytes());
+    data[offset + 16..offset + 20].copy_from_slice(&scale.to_le_bytes());
+}
+
+/// Aggregator whose latest confirmed round, answered by one oracle, reports
+/// `price` with `conf` as its standard deviation
+pub fn aggregator(price: &OraclePrice) -> Vec<u8> {
+    let mut data = vec![0u8; AGGREGATOR_LEN];
+    data[..8].copy_from_slice(&DISCRIMINATOR);
+    data[MIN_ORACLE_RESULTS_OFFSET..MIN_ORACLE_RESULTS_OFFSET + 4]
+        .copy_from_slice(&1u32.to_le_bytes());
+
+    let result = decimal(price.price as i128, price.expo);
+    let round = &mut data[LATEST_ROUND_OFFSET..];
+    round[ROUND_NUM_SUCCESS..ROUND_NUM_SUCCESS + 4].copy_from_slice(&1u32.to_le_bytes());
+    round[ROUND_IS_CLOSED] = 1;
+    round[ROUND_OPEN_SLOT..ROUND_OPEN_SLOT + 8].copy_from_slice(&price.publish_slot.to_le_bytes());
+    round[ROUND_OPEN_TIMESTAMP..ROUND_OPEN_TIMESTAMP + 8]
+        .copy_from_slice(&price.publish_time.to_le_bytes());
+    write_decimal(round, ROUND_RESULT, result);
+    write_decimal(
+        round,
+        ROUND_STD_DEVIATION,
+        decimal(price.conf as i128, price.expo),
+    );
+    write_decimal(round, ROUND_MIN_RESPONSE, result);
+    write_decimal(round, ROUND_MAX_RESPONSE, result);
+    data
+}
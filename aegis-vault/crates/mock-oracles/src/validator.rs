@@ -0,0 +1,30 @@
+// Account dumps for `solana-test-validator --account <ADDRESS> <FILE>`, in
+// the same JSON shape `solana account --output json` produces.
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+pub fn account_json(address: &Pubkey, account: &Account) -> Value {
+    json!({
+        "pubkey": address.to_string(),
+        "account": {
+            "lamports": account.lamports,
+            "data": [STANDARD.encode(&account.data), "base64"],
+            "owner": account.owner.to_string(),
+            "executable": account.executable,
+            "rentEpoch": account.rent_epoch,
+            "space": account.data.len(),
+        }
+    })
+}
+
+pub fn write_account_json(path: &Path, address: &Pubkey, account: &Account) -> io::Result<()> {
+    let file = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(file, &account_json(address, account)).map_err(io::Error::from)
+}
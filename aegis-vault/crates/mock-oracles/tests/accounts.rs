@@ -0,0 +1,133 @@
+use mock_oracles::pyth::{PRICE_UPDATE_V2_DISCRIMINATOR, PRICE_UPDATE_V2_LEN};
+use mock_oracles::switchboard::{
+    AGGREGATOR_LEN, DISCRIMINATOR, LATEST_ROUND_OFFSET, MIN_ORACLE_RESULTS_OFFSET,
+    ROUND_NUM_SUCCESS, ROUND_OPEN_SLOT, ROUND_OPEN_TIMESTAMP, ROUND_RESULT, ROUND_STD_DEVIATION,
+};
+use mock_oracles::validator::account_json;
+use mock_oracles::{OracleFormat, OraclePrice};
+use pyth_sdk_solana::state::{load_price_account, PriceStatus};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+
+const SLOT: u64 = 1_000;
+const TIME: i64 = 1_700_000_000;
+
+fn price() -> OraclePrice {
+    OraclePrice::from_e6(998_500, 250, SLOT, TIME).with_expo(-8)
+}
+
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn i64_at(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn i128_at(data: &[u8], offset: usize) -> i128 {
+    i128::from_le_bytes(data[offset..offset + 16].try_into().unwrap())
+}
+
+#[test]
+fn rescaling_keeps_the_value() {
+    let price = price();
+    assert_eq!(
+        (price.price, price.conf, price.expo),
+        (99_850_000, 25_000, -8)
+    );
+    assert_eq!(
+        price.with_expo(-6),
+        OraclePrice::from_e6(998_500, 250, SLOT, TIME)
+    );
+    // Dropping digits truncates
+    assert_eq!(price.with_expo(-2).price, 99);
+}
+
+#[test]
+fn legacy_pyth_loads_through_the_sdk() {
+    let data = OracleFormat::PythLegacy.encode(&price());
+    let account = load_price_account(&data).unwrap();
+    assert_eq!(account.expo, -8);
+    assert_eq!(account.timestamp, TIME);
+    assert_eq!(account.agg.price, 99_850_000);
+    assert_eq!(account.agg.conf, 25_000);
+    assert_eq!(account.agg.pub_slot, SLOT);
+    assert!(matches!(account.agg.status, PriceStatus::Trading));
+
+    let feed = account.to_price_feed(&Pubkey::new_unique());
+    let current = feed.get_price_unchecked();
+    assert_eq!(
+        (current.price, current.conf, current.publish_time),
+        (99_850_000, 25_000, TIME)
+    );
+}
+
+#[test]
+fn price_update_v2_layout() {
+    let feed_id = [7u8; 32];
+    let data = OracleFormat::PythPriceUpdateV2 { feed_id }.encode(&price());
+    assert_eq!(data.len(), PRICE_UPDATE_V2_LEN);
+    assert_eq!(data[..8], PRICE_UPDATE_V2_DISCRIMINATOR);
+    assert_eq!(data[40], 1); // VerificationLevel::Full
+    let message = 41;
+    assert_eq!(data[message..message + 32], feed_id);
+    assert_eq!(i64_at(&data, message + 32), 99_850_000);
+    assert_eq!(u64_at(&data, message + 40), 25_000);
+    assert_eq!(u32_at(&data, message + 48) as i32, -8);
+    assert_eq!(i64_at(&data, message + 52), TIME);
+    assert_eq!(u64_at(&data, message + 84), SLOT); // posted_slot
+}
+
+#[test]
+fn switchboard_round_layout() {
+    let data = OracleFormat::Switchboard.encode(&price());
+    assert_eq!(data.len(), AGGREGATOR_LEN);
+    assert_eq!(data[..8], DISCRIMINATOR);
+    assert_eq!(u32_at(&data, MIN_ORACLE_RESULTS_OFFSET), 1);
+
+    let round = &data[LATEST_ROUND_OFFSET..];
+    assert_eq!(u32_at(round, ROUND_NUM_SUCCESS), 1);
+    assert_eq!(u64_at(round, ROUND_OPEN_SLOT), SLOT);
+    assert_eq!(i64_at(round, ROUND_OPEN_TIMESTAMP), TIME);
+    assert_eq!(i128_at(round, ROUND_RESULT), 99_850_000);
+    assert_eq!(u32_at(round, ROUND_RESULT + 16), 8);
+    assert_eq!(i128_at(round, ROUND_STD_DEVIATION), 25_000);
+    assert_eq!(u32_at(round, ROUND_STD_DEVIATION + 16), 8);
+}
+
+#[test]
+fn accounts_are_rent_exempt_and_owned_by_the_oracle() {
+    for format in [
+        OracleFormat::PythLegacy,
+        OracleFormat::PythPriceUpdateV2 { feed_id: [0; 32] },
+        OracleFormat::Switchboard,
+    ] {
+        let account = format.account(&price());
+        assert_eq!(account.owner, format.owner());
+        assert!(Rent::default().is_exempt(account.lamports, account.data.len()));
+    }
+}
+
+#[test]
+fn validator_json_round_trips() {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    let address = Pubkey::new_unique();
+    let account = OracleFormat::Switchboard.account(&price());
+    let json = account_json(&address, &account);
+
+    assert_eq!(json["pubkey"], address.to_string());
+    let inner = &json["account"];
+    assert_eq!(inner["lamports"], account.lamports);
+    assert_eq!(inner["owner"], account.owner.to_string());
+    assert_eq!(inner["executable"], false);
+    assert_eq!(inner["space"], AGGREGATOR_LEN);
+    assert_eq!(inner["data"][1], "base64");
+    let data = STANDARD.decode(inner["data"][0].as_str().unwrap()).unwrap();
+    assert_eq!(data, account.data);
+}
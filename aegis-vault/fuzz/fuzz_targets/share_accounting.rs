@@ -0,0 +1,11 @@
+#![no_main]
+
+use aegis_fuzz::{Ledger, Op};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|ops: Vec<Op>| {
+    let mut ledger = Ledger::default();
+    for op in ops {
+        ledger.apply(op);
+    }
+});
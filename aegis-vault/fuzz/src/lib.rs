@@ -0,0 +1,291 @@
+// Share-accounting model for fuzzing. `Ledger` mirrors the state the
+// deposit/withdraw handlers touch (vault totals, idle USDC, share mint supply
+// and per-user share balances) and runs the same aegis-core conversions in the
+// same order. Venue events move equity underneath the shares.
+//
+// An operation the program would reject (math overflow, a deposit minting
+// no shares, not enough idle USDC or shares) leaves the ledger unchanged, like a failed
+// transaction. After every operation `Ledger::apply` checks:
+//   - total_shares == share mint supply + the first deposit's DEAD_SHARES,
+//     and the mint supply == sum of share balances
+//   - deposits and withdrawals never lower the assets behind each
+//     outstanding share, i.e. rounding always favours the vault
+//   - depositing and immediately redeeming never returns more than was paid
+//   - after a first-depositor inflation (small seed deposit, then a
+//     donation), a victim's deposit stays redeemable for all but
+//     2 + donated / INFLATION_TOLERANCE: one share at the inflated price plus
+//     rounding. Without aegis-core's DEAD_SHARES a 1-unit seed lets the
+//     donation swallow the victim's whole deposit.
+// Assets held while no shares exist belong to nobody and go to the next
+// depositor; only value owned by other holders counts as "free value".
+use aegis_core::{assets_for_shares, depositor_shares, equity, shares_for_deposit, DEAD_SHARES};
+use arbitrary::Arbitrary;
+
+pub const USERS: usize = 4;
+
+/// Donated units an inflation attack may spend per unit a victim loses
+pub const INFLATION_TOLERANCE: u64 = 1_000;
+
+#[derive(Debug, Clone, Copy, Arbitrary)]
+pub enum Op {
+    Deposit {
+        user: u8,
+        amount: u64,
+    },
+    /// Redeem `shares` modulo the user's balance + 1
+    Withdraw {
+        user: u8,
+        shares: u64,
+    },
+    /// Deposit, then redeem exactly the shares minted
+    RoundTrip {
+        user: u8,
+        amount: u64,
+    },
+    /// SPL transfer of `shares` modulo the sender's balance + 1
+    Transfer {
+        from: u8,
+        to: u8,
+        shares: u64,
+    },
+    /// Into a vault without shares: `attacker` deposits `seed`, donates
+    /// `donation`, then `victim` deposits `amount`
+    Inflate {
+        attacker: u8,
+        victim: u8,
+        seed: u64,
+        donation: u64,
+        amount: u64,
+    },
+    /// USDC sent straight to the vault's token account
+    Donate {
+        amount: u64,
+    },
+    /// Idle USDC supplied to the venue (leverage up)
+    Deploy {
+        amount: u64,
+    },
+    /// Venue equity pulled back to idle (unwind)
+    Recall {
+        amount: u64,
+    },
+    /// Borrow against supplied collateral and re-supply (loop)
+    Loop {
+        amount: u64,
+    },
+    /// Supply and borrow interest, in bps of the balances
+    Accrue {
+        supply_bps: u16,
+        borrow_bps: u16,
+    },
+    /// Supplied collateral written down, in bps (bad debt, depeg)
+    Loss {
+        bps: u16,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Applied,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ledger {
+    pub total_supplied: u64,
+    pub total_borrowed: u64,
+    /// Balance of the vault's USDC token account
+    pub idle: u64,
+    pub total_shares: u64,
+    pub share_supply: u64,
+    /// Share token balances; shares move freely between users
+    pub balances: [u64; USERS],
+}
+
+fn bps_of(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / 10_000) as u64
+}
+
+impl Ledger {
+    /// `Vault::total_assets`: venue equity plus idle USDC
+    pub fn total_assets(&self) -> Option<u64> {
+        equity(self.total_supplied, self.total_borrowed).checked_add(self.idle)
+    }
+
+    /// Apply `op` and check every invariant; panics on a violation
+    pub fn apply(&mut self, op: Op) -> Outcome {
+        let before = self.clone();
+        let outcome = match op {
+            Op::Deposit { user, amount } => self.deposit(user, amount).map(|_| ()),
+            Op::Withdraw { user, shares } => {
+                let held = self.balances[user as usize % USERS];
+                self.withdraw(user, shares % held.saturating_add(1))
+                    .map(|_| ())
+            }
+            Op::RoundTrip { user, amount } => self.round_trip(user, amount),
+            Op::Transfer { from, to, shares } => {
+                let (from, to) = (from as usize % USERS, to as usize % USERS);
+                let shares = shares % self.balances[from].saturating_add(1);
+                self.balances[from] -= shares;
+                self.balances[to] += shares; // bounded by total_shares
+                Some(())
+            }
+            Op::Inflate {
+                attacker,
+                victim,
+                seed,
+                donation,
+                amount,
+            } => self.inflate(attacker, victim, seed, donation, amount),
+            Op::Donate { amount } => self.idle.checked_add(amount).map(|idle| self.idle = idle),
+            Op::Deploy { amount } => {
+                let amount = amount.min(self.idle);
+                self.total_supplied.checked_add(amount).map(|supplied| {
+                    self.total_supplied = supplied;
+                    self.idle -= amount;
+                })
+            }
+            Op::Recall { amount } => {
+                let amount = amount.min(equity(self.total_supplied, self.total_borrowed));
+                self.idle.checked_add(amount).map(|idle| {
+                    self.idle = idle;
+                    self.total_supplied -= amount;
+                })
+            }
+            Op::Loop { amount } => self
+                .total_supplied
+                .checked_add(amount)
+                .zip(self.total_borrowed.checked_add(amount))
+                .map(|(supplied, borrowed)| {
+                    self.total_supplied = supplied;
+                    self.total_borrowed = borrowed;
+                }),
+            Op::Accrue {
+                supply_bps,
+                borrow_bps,
+            } => self
+                .total_supplied
+                .checked_add(bps_of(self.total_supplied, supply_bps))
+                .zip(
+                    self.total_borrowed
+                        .checked_add(bps_of(self.total_borrowed, borrow_bps)),
+                )
+                .map(|(supplied, borrowed)| {
+                    self.total_supplied = supplied;
+                    self.total_borrowed = borrowed;
+                }),
+            Op::Loss { bps } => {
+                self.total_supplied -= bps_of(self.total_supplied, bps.min(10_000));
+                Some(())
+            }
+        };
+
+        match outcome {
+            Some(()) => {
+                self.check_supply();
+                Outcome::Applied
+            }
+            None => {
+                *self = before;
+                Outcome::Rejected
+            }
+        }
+    }
+
+    /// `deposit::handler`: shares priced off total assets before the transfer
+    fn deposit(&mut self, user: u8, amount: u64) -> Option<u64> {
+        let before = self.clone();
+        let minted = shares_for_deposit(amount, self.total_shares, self.total_assets()?)?;
+        let shares = depositor_shares(minted, self.total_shares)?;
+        if shares == 0 {
+            return None;
+        }
+        self.idle = self.idle.checked_add(amount)?;
+        self.share_supply = self.share_supply.checked_add(shares)?;
+        let balance = &mut self.balances[user as usize % USERS];
+        *balance = balance.checked_add(shares)?;
+        self.total_shares = self.total_shares.checked_add(minted)?;
+        before.check_share_value(self);
+        Some(shares)
+    }
+
+    /// `withdraw::handler`: paid from idle USDC only
+    fn withdraw(&mut self, user: u8, shares: u64) -> Option<u64> {
+        let before = self.clone();
+        let user = user as usize % USERS;
+        if self.balances[user] < shares {
+            return None;
+        }
+        let assets = assets_for_shares(shares, self.total_shares, self.total_assets()?)?;
+        if self.idle < assets {
+            return None;
+        }
+        self.share_supply = self.share_supply.checked_sub(shares)?;
+        self.idle -= assets;
+        self.balances[user] -= shares;
+        self.total_shares = self.total_shares.checked_sub(shares)?;
+        before.check_share_value(self);
+        Some(assets)
+    }
+
+    fn round_trip(&mut self, user: u8, amount: u64) -> Option<()> {
+        let owned = self.total_shares > 0;
+        let shares = self.deposit(user, amount)?;
+        let assets = self.withdraw(user, shares)?;
+        assert!(
+            !owned || assets <= amount,
+            "round trip of {amount} returned {assets}: {self:?}"
+        );
+        Some(())
+    }
+
+    fn inflate(
+        &mut self,
+        attacker: u8,
+        victim: u8,
+        seed: u64,
+        donation: u64,
+        amount: u64,
+    ) -> Option<()> {
+        if self.total_shares > 0 {
+            return None;
+        }
+        // Assets left without shares go to the seed deposit like a donation
+        let donated = self.total_assets()?.checked_add(donation)?;
+        self.deposit(attacker, seed)?;
+        self.idle = self.idle.checked_add(donation)?;
+        let shares = self.deposit(victim, amount)?;
+
+        let redeemable = assets_for_shares(shares, self.total_shares, self.total_assets()?)?;
+        assert!(
+            amount - redeemable.min(amount) <= 2 + donated / INFLATION_TOLERANCE,
+            "victim of {amount} can redeem {redeemable} after {donated} donated: {self:?}"
+        );
+        Some(())
+    }
+
+    fn check_supply(&self) {
+        let balances: u128 = self.balances.iter().map(|&b| b as u128).sum();
+        let locked = if self.total_shares > 0 {
+            DEAD_SHARES
+        } else {
+            0
+        };
+        assert_eq!(self.total_shares, self.share_supply + locked, "{self:?}");
+        assert_eq!(self.share_supply as u128, balances, "{self:?}");
+    }
+
+    /// Assets per share after ≥ before, for shares outstanding on both sides
+    fn check_share_value(&self, after: &Ledger) {
+        if self.total_shares == 0 || after.total_shares == 0 {
+            return;
+        }
+        let (Some(assets_before), Some(assets_after)) = (self.total_assets(), after.total_assets())
+        else {
+            return;
+        };
+        let before = assets_before as u128 * after.total_shares as u128;
+        let now = assets_after as u128 * self.total_shares as u128;
+        assert!(now >= before, "share value fell: {self:?} -> {after:?}");
+    }
+}
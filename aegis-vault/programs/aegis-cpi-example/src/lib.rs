@@ -0,0 +1,111 @@
+// Example caller for the aegis-vault CPI interface, the pattern a structured
+// product would follow: a `treasury` PDA owns a USDC account and a share
+// account, and moves funds in and out of the vault through `aegis-vault-cpi`,
+// signing with its seeds.
+//
+// Setup, by any wallet: create both treasury token accounts and call
+// aegis-vault `open_position` for the treasury. There is no access control
+// here; a real product would gate `invest` and `redeem`. NOT for deployment.
+use aegis_vault_cpi::{AegisVault, DepositCpi, Withdraw};
+use anchor_lang::prelude::*;
+
+declare_id!("FegGBKbFwoGuDUUGoJ84zi2f5n75gvoo9Ea2RoAMT9hz");
+
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+#[program]
+pub mod aegis_cpi_example {
+    use super::*;
+
+    /// Deposit `amount` of the treasury's USDC into aegis-vault
+    pub fn invest(ctx: Context<Treasury>, amount: u64) -> Result<()> {
+        let seeds: &[&[u8]] = &[TREASURY_SEED, &[ctx.bumps.treasury]];
+        let accounts = &ctx.accounts;
+        aegis_vault_cpi::deposit(
+            accounts.aegis_vault_program.to_account_info(),
+            DepositCpi {
+                vault: accounts.vault.to_account_info(),
+                user_position: Some(accounts.user_position.to_account_info()),
+                authority: accounts.treasury.to_account_info(),
+                usdc_mint: accounts.usdc_mint.to_account_info(),
+                source_usdc: accounts.treasury_usdc.to_account_info(),
+                vault_usdc: accounts.vault_usdc.to_account_info(),
+                share_mint: accounts.share_mint.to_account_info(),
+                owner_shares: accounts.treasury_shares.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+                share_token_program: accounts.share_token_program.to_account_info(),
+            },
+            amount,
+            &[seeds],
+        )
+    }
+
+    /// Withdraw exactly `usdc_amount` back to the treasury, burning at most
+    /// `max_shares_in`
+    pub fn redeem(ctx: Context<Treasury>, usdc_amount: u64, max_shares_in: u64) -> Result<()> {
+        let seeds: &[&[u8]] = &[TREASURY_SEED, &[ctx.bumps.treasury]];
+        let accounts = &ctx.accounts;
+        aegis_vault_cpi::withdraw_assets(
+            accounts.aegis_vault_program.to_account_info(),
+            Withdraw {
+                vault: accounts.vault.to_account_info(),
+                user_position: Some(accounts.user_position.to_account_info()),
+                user: accounts.treasury.to_account_info(),
+                usdc_mint: accounts.usdc_mint.to_account_info(),
+                recipient_usdc: accounts.treasury_usdc.to_account_info(),
+                vault_usdc: accounts.vault_usdc.to_account_info(),
+                share_mint: accounts.share_mint.to_account_info(),
+                user_shares: accounts.treasury_shares.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+                share_token_program: accounts.share_token_program.to_account_info(),
+            },
+            usdc_amount,
+            max_shares_in,
+            &[seeds],
+        )
+    }
+}
+
+/// The treasury and the aegis-vault accounts it deposits and withdraws
+/// with; aegis-vault validates everything but the treasury itself
+#[derive(Accounts)]
+pub struct Treasury<'info> {
+    /// CHECK: signing PDA; holds no data
+    #[account(seeds = [TREASURY_SEED], bump)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: validated by aegis-vault
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: the treasury's position, validated by aegis-vault
+    #[account(mut)]
+    pub user_position: UncheckedAccount<'info>,
+
+    /// CHECK: validated by aegis-vault
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    /// CHECK: treasury-owned USDC account, validated by aegis-vault
+    #[account(mut)]
+    pub treasury_usdc: UncheckedAccount<'info>,
+
+    /// CHECK: validated by aegis-vault
+    #[account(mut)]
+    pub vault_usdc: UncheckedAccount<'info>,
+
+    /// CHECK: validated by aegis-vault
+    #[account(mut)]
+    pub share_mint: UncheckedAccount<'info>,
+
+    /// CHECK: treasury-owned share account, validated by aegis-vault
+    #[account(mut)]
+    pub treasury_shares: UncheckedAccount<'info>,
+
+    /// CHECK: validated by aegis-vault
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: validated by aegis-vault
+    pub share_token_program: UncheckedAccount<'info>,
+
+    pub aegis_vault_program: Program<'info, AegisVault>,
+}
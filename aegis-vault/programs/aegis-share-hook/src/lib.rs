@@ -0,0 +1,283 @@
+// Opt-in Token-2022 transfer hook for aegis-vault share mints. Attach it by
+// passing this program to `initialize_vault_2022`, then call `initialize` once
+// for the share mint.
+//
+// Allowlist: while enabled, a share transfer succeeds only if the destination
+// account's owner has an allow entry. Deposits mint and withdrawals burn,
+// neither of which invokes the hook, so only secondary transfers are gated.
+//
+// Position sync: not needed. Withdrawals are authorised by the share token
+// balance, not `UserPosition.shares`, so a position left stale by a transfer
+// never affects redemption; the holder's next deposit or withdrawal resyncs it.
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::{
+    TransferHook, TransferHookAccount,
+};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::{Account as AccountState, Mint as MintState};
+use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("CE8xZioRcXWiQgf8SnE77ufv3Wwvzq76ZLdN3ncBjQGf");
+
+#[program]
+pub mod aegis_share_hook {
+    use super::*;
+
+    /// Create the hook config and the extra-account list Token-2022 reads
+    /// on every transfer. Only the share's metadata update authority (the
+    /// vault admin) may do this, and only for a mint pointing at this hook.
+    pub fn initialize(ctx: Context<Initialize>, allowlist_enabled: bool) -> Result<()> {
+        let mint = ctx.accounts.mint.to_account_info();
+        let data = mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+        let hook = mint_state.get_extension::<TransferHook>()?;
+        require!(
+            Option::<Pubkey>::from(hook.program_id) == Some(crate::ID),
+            ShareHookError::HookNotAttached
+        );
+        let metadata = mint_state.get_variable_len_extension::<TokenMetadata>()?;
+        require!(
+            Option::<Pubkey>::from(metadata.update_authority) == Some(ctx.accounts.authority.key()),
+            ShareHookError::Unauthorized
+        );
+
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.mint = ctx.accounts.mint.key();
+        config.allowlist_enabled = allowlist_enabled;
+        config.bump = ctx.bumps.config;
+
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas()?,
+        )?;
+        Ok(())
+    }
+
+    pub fn set_allowlist_enabled(ctx: Context<UpdateConfig>, enabled: bool) -> Result<()> {
+        ctx.accounts.config.allowlist_enabled = enabled;
+        Ok(())
+    }
+
+    /// Let `owner`'s token accounts receive shares
+    pub fn allow(ctx: Context<Allow>, owner: Pubkey) -> Result<()> {
+        let entry = &mut ctx.accounts.entry;
+        entry.mint = ctx.accounts.config.mint;
+        entry.owner = owner;
+        entry.bump = ctx.bumps.entry;
+        Ok(())
+    }
+
+    /// Remove `owner`'s allow entry; shares already held stay redeemable
+    pub fn revoke(_ctx: Context<Revoke>, owner: Pubkey) -> Result<()> {
+        msg!("Revoked {}", owner);
+        Ok(())
+    }
+
+    #[interface(spl_transfer_hook_interface::execute)]
+    pub fn execute(ctx: Context<Execute>, _amount: u64) -> Result<()> {
+        // Only Token-2022, mid-transfer, sets the flag: refuse direct calls
+        let source = ctx.accounts.source.to_account_info();
+        let data = source.try_borrow_data()?;
+        let account = StateWithExtensions::<AccountState>::unpack(&data)?;
+        let transferring = account.get_extension::<TransferHookAccount>()?.transferring;
+        require!(bool::from(transferring), ShareHookError::NotTransferring);
+
+        if ctx.accounts.config.allowlist_enabled {
+            // A closed or never-created entry is owned by the system program
+            require_keys_eq!(
+                *ctx.accounts.destination_allow.owner,
+                crate::ID,
+                ShareHookError::NotAllowlisted
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Accounts appended to `execute` after the extra-account list (index 4):
+/// the hook config (5) and the destination owner's allow entry (6), whose
+/// owner is read from bytes 32..64 of the destination token account (2)
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: b"hook-config".to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: b"allow".to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountData {
+                    account_index: 2,
+                    data_index: 32,
+                    length: 32,
+                },
+            ],
+            false,
+            false,
+        )?,
+    ])
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = HookConfig::LEN,
+        seeds = [b"hook-config", mint.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, HookConfig>,
+
+    /// CHECK: TLV extra-account list, written by the handler
+    #[account(
+        init,
+        payer = authority,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority)]
+    pub config: Account<'info, HookConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct Allow<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority)]
+    pub config: Account<'info, HookConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AllowEntry::LEN,
+        seeds = [b"allow", config.mint.as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, AllowEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct Revoke<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority)]
+    pub config: Account<'info, HookConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"allow", config.mint.as_ref(), owner.as_ref()],
+        bump = entry.bump
+    )]
+    pub entry: Account<'info, AllowEntry>,
+}
+
+/// Account order fixed by the transfer-hook interface, then `extra_account_metas`
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    #[account(token::mint = mint)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: source owner or delegate, already verified by Token-2022
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: read by Token-2022 to resolve the accounts below
+    #[account(
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"hook-config", mint.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, HookConfig>,
+
+    /// CHECK: allow entry PDA for the destination owner; may not exist
+    #[account(
+        seeds = [b"allow", mint.key().as_ref(), destination.owner.as_ref()],
+        bump
+    )]
+    pub destination_allow: UncheckedAccount<'info>,
+}
+
+#[account]
+pub struct HookConfig {
+    /// The share's metadata update authority at `initialize`
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub allowlist_enabled: bool,
+    pub bump: u8,
+}
+
+impl HookConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1;
+}
+
+#[account]
+pub struct AllowEntry {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub bump: u8,
+}
+
+impl AllowEntry {
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+}
+
+#[error_code]
+pub enum ShareHookError {
+    #[msg("Mint does not use this program as its transfer hook")]
+    HookNotAttached,
+    #[msg("Signer is not the share metadata update authority")]
+    Unauthorized,
+    #[msg("Hook called outside a Token-2022 transfer")]
+    NotTransferring,
+    #[msg("Destination owner is not on the share allowlist")]
+    NotAllowlisted,
+}
@@ -0,0 +1,51 @@
+// DEX integration module — Orca Whirlpool (CLMM) primary, per PRD §4.2.
+// Meteora fallback can be added as another `SwapAdapter` implementation.
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+pub mod whirlpool;
+pub mod slippage;
+
+pub use whirlpool::*;
+pub use slippage::*;
+
+/// Default `Vault::max_slippage_bps` set at initialization.
+pub const DEFAULT_MAX_SLIPPAGE_BPS: u16 = 30;
+
+/// A venue that can swap an exact input amount for at least `min_amount_out`.
+pub trait SwapAdapter {
+    fn swap_exact_in(&self, amount_in: u64, min_amount_out: u64) -> Result<()>;
+}
+
+/// Minimum acceptable output for swapping `amount_in`, derived from oracle
+/// prices (both normalised to 6dp) and discounted by `max_slippage_bps`.
+pub fn oracle_min_amount_out(
+    amount_in: u64,
+    price_in: i64,
+    price_out: i64,
+    decimals_in: u8,
+    decimals_out: u8,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    require!(price_in > 0 && price_out > 0, ErrorCode::PythPriceUnavailable);
+    require!(max_slippage_bps < 10_000, ErrorCode::InvalidSlippage);
+
+    // value_out = amount_in × price_in / price_out, rescaled between decimals
+    let mut numerator = (amount_in as u128)
+        .checked_mul(price_in as u128)
+        .and_then(|v| v.checked_mul((10_000 - max_slippage_bps) as u128))
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut denominator = (price_out as u128) * 10_000;
+
+    if decimals_out >= decimals_in {
+        numerator = numerator
+            .checked_mul(10u128.pow((decimals_out - decimals_in) as u32))
+            .ok_or(ErrorCode::MathOverflow)?;
+    } else {
+        denominator = denominator
+            .checked_mul(10u128.pow((decimals_in - decimals_out) as u32))
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    u64::try_from(numerator / denominator).map_err(|_| error!(ErrorCode::MathOverflow))
+}
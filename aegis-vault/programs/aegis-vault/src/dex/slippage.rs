@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::errors::ErrorCode;
+use super::{oracle_min_amount_out, SwapAdapter};
+
+/// Oracle prices (6dp, read before the swap) and tolerance bounding a swap.
+#[derive(Debug, Clone)]
+pub struct SwapBound {
+    pub price_in: i64,
+    pub price_out: i64,
+    pub decimals_in: u8,
+    pub decimals_out: u8,
+    pub max_slippage_bps: u16,
+}
+
+/// Run `adapter` and verify the realized execution price from the vault's
+/// own balance deltas, so a venue that under-reports or is sandwiched cannot
+/// pass just by honoring its `min_amount_out`. Returns the amount received.
+pub fn guarded_swap<'info, A: SwapAdapter>(
+    adapter: &A,
+    source: &mut InterfaceAccount<'info, TokenAccount>,
+    destination: &mut InterfaceAccount<'info, TokenAccount>,
+    amount_in: u64,
+    bound: &SwapBound,
+) -> Result<u64> {
+    let min_amount_out = min_out(amount_in, bound)?;
+    let source_before = source.amount;
+    let destination_before = destination.amount;
+
+    adapter.swap_exact_in(amount_in, min_amount_out)?;
+
+    source.reload()?;
+    destination.reload()?;
+    let spent = source_before
+        .checked_sub(source.amount)
+        .ok_or(ErrorCode::SlippageExceeded)?;
+    let received = destination
+        .amount
+        .checked_sub(destination_before)
+        .ok_or(ErrorCode::SlippageExceeded)?;
+
+    require!(spent <= amount_in, ErrorCode::SlippageExceeded);
+    require!(received >= min_out(spent, bound)?, ErrorCode::SlippageExceeded);
+
+    Ok(received)
+}
+
+fn min_out(amount_in: u64, bound: &SwapBound) -> Result<u64> {
+    oracle_min_amount_out(
+        amount_in,
+        bound.price_in,
+        bound.price_out,
+        bound.decimals_in,
+        bound.decimals_out,
+        bound.max_slippage_bps,
+    )
+}
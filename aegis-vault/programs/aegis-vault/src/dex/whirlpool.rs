@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use super::SwapAdapter;
+
+/// Orca Whirlpool program (mainnet and devnet)
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+// sha256("global:swap")[..8]
+const SWAP_IX: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+
+// Whirlpool sqrt-price bounds (Q64.64); used as "no limit" for each direction
+const MIN_SQRT_PRICE_X64: u128 = 4_295_048_016;
+const MAX_SQRT_PRICE_X64: u128 = 79_226_673_515_401_279_992_447_579_055;
+
+/// CPI adapter for a Whirlpool `swap`. `source`/`destination` are the vault's
+/// token accounts; they are mapped onto the pool's A/B sides by `a_to_b`.
+pub struct WhirlpoolSwap<'a, 'info> {
+    pub program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub whirlpool: AccountInfo<'info>,
+    pub source: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub token_vault_a: AccountInfo<'info>,
+    pub token_vault_b: AccountInfo<'info>,
+    pub tick_arrays: [AccountInfo<'info>; 3],
+    pub oracle: AccountInfo<'info>,
+    pub a_to_b: bool,
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+impl<'a, 'info> SwapAdapter for WhirlpoolSwap<'a, 'info> {
+    fn swap_exact_in(&self, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        let (owner_a, owner_b) = if self.a_to_b {
+            (&self.source, &self.destination)
+        } else {
+            (&self.destination, &self.source)
+        };
+        let sqrt_price_limit = if self.a_to_b {
+            MIN_SQRT_PRICE_X64
+        } else {
+            MAX_SQRT_PRICE_X64
+        };
+
+        let mut data = Vec::with_capacity(42);
+        data.extend_from_slice(&SWAP_IX);
+        data.extend_from_slice(&amount_in.to_le_bytes());
+        data.extend_from_slice(&min_amount_out.to_le_bytes());
+        data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
+        data.push(1); // amount_specified_is_input
+        data.push(self.a_to_b as u8);
+
+        let ix = Instruction {
+            program_id: self.program.key(),
+            accounts: vec![
+                AccountMeta::new_readonly(self.token_program.key(), false),
+                AccountMeta::new_readonly(self.authority.key(), true),
+                AccountMeta::new(self.whirlpool.key(), false),
+                AccountMeta::new(owner_a.key(), false),
+                AccountMeta::new(self.token_vault_a.key(), false),
+                AccountMeta::new(owner_b.key(), false),
+                AccountMeta::new(self.token_vault_b.key(), false),
+                AccountMeta::new(self.tick_arrays[0].key(), false),
+                AccountMeta::new(self.tick_arrays[1].key(), false),
+                AccountMeta::new(self.tick_arrays[2].key(), false),
+                AccountMeta::new_readonly(self.oracle.key(), false),
+            ],
+            data,
+        };
+
+        invoke_signed(
+            &ix,
+            &[
+                self.token_program.clone(),
+                self.authority.clone(),
+                self.whirlpool.clone(),
+                owner_a.clone(),
+                self.token_vault_a.clone(),
+                owner_b.clone(),
+                self.token_vault_b.clone(),
+                self.tick_arrays[0].clone(),
+                self.tick_arrays[1].clone(),
+                self.tick_arrays[2].clone(),
+                self.oracle.clone(),
+                self.program.clone(),
+            ],
+            self.signer_seeds,
+        )
+        .map_err(Into::into)
+    }
+}
@@ -17,9 +17,36 @@ pub enum ErrorCode {
     #[msg("Oracle price conflict detected")]
     OraclePriceConflict,
 
+    #[msg("Oracle feed accounts missing or do not match the registry")]
+    OracleFeedMismatch,
+
+    #[msg("Oracle registry is full")]
+    OracleRegistryFull,
+
+    #[msg("Oracle feed already registered, not registered or invalid")]
+    InvalidOracleFeed,
+
+    #[msg("Invalid oracle quorum parameter")]
+    InvalidQuorum,
+
+    #[msg("Debt asset feed missing or does not match the vault")]
+    DebtAssetMismatch,
+
     #[msg("Pyth price unavailable")]
     PythPriceUnavailable,
 
+    #[msg("Switchboard price unavailable")]
+    SwitchboardPriceUnavailable,
+
+    #[msg("Oracle price is zero or negative")]
+    NonPositivePrice,
+
+    #[msg("Oracle price out of representable range")]
+    PriceOutOfRange,
+
+    #[msg("Observation interval not elapsed")]
+    ObservationTooSoon,
+
     #[msg("Insufficient vault equity for withdrawal")]
     InsufficientEquity,
 
@@ -31,5 +58,52 @@ pub enum ErrorCode {
 
     #[msg("Invalid health factor parameter")]
     InvalidHealthFactor,
-}
 
+    #[msg("Invalid oracle confidence parameter")]
+    InvalidConfidence,
+
+    #[msg("Invalid oracle staleness parameter")]
+    InvalidStaleness,
+
+    #[msg("Invalid flash loan fee parameter")]
+    InvalidFlashFee,
+
+    #[msg("Lending venue accounts missing or do not match the vault")]
+    LendingVenueMismatch,
+
+    #[msg("Invalid slippage parameter")]
+    InvalidSlippage,
+
+    #[msg("Swap route not configured or accounts do not match the vault")]
+    SwapRouteMismatch,
+
+    #[msg("Swap realized price is worse than the oracle bound")]
+    SlippageExceeded,
+
+    #[msg("Deposit too small to mint a share")]
+    ZeroShares,
+
+    #[msg("Position still holds shares")]
+    PositionNotEmpty,
+
+    #[msg("Withdrawal would burn more shares than allowed")]
+    MaxSharesExceeded,
+
+    #[msg("Flash fee differs from the one the lending reserve charges")]
+    FlashFeeMismatch,
+
+    #[msg("Account already has the current layout")]
+    AlreadyMigrated,
+
+    #[msg("Share account required to close it")]
+    ShareAccountMissing,
+
+    #[msg("Referrer cannot be the payer or the beneficiary")]
+    SelfReferral,
+
+    #[msg("Deposit too small to credit a referrer")]
+    ReferredDepositTooSmall,
+
+    #[msg("Swaps only support SPL Token mints")]
+    UnsupportedTokenProgram,
+}
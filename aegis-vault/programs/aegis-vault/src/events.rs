@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Emitted by every deposit instruction
+#[event]
+pub struct DepositEvent {
+    /// Signer the USDC came from
+    pub depositor: Pubkey,
+    /// Receiver of the shares and owner of the position
+    pub owner: Pubkey,
+    /// USDC sent, before any Token-2022 transfer fee
+    pub amount: u64,
+    /// USDC credited to the vault
+    pub received: u64,
+    pub shares: u64,
+    /// Referrer credited in `ReferrerStats`, if any
+    pub referrer: Option<Pubkey>,
+}
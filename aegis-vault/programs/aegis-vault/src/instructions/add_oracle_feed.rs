@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::{OracleFeed, OracleKind, OracleRegistry, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct AddOracleFeed<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle-registry", vault.key().as_ref()],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    /// CHECK: Price feed account; its layout is validated by the matching oracle reader.
+    pub feed: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Register a feed. The feed that brings the registry to `min_quorum` pins it
+/// on the vault; from then on `rebalance` must be given the registry and its
+/// feeds instead of relying on the Pyth feed alone.
+pub fn handler(
+    ctx: Context<AddOracleFeed>,
+    kind: OracleKind,
+    weight: u16,
+    max_age_slots: u64,
+    max_age_sec: i64,
+    max_conf_bps: u16,
+) -> Result<()> {
+    require!(weight > 0, ErrorCode::InvalidOracleFeed);
+    require!(
+        max_age_slots > 0 && max_age_slots <= 1_500, // Max ~10 minutes
+        ErrorCode::InvalidStaleness
+    );
+    require!(
+        max_age_sec > 0 && max_age_sec <= 600,
+        ErrorCode::InvalidStaleness
+    );
+    require!(max_conf_bps > 0 && max_conf_bps <= 100, ErrorCode::InvalidConfidence); // Max 1%
+
+    let feed = ctx.accounts.feed.key();
+    let registry = &mut ctx.accounts.oracle_registry;
+    require!(!registry.contains(&feed), ErrorCode::InvalidOracleFeed);
+    require!(
+        registry.push(OracleFeed {
+            feed,
+            kind,
+            weight,
+            max_age_slots,
+            max_age_sec,
+            max_conf_bps,
+        }),
+        ErrorCode::OracleRegistryFull
+    );
+
+    msg!(
+        "Oracle feed added — {:?} {}, weight: {}, {} of {} feeds",
+        kind,
+        feed,
+        weight,
+        registry.len,
+        OracleRegistry::CAPACITY
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    if !vault.has_oracle_registry() && registry.len >= registry.min_quorum {
+        vault.oracle_registry = registry.key();
+        msg!("Oracle registry active — quorum of {} reached", registry.min_quorum);
+    }
+
+    Ok(())
+}
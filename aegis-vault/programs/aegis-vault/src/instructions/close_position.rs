@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+use crate::state::{Vault, UserPosition};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"user-position", user.key().as_ref()],
+        bump = user_position.bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    /// Position owner; receives the rent of everything closed
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(address = vault.share_mint)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    /// The owner's share ATA, the account deposits mint into. Left out when
+    /// it no longer exists; the position's own share count is checked instead.
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = user,
+        associated_token::token_program = share_token_program
+    )]
+    pub user_shares: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = vault.share_token_program)]
+    pub share_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Close the signer's position once their share ATA is empty, returning its
+/// rent, and optionally close the ATA too. Without the ATA, the position must
+/// record no shares. The cost basis and PnL history go with it; a later
+/// deposit opens a fresh position.
+pub fn handler(ctx: Context<ClosePosition>, close_share_account: bool) -> Result<()> {
+    let accounts = ctx.accounts;
+    match &accounts.user_shares {
+        Some(user_shares) => {
+            require!(user_shares.amount == 0, ErrorCode::PositionNotEmpty);
+            if close_share_account {
+                token_interface::close_account(CpiContext::new(
+                    accounts.share_token_program.to_account_info(),
+                    CloseAccount {
+                        account: user_shares.to_account_info(),
+                        destination: accounts.user.to_account_info(),
+                        authority: accounts.user.to_account_info(),
+                    },
+                ))?;
+            }
+        }
+        None => {
+            require!(accounts.user_position.shares == 0, ErrorCode::PositionNotEmpty);
+            require!(!close_share_account, ErrorCode::ShareAccountMissing);
+        }
+    }
+
+    msg!(
+        "Closed position for {} (share account closed: {})",
+        accounts.user.key(),
+        close_share_account
+    );
+
+    Ok(())
+}
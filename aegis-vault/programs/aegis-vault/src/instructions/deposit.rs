@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{
+    self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
 use crate::state::{Vault, UserPosition};
+use crate::errors::ErrorCode;
+use crate::events::DepositEvent;
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
@@ -19,94 +23,203 @@ pub struct Deposit<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
+    #[account(
+        address = vault.usdc_mint,
+        mint::token_program = token_program
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
     #[account(
         mut,
         constraint = user_usdc.mint == vault.usdc_mint,
         constraint = user_usdc.owner == user.key()
     )]
-    pub user_usdc: Account<'info, TokenAccount>,
+    pub user_usdc: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         seeds = [b"vault-usdc"],
         bump
     )]
-    pub vault_usdc: Account<'info, TokenAccount>,
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         seeds = [b"share-mint"],
         bump
     )]
-    pub share_mint: Account<'info, Mint>,
+    pub share_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = share_mint,
-        associated_token::authority = user
+        associated_token::authority = user,
+        associated_token::token_program = share_token_program
     )]
-    pub user_shares: Account<'info, TokenAccount>,
+    pub user_shares: InterfaceAccount<'info, TokenAccount>,
     
-    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022, whichever owns the USDC mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// SPL Token or Token-2022, whichever owns the share mint
+    #[account(address = vault.share_token_program)]
+    pub share_token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
+/// Deposits are never referred here; a referred deposit goes through
+/// `deposit_for` with the depositor as beneficiary
 pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
-    let user_position = &mut ctx.accounts.user_position;
-    let clock = Clock::get()?;
-    
-    // Calculate shares to mint
-    let shares = if vault.total_shares == 0 {
-        amount // 1:1 for first deposit
-    } else {
-        let equity = vault.equity();
-        (amount as u128)
-            .checked_mul(vault.total_shares as u128)
-            .and_then(|v| v.checked_div(equity as u128))
-            .ok_or(crate::errors::ErrorCode::MathOverflow)? as u64
-    };
-    
-    // Transfer USDC from user to vault
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.user_usdc.to_account_info(),
-                to: ctx.accounts.vault_usdc.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        amount,
-    )?;
-    
-    // Mint shares to user
-    let vault_seeds = &[b"vault".as_ref(), &[vault.bump]];
-    token::mint_to(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.share_mint.to_account_info(),
-                to: ctx.accounts.user_shares.to_account_info(),
-                authority: vault.to_account_info(),
-            },
-            &[vault_seeds],
-        ),
-        shares,
-    )?;
-    
-    // Update state
-    if user_position.owner == Pubkey::default() {
-        user_position.owner = ctx.accounts.user.key();
-        user_position.deposited_at = clock.unix_timestamp;
-        user_position.bump = ctx.bumps.user_position;
+    let accounts = ctx.accounts;
+    let deposited = DepositFlow {
+        vault: &mut accounts.vault,
+        user_position: Some(&mut accounts.user_position),
+        position_bump: ctx.bumps.user_position,
+        owner: accounts.user.key(),
+        depositor: accounts.user.to_account_info(),
+        source_usdc: accounts.user_usdc.to_account_info(),
+        usdc_mint: &accounts.usdc_mint,
+        vault_usdc: &mut accounts.vault_usdc,
+        share_mint: accounts.share_mint.to_account_info(),
+        owner_shares: &accounts.user_shares,
+        token_program: accounts.token_program.to_account_info(),
+        share_token_program: accounts.share_token_program.to_account_info(),
     }
-    user_position.shares += shares;
-    vault.total_shares += shares;
+    .execute(amount)?;
     
-    msg!("Deposited {} USDC, minted {} shares", amount, shares);
+    emit!(deposited.event(None));
     
     Ok(())
 }
+
+/// Accounts one deposit touches, whichever instruction supplied them. The
+/// caller's constraints have already tied each of them to the vault.
+pub(crate) struct DepositFlow<'a, 'info> {
+    pub vault: &'a mut Account<'info, Vault>,
+    /// Position of `owner`, possibly just created; deposits made without
+    /// one aren't cost-tracked
+    pub user_position: Option<&'a mut Account<'info, UserPosition>>,
+    pub position_bump: u8,
+    /// Who the shares and the position belong to
+    pub owner: Pubkey,
+    /// Authority over `source_usdc`
+    pub depositor: AccountInfo<'info>,
+    pub source_usdc: AccountInfo<'info>,
+    pub usdc_mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault_usdc: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub share_mint: AccountInfo<'info>,
+    /// `owner`'s share account
+    pub owner_shares: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: AccountInfo<'info>,
+    pub share_token_program: AccountInfo<'info>,
+}
+
+/// Outcome of a `DepositFlow`
+pub(crate) struct Deposited {
+    pub depositor: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub received: u64,
+    pub shares: u64,
+}
+
+impl Deposited {
+    pub fn event(&self, referrer: Option<Pubkey>) -> DepositEvent {
+        DepositEvent {
+            depositor: self.depositor,
+            owner: self.owner,
+            amount: self.amount,
+            received: self.received,
+            shares: self.shares,
+            referrer,
+        }
+    }
+}
+
+impl DepositFlow<'_, '_> {
+    pub fn execute(self, amount: u64) -> Result<Deposited> {
+        let idle = self.vault_usdc.amount;
+        let share_balance = self.owner_shares.amount;
+        
+        // Transfer USDC from depositor to vault
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.token_program,
+                TransferChecked {
+                    from: self.source_usdc,
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.vault_usdc.to_account_info(),
+                    authority: self.depositor.clone(),
+                },
+            ),
+            amount,
+            self.usdc_mint.decimals,
+        )?;
+        
+        // Credit what arrived: a Token-2022 transfer fee is withheld from `amount`
+        self.vault_usdc.reload()?;
+        let received = self.vault_usdc.amount
+            .checked_sub(idle)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        let vault = self.vault;
+        let user_position = self.user_position;
+        let clock = Clock::get()?;
+        
+        // Calculate shares to mint, rounded down: the remainder accrues to existing holders
+        let total_assets = vault.total_assets(idle).ok_or(ErrorCode::MathOverflow)?;
+        let minted = aegis_core::shares_for_deposit(received, vault.total_shares, total_assets)
+            .ok_or(ErrorCode::MathOverflow)?;
+        // The first deposit locks `DEAD_SHARES` in the supply without minting them
+        let shares = aegis_core::depositor_shares(minted, vault.total_shares)
+            .ok_or(ErrorCode::ZeroShares)?;
+        require!(shares > 0, ErrorCode::ZeroShares);
+        
+        // Mint shares to the owner
+        let vault_seeds = &[b"vault".as_ref(), &[vault.bump]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.share_token_program,
+                MintTo {
+                    mint: self.share_mint,
+                    to: self.owner_shares.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            shares,
+        )?;
+        
+        // Update state
+        if let Some(user_position) = user_position {
+            if user_position.owner == Pubkey::default() {
+                user_position.owner = self.owner;
+                user_position.bump = self.position_bump;
+            }
+            // Positions opened ahead of time by `open_position` start here
+            if user_position.deposited_at == 0 {
+                user_position.deposited_at = clock.unix_timestamp;
+            }
+            // Resync with the share balance at the pre-deposit NAV, then add this deposit at cost
+            let mut basis = user_position.cost_basis();
+            basis.sync(share_balance, vault.total_shares, total_assets)
+                .and_then(|_| basis.deposit(received, shares))
+                .ok_or(ErrorCode::MathOverflow)?;
+            user_position.set_cost_basis(basis);
+        }
+        vault.total_shares = vault.total_shares
+            .checked_add(minted)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        msg!("Deposited {} USDC ({} received), minted {} shares", amount, received, shares);
+        
+        Ok(Deposited {
+            depositor: self.depositor.key(),
+            owner: self.owner,
+            amount,
+            received,
+            shares,
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Vault, UserPosition};
+use super::deposit::DepositFlow;
+
+/// `deposit` for callers signing by CPI: nothing is created or paid for, so
+/// `authority` can be another program's PDA. Its share account must already
+/// exist and its position, if tracked, comes from `open_position`.
+#[derive(Accounts)]
+pub struct DepositCpi<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+
+    /// Omit to skip cost-basis tracking
+    #[account(
+        mut,
+        seeds = [b"user-position", authority.key().as_ref()],
+        bump = user_position.bump
+    )]
+    pub user_position: Option<Account<'info, UserPosition>>,
+
+    /// Signs for `source_usdc` and owns `owner_shares`
+    pub authority: Signer<'info>,
+
+    #[account(
+        address = vault.usdc_mint,
+        mint::token_program = token_program
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = source_usdc.mint == vault.usdc_mint
+    )]
+    pub source_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault-usdc"],
+        bump
+    )]
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"share-mint"],
+        bump
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    /// Any share account owned by `authority`, not necessarily its ATA
+    #[account(
+        mut,
+        constraint = owner_shares.mint == share_mint.key(),
+        constraint = owner_shares.owner == authority.key()
+    )]
+    pub owner_shares: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token or Token-2022, whichever owns the USDC mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// SPL Token or Token-2022, whichever owns the share mint
+    #[account(address = vault.share_token_program)]
+    pub share_token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(ctx: Context<DepositCpi>, amount: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    let deposited = DepositFlow {
+        vault: &mut accounts.vault,
+        user_position: accounts.user_position.as_mut(),
+        // Positions are opened by `open_position`, which records the bump
+        position_bump: 0,
+        owner: accounts.authority.key(),
+        depositor: accounts.authority.to_account_info(),
+        source_usdc: accounts.source_usdc.to_account_info(),
+        usdc_mint: &accounts.usdc_mint,
+        vault_usdc: &mut accounts.vault_usdc,
+        share_mint: accounts.share_mint.to_account_info(),
+        owner_shares: &accounts.owner_shares,
+        token_program: accounts.token_program.to_account_info(),
+        share_token_program: accounts.share_token_program.to_account_info(),
+    }
+    .execute(amount)?;
+
+    emit!(deposited.event(None));
+
+    Ok(())
+}
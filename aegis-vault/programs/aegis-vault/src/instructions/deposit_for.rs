@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Vault, UserPosition, ReferrerStats};
+use crate::errors::ErrorCode;
+use super::deposit::DepositFlow;
+
+/// `deposit` with the paying signer and the share owner split: `payer`
+/// supplies the USDC and rent, `beneficiary` gets the shares and the position.
+/// Passing a referrer's stats account attributes the deposit to them, unless
+/// they are the payer or the beneficiary. Plain `deposit` takes no referrer.
+#[derive(Accounts)]
+pub struct DepositFor<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserPosition::LEN,
+        seeds = [b"user-position", beneficiary.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: any wallet or PDA; only its key is used, as share owner
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        address = vault.usdc_mint,
+        mint::token_program = token_program
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = payer_usdc.mint == vault.usdc_mint,
+        constraint = payer_usdc.owner == payer.key()
+    )]
+    pub payer_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault-usdc"],
+        bump
+    )]
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"share-mint"],
+        bump
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = share_mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = share_token_program
+    )]
+    pub beneficiary_shares: InterfaceAccount<'info, TokenAccount>,
+
+    /// The referrer's stats, opened beforehand with `init_referrer`; the
+    /// deposit must receive at least `ReferrerStats::MIN_DEPOSIT`
+    #[account(
+        mut,
+        seeds = [b"referrer-stats", vault.key().as_ref(), referrer_stats.referrer.as_ref()],
+        bump = referrer_stats.bump
+    )]
+    pub referrer_stats: Option<Account<'info, ReferrerStats>>,
+
+    /// SPL Token or Token-2022, whichever owns the USDC mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// SPL Token or Token-2022, whichever owns the share mint
+    #[account(address = vault.share_token_program)]
+    pub share_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositFor>, amount: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    if let Some(stats) = &accounts.referrer_stats {
+        require!(
+            stats.referrer != accounts.payer.key()
+                && stats.referrer != accounts.beneficiary.key(),
+            ErrorCode::SelfReferral
+        );
+    }
+
+    let deposited = DepositFlow {
+        vault: &mut accounts.vault,
+        user_position: Some(&mut accounts.user_position),
+        position_bump: ctx.bumps.user_position,
+        owner: accounts.beneficiary.key(),
+        depositor: accounts.payer.to_account_info(),
+        source_usdc: accounts.payer_usdc.to_account_info(),
+        usdc_mint: &accounts.usdc_mint,
+        vault_usdc: &mut accounts.vault_usdc,
+        share_mint: accounts.share_mint.to_account_info(),
+        owner_shares: &accounts.beneficiary_shares,
+        token_program: accounts.token_program.to_account_info(),
+        share_token_program: accounts.share_token_program.to_account_info(),
+    }
+    .execute(amount)?;
+
+    // Credit the referrer
+    let referrer = match &mut accounts.referrer_stats {
+        Some(stats) => {
+            require!(
+                deposited.received >= ReferrerStats::MIN_DEPOSIT,
+                ErrorCode::ReferredDepositTooSmall
+            );
+            stats.deposit_count = stats.deposit_count
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            stats.total_deposited = stats.total_deposited
+                .checked_add(deposited.received)
+                .ok_or(ErrorCode::MathOverflow)?;
+            stats.total_shares = stats.total_shares
+                .checked_add(deposited.shares)
+                .ok_or(ErrorCode::MathOverflow)?;
+            Some(stats.referrer)
+        }
+        None => None,
+    };
+
+    emit!(deposited.event(referrer));
+
+    Ok(())
+}
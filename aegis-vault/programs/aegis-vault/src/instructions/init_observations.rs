@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::{PegObservations, Vault};
+
+#[derive(Accounts)]
+pub struct InitObservations<'info> {
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = PegObservations::LEN,
+        seeds = [b"peg-observations", vault.key().as_ref()],
+        bump
+    )]
+    pub peg_observations: Account<'info, PegObservations>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitObservations>) -> Result<()> {
+    let observations = &mut ctx.accounts.peg_observations;
+    observations.vault = ctx.accounts.vault.key();
+    observations.head = 0;
+    observations.len = 0;
+    observations.bump = ctx.bumps.peg_observations;
+
+    msg!("Peg observation buffer initialized ({} samples)", PegObservations::CAPACITY);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::{OracleRegistry, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitOracleRegistry<'info> {
+    #[account(has_one = authority)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OracleRegistry::LEN,
+        seeds = [b"oracle-registry", vault.key().as_ref()],
+        bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the vault's oracle registry, empty. It takes over from the Pyth
+/// feed alone once `add_oracle_feed` has registered `min_quorum` feeds.
+pub fn handler(
+    ctx: Context<InitOracleRegistry>,
+    min_quorum: u8,
+    max_disagreement_bps: u16,
+) -> Result<()> {
+    require!(
+        min_quorum > 0 && min_quorum as usize <= OracleRegistry::CAPACITY,
+        ErrorCode::InvalidQuorum
+    );
+    require!(
+        max_disagreement_bps > 0 && max_disagreement_bps <= 100, // Max 1%
+        ErrorCode::InvalidQuorum
+    );
+
+    let registry = &mut ctx.accounts.oracle_registry;
+    registry.vault = ctx.accounts.vault.key();
+    registry.min_quorum = min_quorum;
+    registry.max_disagreement_bps = max_disagreement_bps;
+    registry.len = 0;
+    registry.bump = ctx.bumps.oracle_registry;
+
+    msg!(
+        "Oracle registry initialized — min_quorum: {}, max_disagreement: {}bps",
+        min_quorum,
+        max_disagreement_bps
+    );
+
+    Ok(())
+}
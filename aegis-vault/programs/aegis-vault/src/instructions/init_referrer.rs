@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::{ReferrerStats, Vault};
+
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct InitReferrer<'info> {
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ReferrerStats::LEN,
+        seeds = [b"referrer-stats", vault.key().as_ref(), referrer.as_ref()],
+        bump
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: open the stats account `deposit_for` credits `referrer` in
+pub fn handler(ctx: Context<InitReferrer>, referrer: Pubkey) -> Result<()> {
+    let stats = &mut ctx.accounts.referrer_stats;
+    stats.vault = ctx.accounts.vault.key();
+    stats.referrer = referrer;
+    stats.deposit_count = 0;
+    stats.total_deposited = 0;
+    stats.total_shares = 0;
+    stats.bump = ctx.bumps.referrer_stats;
+
+    msg!("Referrer stats initialized for {}", referrer);
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::Vault;
 use crate::errors::ErrorCode;
 
@@ -17,7 +18,9 @@ pub struct InitializeVault<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    /// USDC, or any 6-decimal stable under SPL Token or Token-2022
+    #[account(mint::token_program = token_program)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
@@ -25,9 +28,10 @@ pub struct InitializeVault<'info> {
         seeds = [b"vault-usdc"],
         bump,
         token::mint = usdc_mint,
-        token::authority = vault
+        token::authority = vault,
+        token::token_program = token_program
     )]
-    pub vault_usdc: Account<'info, TokenAccount>,
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
@@ -35,15 +39,18 @@ pub struct InitializeVault<'info> {
         seeds = [b"share-mint"],
         bump,
         mint::decimals = 6,
-        mint::authority = vault
+        mint::authority = vault,
+        mint::token_program = share_token_program
     )]
-    pub share_mint: Account<'info, Mint>,
+    pub share_mint: InterfaceAccount<'info, Mint>,
 
     /// CHECK: Pyth USDC/USD price feed; validated by the oracle module at rebalance time.
     pub pyth_usdc_feed: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    /// Program owning `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Program<'info, Token>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -52,22 +59,40 @@ pub fn handler(
     max_leverage_bps: u16,
     hf_floor_bps: u16,
 ) -> Result<()> {
-    require!(max_leverage_bps <= 20_000, ErrorCode::InvalidLeverage);    // Max 2.0x
-    require!(hf_floor_bps >= 10_000, ErrorCode::InvalidHealthFactor);   // Min 1.0
-
     let vault = &mut ctx.accounts.vault;
+    init_defaults(vault, max_leverage_bps, hf_floor_bps)?;
 
     vault.authority = ctx.accounts.authority.key();
     vault.usdc_mint = ctx.accounts.usdc_mint.key();
     vault.vault_usdc = ctx.accounts.vault_usdc.key();
     vault.share_mint = ctx.accounts.share_mint.key();
+    vault.share_token_program = ctx.accounts.share_token_program.key();
     vault.pyth_usdc_feed = ctx.accounts.pyth_usdc_feed.key();
+    vault.bump = ctx.bumps.vault;
+
+    msg!(
+        "Vault initialized — max_leverage: {}bps, hf_floor: {}bps",
+        max_leverage_bps,
+        hf_floor_bps
+    );
+
+    Ok(())
+}
+
+/// Validate the init parameters and set everything but the vault's accounts;
+/// shared by `initialize_vault` and `initialize_vault_2022`
+pub(crate) fn init_defaults(vault: &mut Vault, max_leverage_bps: u16, hf_floor_bps: u16) -> Result<()> {
+    require!(max_leverage_bps <= 20_000, ErrorCode::InvalidLeverage);    // Max 2.0x
+    require!(hf_floor_bps >= 10_000, ErrorCode::InvalidHealthFactor);   // Min 1.0
 
     vault.total_supplied = 0;
     vault.total_borrowed = 0;
     vault.total_shares = 0;
     vault.last_rebalance_slot = 0;
     vault.reexpansion_unlocked_at = 0;
+    vault.last_good_price = 0;
+    vault.last_good_slot = 0;
+    vault.stale_since_slot = 0;
 
     // Hard-coded constants from PRD §4.1
     vault.max_leverage_bps = max_leverage_bps;
@@ -78,14 +103,22 @@ pub fn handler(
     vault.peg_panic_bps = 50;
     vault.cooldown_slots = 30_000;
     vault.reexpansion_delay_sec = 30_000;
+    appended_defaults(vault);
 
-    vault.bump = ctx.bumps.vault;
+    Ok(())
+}
 
-    msg!(
-        "Vault initialized — max_leverage: {}bps, hf_floor: {}bps",
-        max_leverage_bps,
-        hf_floor_bps
-    );
+/// Defaults for the parameters appended after the original layout; also
+/// what `migrate_vault` gives a vault created before them
+pub(crate) fn appended_defaults(vault: &mut Vault) {
+    vault.dex_program = crate::dex::WHIRLPOOL_PROGRAM_ID;
+    vault.max_slippage_bps = crate::dex::DEFAULT_MAX_SLIPPAGE_BPS;
 
-    Ok(())
+    // PRD §4.1
+    vault.oracle_max_age_sec = 60;
+    vault.stale_grace_slots = 750;
+    vault.max_conf_bps = 100;
+    vault.peg_use_conf_edge = true;
+    vault.twap_window_slots = 750;
+    vault.observation_interval_slots = 25;
 }
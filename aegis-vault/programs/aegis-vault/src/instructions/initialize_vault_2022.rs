@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{
+    self, spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::TokenMetadata, InitializeMint2, MetadataPointerInitialize,
+    Mint, Token2022, TokenAccount, TokenInterface, TokenMetadataInitialize, TransferHookInitialize,
+};
+use crate::state::Vault;
+use super::initialize_vault::init_defaults;
+
+/// Same vault as `initialize_vault`, but the share mint is a Token-2022 mint
+/// carrying its own name/symbol/URI (metadata pointer to itself), so wallets
+/// can display it. A transfer-hook program can be attached at creation; the
+/// choice is fixed for the life of the mint.
+#[derive(Accounts)]
+pub struct InitializeVault2022<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Vault::LEN,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Pays for the accounts and becomes the metadata update authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// USDC, or any 6-decimal stable under SPL Token or Token-2022
+    #[account(mint::token_program = token_program)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vault-usdc"],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: created and initialized by the handler; its size depends on the
+    /// extensions, which Anchor's `mint::` constraints can't make conditional
+    #[account(
+        mut,
+        seeds = [b"share-mint"],
+        bump
+    )]
+    pub share_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Pyth USDC/USD price feed; validated by the oracle module at rebalance time.
+    pub pyth_usdc_feed: AccountInfo<'info>,
+
+    /// CHECK: any executable implementing the SPL transfer-hook interface,
+    /// e.g. `aegis-share-hook`; omit for a freely transferable share
+    #[account(executable)]
+    pub transfer_hook_program: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+    /// Program owning `usdc_mint`
+    pub token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Program<'info, Token2022>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeVault2022>,
+    max_leverage_bps: u16,
+    hf_floor_bps: u16,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    let vault = &mut accounts.vault;
+    init_defaults(vault, max_leverage_bps, hf_floor_bps)?;
+
+    vault.authority = accounts.authority.key();
+    vault.usdc_mint = accounts.usdc_mint.key();
+    vault.vault_usdc = accounts.vault_usdc.key();
+    vault.share_mint = accounts.share_mint.key();
+    vault.share_token_program = accounts.share_token_program.key();
+    vault.pyth_usdc_feed = accounts.pyth_usdc_feed.key();
+    vault.bump = ctx.bumps.vault;
+
+    let hook_program = accounts.transfer_hook_program.as_ref().map(|p| p.key());
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if hook_program.is_some() {
+        extensions.push(ExtensionType::TransferHook);
+    }
+    let space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+
+    // Token-2022 reallocs the mint when the metadata is written but doesn't
+    // fund it: pay rent for the final size up front
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(accounts.authority.key()))?,
+        mint: accounts.share_mint.key(),
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+    let lamports = Rent::get()?.minimum_balance(space + metadata.tlv_size_of()?);
+
+    let token_program = accounts.share_token_program.to_account_info();
+    let share_mint = accounts.share_mint.to_account_info();
+    let share_mint_seeds = &[b"share-mint".as_ref(), &[ctx.bumps.share_mint]];
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: accounts.authority.to_account_info(),
+                to: share_mint.clone(),
+            },
+            &[share_mint_seeds],
+        ),
+        lamports,
+        space as u64,
+        &token_program.key(),
+    )?;
+
+    // Extensions must be initialized before the mint itself
+    token_interface::metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program.clone(),
+                mint: share_mint.clone(),
+            },
+        ),
+        None,
+        Some(share_mint.key()),
+    )?;
+    if let Some(hook_program) = hook_program {
+        token_interface::transfer_hook_initialize(
+            CpiContext::new(
+                token_program.clone(),
+                TransferHookInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: share_mint.clone(),
+                },
+            ),
+            None,
+            Some(hook_program),
+        )?;
+    }
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            token_program.clone(),
+            InitializeMint2 {
+                mint: share_mint.clone(),
+            },
+        ),
+        6,
+        &vault.key(),
+        None,
+    )?;
+
+    let vault_seeds = &[b"vault".as_ref(), &[vault.bump]];
+    token_interface::token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataInitialize {
+                token_program_id: token_program,
+                metadata: share_mint.clone(),
+                update_authority: accounts.authority.to_account_info(),
+                mint_authority: vault.to_account_info(),
+                mint: share_mint,
+            },
+            &[vault_seeds],
+        ),
+        name,
+        symbol,
+        uri,
+    )?;
+
+    msg!(
+        "Vault initialized (Token-2022 shares) — max_leverage: {}bps, hf_floor: {}bps, transfer hook: {:?}",
+        max_leverage_bps,
+        hf_floor_bps,
+        hook_program
+    );
+
+    Ok(())
+}
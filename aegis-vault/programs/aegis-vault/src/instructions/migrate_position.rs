@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{Vault, UserPosition};
+use crate::errors::ErrorCode;
+
+/// Grow a position opened with the original `UserPosition::LEGACY_LEN`
+/// layout to `UserPosition::LEN`. Its history is unknown, so the position
+/// starts as if its shares had arrived by transfer: costed at current NAV,
+/// with no deposits, withdrawals or realized PnL. Permissionless; `payer`
+/// covers the extra rent.
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: too short to load as `UserPosition`; discriminator and seeds
+    /// are checked by hand
+    #[account(mut, owner = crate::ID)]
+    pub user_position: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"vault-usdc"],
+        bump
+    )]
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigratePosition>) -> Result<()> {
+    let position = ctx.accounts.user_position.to_account_info();
+    require!(position.data_len() == UserPosition::LEGACY_LEN, ErrorCode::AlreadyMigrated);
+    {
+        let data = position.try_borrow_data()?;
+        require!(
+            data[..8] == UserPosition::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        // `owner` follows the discriminator; `bump` is the last byte
+        let owner = &data[8..40];
+        let bump = data[UserPosition::LEGACY_LEN - 1];
+        let address = Pubkey::create_program_address(
+            &[b"user-position", owner, &[bump]],
+            &crate::ID,
+        )
+        .map_err(|_| anchor_lang::error::ErrorCode::ConstraintSeeds)?;
+        require_keys_eq!(
+            address,
+            position.key(),
+            anchor_lang::error::ErrorCode::ConstraintSeeds
+        );
+    }
+
+    let rent = Rent::get()?
+        .minimum_balance(UserPosition::LEN)
+        .saturating_sub(position.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: position.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+    position.realloc(UserPosition::LEN, true)?;
+
+    // The zeroed tail reads back as an empty cost basis
+    let mut state = UserPosition::try_deserialize(&mut &position.try_borrow_data()?[..])?;
+    let vault = &ctx.accounts.vault;
+    let total_assets = vault
+        .total_assets(ctx.accounts.vault_usdc.amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut basis = aegis_core::CostBasis::default();
+    basis
+        .sync(state.shares, vault.total_shares, total_assets)
+        .ok_or(ErrorCode::MathOverflow)?;
+    state.set_cost_basis(basis);
+    state.try_serialize(&mut &mut position.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Position {} migrated: {} shares costed at {}",
+        state.owner,
+        state.shares,
+        state.cost_basis
+    );
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token::spl_token;
+use crate::state::Vault;
+use crate::errors::ErrorCode;
+use super::initialize_vault::appended_defaults;
+
+/// Grow a vault created with the original `Vault::LEGACY_LEN` layout to
+/// `Vault::LEN`. The original fields keep their offsets; the appended ones
+/// get the defaults a new vault starts with, and the share mint is the
+/// classic SPL mint `initialize_vault` created. The authority pays the rent.
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// CHECK: too short to load as `Vault`; discriminator and authority are
+    /// checked by hand
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump,
+        owner = crate::ID
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateVault>) -> Result<()> {
+    let vault = ctx.accounts.vault.to_account_info();
+    require!(vault.data_len() == Vault::LEGACY_LEN, ErrorCode::AlreadyMigrated);
+    {
+        let data = vault.try_borrow_data()?;
+        require!(
+            data.len() >= 40,
+            anchor_lang::error::ErrorCode::AccountDidNotDeserialize
+        );
+        require!(
+            data[..8] == Vault::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        // `authority` is the first field, right after the discriminator
+        let authority = Pubkey::try_from(&data[8..40])
+            .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize)?;
+        require_keys_eq!(
+            authority,
+            ctx.accounts.authority.key(),
+            anchor_lang::error::ErrorCode::ConstraintHasOne
+        );
+    }
+
+    let rent = Rent::get()?
+        .minimum_balance(Vault::LEN)
+        .saturating_sub(vault.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: vault.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+    vault.realloc(Vault::LEN, true)?;
+
+    // The zeroed tail reads back as default values
+    let mut state = Vault::try_deserialize(&mut &vault.try_borrow_data()?[..])?;
+    state.share_token_program = spl_token::ID;
+    appended_defaults(&mut state);
+    state.try_serialize(&mut &mut vault.try_borrow_mut_data()?[..])?;
+
+    msg!("Vault migrated to {} bytes", Vault::LEN);
+
+    Ok(())
+}
@@ -1,9 +1,48 @@
 pub mod initialize_vault;
+pub mod initialize_vault_2022;
+pub mod migrate_vault;
 pub mod deposit;
+pub mod deposit_for;
+pub mod deposit_cpi;
+pub mod open_position;
+pub mod migrate_position;
+pub mod init_referrer;
 pub mod withdraw;
+pub mod withdraw_assets;
+pub mod position_pnl;
+pub mod close_position;
 pub mod rebalance;
+pub mod init_observations;
+pub mod record_observation;
+pub mod init_oracle_registry;
+pub mod add_oracle_feed;
+pub mod remove_oracle_feed;
+pub mod set_debt_asset;
+pub mod set_lending_venue;
+pub mod set_oracle_config;
+pub mod set_swap_route;
+pub mod swap_to_usdc;
 
 pub use initialize_vault::*;
+pub use initialize_vault_2022::*;
+pub use migrate_vault::*;
 pub use deposit::*;
+pub use deposit_for::*;
+pub use deposit_cpi::*;
+pub use open_position::*;
+pub use migrate_position::*;
+pub use init_referrer::*;
 pub use withdraw::*;
+pub use position_pnl::*;
+pub use close_position::*;
 pub use rebalance::*;
+pub use init_observations::*;
+pub use record_observation::*;
+pub use init_oracle_registry::*;
+pub use add_oracle_feed::*;
+pub use remove_oracle_feed::*;
+pub use set_debt_asset::*;
+pub use set_lending_venue::*;
+pub use set_oracle_config::*;
+pub use set_swap_route::*;
+pub use swap_to_usdc::*;
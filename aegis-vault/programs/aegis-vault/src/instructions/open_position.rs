@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::UserPosition;
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct OpenPosition<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = UserPosition::LEN,
+        seeds = [b"user-position", owner.as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: create `owner`'s position ahead of `deposit_cpi`, so the
+/// depositing program never has to pay rent or create accounts itself
+pub fn handler(ctx: Context<OpenPosition>, owner: Pubkey) -> Result<()> {
+    let position = &mut ctx.accounts.user_position;
+    position.owner = owner;
+    position.bump = ctx.bumps.user_position;
+
+    msg!("Position opened for {}", owner);
+
+    Ok(())
+}
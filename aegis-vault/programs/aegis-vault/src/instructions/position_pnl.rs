@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::state::{Vault, UserPosition};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct PositionPnl<'info> {
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        seeds = [b"user-position", user_position.owner.as_ref()],
+        bump = user_position.bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    /// The position owner's share account; its balance is what gets valued
+    #[account(
+        constraint = owner_shares.mint == vault.share_mint,
+        constraint = owner_shares.owner == user_position.owner
+    )]
+    pub owner_shares: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"vault-usdc"],
+        bump
+    )]
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Returned by `position_pnl`, in base-asset units
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PnlReport {
+    pub shares: u64,
+    /// What `shares` would redeem for at current NAV
+    pub value: u64,
+    pub cost_basis: u64,
+    /// Assets paid per share, scaled by 1e6
+    pub avg_entry_price_e6: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub realized_pnl: i64,
+    pub unrealized_pnl: i64,
+}
+
+/// Read-only: value the owner's shares at current NAV against their cost
+/// basis. Transfers since the last deposit or withdrawal are folded in the
+/// same way those instructions would, without writing the position.
+pub fn handler(ctx: Context<PositionPnl>) -> Result<PnlReport> {
+    let vault = &ctx.accounts.vault;
+    let share_balance = ctx.accounts.owner_shares.amount;
+
+    let total_assets = vault
+        .total_assets(ctx.accounts.vault_usdc.amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let mut basis = ctx.accounts.user_position.cost_basis();
+    basis
+        .sync(share_balance, vault.total_shares, total_assets)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // No shares outstanding means nothing to value
+    let value = match basis.shares {
+        0 => 0,
+        shares => aegis_core::assets_for_shares(shares, vault.total_shares, total_assets)
+            .ok_or(ErrorCode::MathOverflow)?,
+    };
+    let unrealized_pnl = basis.unrealized_pnl(value).ok_or(ErrorCode::MathOverflow)?;
+
+    msg!(
+        "Position {}: value {}, cost {}, realized {}, unrealized {}",
+        ctx.accounts.user_position.owner,
+        value,
+        basis.cost,
+        basis.realized_pnl,
+        unrealized_pnl
+    );
+
+    Ok(PnlReport {
+        shares: basis.shares,
+        value,
+        cost_basis: basis.cost,
+        avg_entry_price_e6: basis.avg_entry_price_e6(),
+        total_deposited: basis.total_deposited,
+        total_withdrawn: basis.total_withdrawn,
+        realized_pnl: basis.realized_pnl,
+        unrealized_pnl,
+    })
+}
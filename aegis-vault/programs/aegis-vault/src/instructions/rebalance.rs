@@ -1,6 +1,14 @@
 use anchor_lang::prelude::*;
-use crate::state::Vault;
-use crate::logic::{determine_vault_state, VaultState};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use crate::state::{Observation, OracleRegistry, PegObservations, Vault};
+use crate::logic::{
+    calculate_cross_rate_bps, calculate_peg_bps, determine_vault_state, max_peg_deviation_bps,
+    twap, two_asset_peg_bps, VaultState,
+};
+use crate::lending::{
+    calculate_repay_amount, execute_leverage, execute_unwind, plan_leverage, plan_unwind,
+    CpiLendingVenue, CONTRACT_REDUCTION_PCT, STALE_CONTRACT_REDUCTION_PCT,
+};
 use crate::errors::ErrorCode;
 
 #[derive(Accounts)]
@@ -14,10 +22,91 @@ pub struct Rebalance<'info> {
     /// CHECK: Pyth USDC/USD price feed — validated inside fetch_oracle_data().
     pub pyth_feed: AccountInfo<'info>,
 
-    // V2: Add Kamino lending accounts here when CPI is implemented.
+    #[account(
+        mut,
+        seeds = [b"vault-usdc"],
+        bump
+    )]
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    /// Peg TWAP buffer from `init_observations`; without it the vault
+    /// decides on the spot peg alone
+    #[account(
+        mut,
+        seeds = [b"peg-observations", vault.key().as_ref()],
+        bump = peg_observations.bump
+    )]
+    pub peg_observations: Option<Account<'info, PegObservations>>,
+
+    /// Required once the registry holds `min_quorum` feeds and is pinned on
+    /// the vault; its feed accounts are passed as remaining accounts.
+    #[account(address = vault.oracle_registry @ ErrorCode::OracleFeedMismatch)]
+    pub oracle_registry: Option<Account<'info, OracleRegistry>>,
+
+    /// CHECK: Pyth feed of the borrowed asset; required once `set_debt_asset` has been called.
+    #[account(address = vault.debt_feed @ ErrorCode::DebtAssetMismatch)]
+    pub debt_feed: Option<UncheckedAccount<'info>>,
+
+    // Lending venue accounts — omit all four to run without a venue (log-only).
+
+    /// CHECK: Lending venue program, pinned by `set_lending_venue`.
+    #[account(address = vault.lending_program @ ErrorCode::LendingVenueMismatch)]
+    pub lending_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Venue reserve, pinned by `set_lending_venue`.
+    #[account(mut, address = vault.lending_reserve @ ErrorCode::LendingVenueMismatch)]
+    pub lending_reserve: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Vault's obligation at the venue, pinned by `set_lending_venue`.
+    #[account(mut, address = vault.lending_obligation @ ErrorCode::LendingVenueMismatch)]
+    pub lending_obligation: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Reserve liquidity supply; validated against the reserve by the venue.
+    #[account(mut)]
+    pub lending_liquidity: Option<UncheckedAccount<'info>>,
+
+    /// Program owning the vault's USDC, passed through to the venue
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<Rebalance>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault_bump = [ctx.accounts.vault.bump];
+    let vault_seeds: &[&[u8]] = &[b"vault".as_ref(), &vault_bump];
+    let signer_seeds = &[vault_seeds];
+    let venue = match (
+        &ctx.accounts.lending_program,
+        &ctx.accounts.lending_reserve,
+        &ctx.accounts.lending_obligation,
+        &ctx.accounts.lending_liquidity,
+    ) {
+        (Some(program), Some(reserve), Some(obligation), Some(liquidity)) => Some(CpiLendingVenue {
+            program: program.to_account_info(),
+            reserve: reserve.to_account_info(),
+            obligation: obligation.to_account_info(),
+            liquidity: liquidity.to_account_info(),
+            vault_usdc: ctx.accounts.vault_usdc.to_account_info(),
+            vault: vault_info,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        }),
+        (None, None, None, None) => None,
+        _ => return Err(ErrorCode::LendingVenueMismatch.into()),
+    };
+    require!(
+        venue.is_some() || !ctx.accounts.vault.has_lending_venue(),
+        ErrorCode::LendingVenueMismatch
+    );
+    require!(
+        ctx.accounts.oracle_registry.is_some() || !ctx.accounts.vault.has_oracle_registry(),
+        ErrorCode::OracleFeedMismatch
+    );
+    require!(
+        ctx.accounts.debt_feed.is_some() == ctx.accounts.vault.has_debt_asset(),
+        ErrorCode::DebtAssetMismatch
+    );
+    let idle = ctx.accounts.vault_usdc.amount;
+
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
 
@@ -27,17 +116,121 @@ pub fn handler(ctx: Context<Rebalance>) -> Result<()> {
         ErrorCode::RebalanceCooldown
     );
 
-    // --- Oracle read (Pyth only in v1) ---
-    let oracle_data = crate::oracles::fetch_oracle_data(
-        &ctx.accounts.pyth_feed,
-        vault.oracle_stale_slots,
-    )?;
+    // --- Oracle read: median-of-N over the registry, else Pyth alone ---
+    let oracle_data = match &ctx.accounts.oracle_registry {
+        Some(registry) => {
+            let data = crate::oracles::fetch_aggregated_oracle_data(
+                registry,
+                ctx.remaining_accounts,
+                vault.peg_use_conf_edge,
+                vault.last_good_price,
+            )?;
+            msg!(
+                "Oracle quorum {}/{} (min {}), max disagreement {}bps",
+                data.quorum,
+                registry.len,
+                registry.min_quorum,
+                data.max_disagreement_bps
+            );
+            data
+        }
+        None => crate::oracles::fetch_oracle_data(
+            &ctx.accounts.pyth_feed,
+            vault.staleness(),
+            vault.peg_use_conf_edge,
+        )?,
+    };
+
+    // --- Debt leg (two-asset vaults only) ---
+    let debt_data = match &ctx.accounts.debt_feed {
+        Some(feed) => Some(crate::oracles::fetch_oracle_data(
+            feed,
+            vault.staleness(),
+            vault.peg_use_conf_edge,
+        )?),
+        None => None,
+    };
+    let debt_stale = debt_data.as_ref().is_some_and(|d| d.is_stale);
 
-    let oracle_quality_ok = crate::oracles::validate_oracle_quality(&ctx.accounts.pyth_feed)?;
-    if !oracle_quality_ok {
-        msg!("WARNING: Pyth confidence interval is wide — proceeding conservatively");
+    let oracle_unreliable =
+        !crate::oracles::validate_oracle_quality(&oracle_data, vault.max_conf_bps)
+            || debt_data
+                .as_ref()
+                .is_some_and(|d| !crate::oracles::validate_oracle_quality(d, vault.max_conf_bps));
+    if oracle_unreliable {
+        msg!(
+            "WARNING: Oracle confidence {}bps exceeds max {}bps — oracle treated as unreliable",
+            oracle_data.conf_bps,
+            vault.max_conf_bps
+        );
     }
 
+    // --- Staleness: keep the last-known-good price, time the stale period ---
+    let oracle_stale_for = vault.track_oracle(
+        oracle_data.price,
+        oracle_data.is_stale || debt_stale,
+        !oracle_unreliable && !oracle_data.quorum_lost,
+        clock.slot,
+    );
+    // A stale spot price is not trusted; fall back to the last-known-good
+    // price (none yet → maximal deviation)
+    let spot_peg_bps = if oracle_data.is_stale {
+        calculate_peg_bps(vault.last_good_price, None)
+    } else {
+        oracle_data.peg_deviation_bps
+    };
+
+    // --- Peg TWAP: record spot sample, decide on TWAP and window max, not spot ---
+    let (peg_bps, peg_window_max_bps) = match &mut ctx.accounts.peg_observations {
+        Some(observations) => {
+            if !oracle_data.is_stale
+                && observations.is_due(clock.slot, vault.observation_interval_slots)
+            {
+                observations.push(Observation {
+                    slot: clock.slot,
+                    price: oracle_data.price,
+                    conf: oracle_data.conf,
+                });
+            }
+            let peg_bps = twap(observations, clock.slot, vault.twap_window_slots)
+                .map_or(spot_peg_bps, |t| {
+                    calculate_peg_bps(t.price, vault.peg_use_conf_edge.then_some(t.conf))
+                });
+            let peg_window_max_bps = max_peg_deviation_bps(
+                observations,
+                clock.slot,
+                vault.twap_window_slots,
+                vault.peg_use_conf_edge,
+            )
+            .unwrap_or(0)
+            .max(spot_peg_bps);
+            (peg_bps, peg_window_max_bps)
+        }
+        None => (spot_peg_bps, spot_peg_bps),
+    };
+
+    // Two-asset vaults: also drive on the debt leg's peg and the cross rate
+    // (fresh legs only — staleness is handled by the stale path)
+    let (peg_bps, peg_window_max_bps) = match &debt_data {
+        Some(debt) if !debt.is_stale && !oracle_data.is_stale => {
+            let cross_rate_bps = calculate_cross_rate_bps(
+                oracle_data.price,
+                debt.price,
+                vault.peg_use_conf_edge.then_some((oracle_data.conf, debt.conf)),
+            );
+            msg!(
+                "Debt leg peg {}bps, cross rate {}bps",
+                debt.peg_deviation_bps,
+                cross_rate_bps
+            );
+            (
+                two_asset_peg_bps(peg_bps, debt.peg_deviation_bps, cross_rate_bps),
+                two_asset_peg_bps(peg_window_max_bps, debt.peg_deviation_bps, cross_rate_bps),
+            )
+        }
+        _ => (peg_bps, peg_window_max_bps),
+    };
+
     // --- Health factor (v1: placeholder 3.12×; v2: read from Kamino obligation) ---
     let health_factor_bps = crate::lending::kamino::get_health_factor(
         &ctx.accounts.pyth_feed, // placeholder account — unused in v1
@@ -45,9 +238,11 @@ pub fn handler(ctx: Context<Rebalance>) -> Result<()> {
 
     // --- State determination ---
     let state = determine_vault_state(
-        oracle_data.peg_deviation_bps,
-        oracle_data.is_stale,
-        oracle_data.has_conflict,
+        peg_bps,
+        peg_window_max_bps,
+        oracle_stale_for,
+        oracle_data.quorum_lost,
+        oracle_unreliable,
         health_factor_bps,
         vault,
     );
@@ -59,18 +254,77 @@ pub fn handler(ctx: Context<Rebalance>) -> Result<()> {
                 clock.unix_timestamp >= vault.reexpansion_unlocked_at,
                 ErrorCode::ReexpansionCooldown
             );
-            // V2: invoke Kamino CPI to increase leverage toward max_leverage_bps.
-            msg!("LOOP: Conditions met for leverage expansion (v2: Kamino CPI pending)");
+            match &venue {
+                Some(venue) => {
+                    if let Some(plan) = plan_leverage(
+                        vault.total_supplied,
+                        vault.total_borrowed,
+                        idle,
+                        vault.max_leverage_bps,
+                        vault.flash_fee_bps,
+                    )? {
+                        execute_leverage(venue, &plan)?;
+                        vault.total_supplied = plan.supplied_after;
+                        vault.total_borrowed = plan.borrowed_after;
+                        msg!("LOOP: Flash-levered {} USDC (fee {})", plan.flash_amount, plan.flash_fee);
+                    } else {
+                        msg!("LOOP: Already at target leverage");
+                    }
+                }
+                None => msg!("LOOP: Conditions met for leverage expansion (no lending venue set)"),
+            }
         }
         VaultState::Contract => {
             vault.reexpansion_unlocked_at =
                 clock.unix_timestamp + vault.reexpansion_delay_sec;
-            // V2: invoke Kamino CPI to reduce leverage.
-            msg!("CONTRACT: Peg deviation detected — marking re-expansion delay");
+            if let Some(venue) = &venue {
+                // Stale oracle: gradual unwind while waiting for a refresh (PRD §7.3)
+                let reduction_pct = if oracle_stale_for.is_some() {
+                    STALE_CONTRACT_REDUCTION_PCT
+                } else {
+                    CONTRACT_REDUCTION_PCT
+                };
+                let repay = calculate_repay_amount(vault.total_borrowed, reduction_pct);
+                if let Some(plan) =
+                    plan_unwind(vault.total_supplied, vault.total_borrowed, repay, vault.flash_fee_bps)?
+                {
+                    execute_unwind(venue, &plan)?;
+                    vault.total_supplied = plan.supplied_after;
+                    vault.total_borrowed = plan.borrowed_after;
+                }
+            }
+            if let Some(slots) = oracle_stale_for {
+                msg!(
+                    "CONTRACT: Oracle stale for {} slots — last good price {} at slot {}",
+                    slots,
+                    vault.last_good_price,
+                    vault.last_good_slot
+                );
+            } else if oracle_unreliable {
+                msg!("CONTRACT: Oracle confidence too wide — marking re-expansion delay");
+            } else {
+                msg!("CONTRACT: Peg deviation detected — marking re-expansion delay");
+            }
         }
         VaultState::Exit => {
-            // V2: invoke Kamino CPI to unwind all leverage.
-            msg!("EXIT: Emergency unwind triggered (v2: Kamino CPI pending)");
+            if let Some(venue) = &venue {
+                if let Some(plan) = plan_unwind(
+                    vault.total_supplied,
+                    vault.total_borrowed,
+                    vault.total_borrowed,
+                    vault.flash_fee_bps,
+                )? {
+                    execute_unwind(venue, &plan)?;
+                    vault.total_supplied = plan.supplied_after;
+                    vault.total_borrowed = plan.borrowed_after;
+                }
+            }
+            match oracle_stale_for {
+                Some(slots) if slots > vault.stale_grace_slots => {
+                    msg!("EXIT: Oracle stale for {} slots, past the grace period — unwinding", slots)
+                }
+                _ => msg!("EXIT: Emergency unwind triggered"),
+            }
         }
         VaultState::Panic => {
             return Err(ErrorCode::VaultInPanicState.into());
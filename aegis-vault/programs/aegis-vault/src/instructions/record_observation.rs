@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::{Observation, PegObservations, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RecordObservation<'info> {
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"peg-observations", vault.key().as_ref()],
+        bump = peg_observations.bump
+    )]
+    pub peg_observations: Account<'info, PegObservations>,
+
+    /// CHECK: Pyth USDC/USD feed, pinned at initialization.
+    #[account(address = vault.pyth_usdc_feed)]
+    pub pyth_feed: AccountInfo<'info>,
+}
+
+/// Permissionless crank: append the current Pyth price to the peg TWAP buffer.
+pub fn handler(ctx: Context<RecordObservation>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let observations = &mut ctx.accounts.peg_observations;
+    let clock = Clock::get()?;
+
+    require!(
+        observations.is_due(clock.slot, vault.observation_interval_slots),
+        ErrorCode::ObservationTooSoon
+    );
+
+    let oracle_data = crate::oracles::fetch_oracle_data(
+        &ctx.accounts.pyth_feed,
+        vault.staleness(),
+        false,
+    )?;
+    require!(!oracle_data.is_stale, ErrorCode::OraclePriceStale);
+
+    observations.push(Observation {
+        slot: clock.slot,
+        price: oracle_data.price,
+        conf: oracle_data.conf,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::{OracleRegistry, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RemoveOracleFeed<'info> {
+    #[account(has_one = authority)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"oracle-registry", vault.key().as_ref()],
+        bump = oracle_registry.bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Unregister a feed. Once the registry is active it must keep at least
+/// `min_quorum` feeds, or every rebalance would lose quorum.
+pub fn handler(ctx: Context<RemoveOracleFeed>, feed: Pubkey) -> Result<()> {
+    let registry = &mut ctx.accounts.oracle_registry;
+    require!(
+        !ctx.accounts.vault.has_oracle_registry() || registry.len > registry.min_quorum,
+        ErrorCode::InvalidQuorum
+    );
+    require!(registry.remove(&feed), ErrorCode::InvalidOracleFeed);
+
+    msg!("Oracle feed removed — {}, {} feeds remain", feed, registry.len);
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::Vault;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetDebtAsset<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+
+    pub authority: Signer<'info>,
+
+    #[account(constraint = debt_mint.key() != vault.usdc_mint @ ErrorCode::DebtAssetMismatch)]
+    pub debt_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Pyth price feed for `debt_mint`; validated by the oracle module at rebalance time.
+    pub debt_feed: AccountInfo<'info>,
+}
+
+/// Configure a borrowed asset distinct from the USDC collateral (e.g. a
+/// USDC-supplied, USDT-borrowed loop). `rebalance` then also watches the debt
+/// leg's peg and the collateral/debt cross rate.
+pub fn handler(ctx: Context<SetDebtAsset>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.debt_mint = ctx.accounts.debt_mint.key();
+    vault.debt_feed = ctx.accounts.debt_feed.key();
+
+    msg!(
+        "Debt asset set — mint: {}, feed: {}",
+        vault.debt_mint,
+        vault.debt_feed
+    );
+
+    Ok(())
+}
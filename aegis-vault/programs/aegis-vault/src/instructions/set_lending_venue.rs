@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::errors::ErrorCode;
+use crate::lending::reserve_flash_fee_bps;
+
+#[derive(Accounts)]
+pub struct SetLendingVenue<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Lending venue program; must be executable.
+    #[account(executable)]
+    pub lending_program: AccountInfo<'info>,
+
+    /// CHECK: Venue reserve for the vault's USDC; owned by the venue program.
+    #[account(owner = lending_program.key())]
+    pub lending_reserve: AccountInfo<'info>,
+
+    /// CHECK: Vault's obligation at the venue, pre-created with the vault PDA as owner.
+    #[account(owner = lending_program.key())]
+    pub lending_obligation: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<SetLendingVenue>, flash_fee_bps: u16) -> Result<()> {
+    require!(flash_fee_bps <= 100, ErrorCode::InvalidFlashFee); // Max 1%
+    // Plans are costed with this fee; a lower one than the reserve charges
+    // would leave every flash repayment short
+    require!(
+        flash_fee_bps == reserve_flash_fee_bps(&ctx.accounts.lending_reserve)?,
+        ErrorCode::FlashFeeMismatch
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    vault.lending_program = ctx.accounts.lending_program.key();
+    vault.lending_reserve = ctx.accounts.lending_reserve.key();
+    vault.lending_obligation = ctx.accounts.lending_obligation.key();
+    vault.flash_fee_bps = flash_fee_bps;
+
+    msg!(
+        "Lending venue set — program: {}, flash_fee: {}bps",
+        vault.lending_program,
+        flash_fee_bps
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::Vault;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetOracleConfig<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetOracleConfig>,
+    max_conf_bps: u16,
+    peg_use_conf_edge: bool,
+    oracle_stale_slots: u64,
+    oracle_max_age_sec: i64,
+    stale_grace_slots: u64,
+) -> Result<()> {
+    require!(max_conf_bps > 0 && max_conf_bps <= 100, ErrorCode::InvalidConfidence); // Max 1%
+    require!(
+        oracle_stale_slots > 0 && oracle_stale_slots <= 1_500, // Max ~10 minutes
+        ErrorCode::InvalidStaleness
+    );
+    require!(
+        oracle_max_age_sec > 0 && oracle_max_age_sec <= 600,
+        ErrorCode::InvalidStaleness
+    );
+    require!(
+        stale_grace_slots > 0 && stale_grace_slots <= 9_000, // Max ~1 hour
+        ErrorCode::InvalidStaleness
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    vault.max_conf_bps = max_conf_bps;
+    vault.peg_use_conf_edge = peg_use_conf_edge;
+    vault.oracle_stale_slots = oracle_stale_slots;
+    vault.oracle_max_age_sec = oracle_max_age_sec;
+    vault.stale_grace_slots = stale_grace_slots;
+
+    msg!(
+        "Oracle config set — max_conf: {}bps, peg_use_conf_edge: {}, max_age: {} slots / {}s, stale_grace: {} slots",
+        max_conf_bps,
+        peg_use_conf_edge,
+        oracle_stale_slots,
+        oracle_max_age_sec,
+        stale_grace_slots
+    );
+
+    Ok(())
+}
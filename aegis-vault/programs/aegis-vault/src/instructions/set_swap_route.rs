@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_interface::Mint;
+use crate::state::Vault;
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetSwapRoute<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: DEX program exposing the Whirlpool swap interface; must be executable.
+    #[account(executable)]
+    pub dex_program: AccountInfo<'info>,
+
+    /// Must be an SPL Token mint, the only kind `swap_to_usdc` can route
+    #[account(
+        constraint = input_mint.key() != vault.usdc_mint @ ErrorCode::SwapRouteMismatch,
+        owner = spl_token::ID @ ErrorCode::UnsupportedTokenProgram
+    )]
+    pub input_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Pyth price feed for `input_mint`; validated by the oracle module at swap time.
+    pub input_feed: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<SetSwapRoute>, max_slippage_bps: u16) -> Result<()> {
+    require!(max_slippage_bps <= 500, ErrorCode::InvalidSlippage); // Max 5%
+
+    let vault = &mut ctx.accounts.vault;
+    vault.dex_program = ctx.accounts.dex_program.key();
+    vault.swap_input_mint = ctx.accounts.input_mint.key();
+    vault.swap_input_feed = ctx.accounts.input_feed.key();
+    vault.max_slippage_bps = max_slippage_bps;
+
+    msg!(
+        "Swap route set — dex: {}, input mint: {}, max_slippage: {}bps",
+        vault.dex_program,
+        vault.swap_input_mint,
+        max_slippage_bps
+    );
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::Vault;
+use crate::dex::{guarded_swap, SwapBound, WhirlpoolSwap};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SwapToUsdc<'info> {
+    pub vault: Account<'info, Vault>,
+
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = vault.swap_input_mint,
+        token::authority = vault
+    )]
+    pub vault_source: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = vault.swap_input_mint @ ErrorCode::SwapRouteMismatch,
+        owner = spl_token::ID @ ErrorCode::UnsupportedTokenProgram
+    )]
+    pub source_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vault-usdc"],
+        bump
+    )]
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
+
+    /// A Token-2022 USDC mint is refused: Whirlpool `swap` moves SPL Token only
+    #[account(
+        address = vault.usdc_mint,
+        owner = spl_token::ID @ ErrorCode::UnsupportedTokenProgram
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Pyth feed for the input token, pinned by `set_swap_route`.
+    #[account(address = vault.swap_input_feed @ ErrorCode::SwapRouteMismatch)]
+    pub source_feed: AccountInfo<'info>,
+
+    /// CHECK: Pyth USDC/USD feed, pinned at initialization.
+    #[account(address = vault.pyth_usdc_feed)]
+    pub pyth_feed: AccountInfo<'info>,
+
+    /// CHECK: DEX program, pinned by `set_swap_route`.
+    #[account(address = vault.dex_program @ ErrorCode::SwapRouteMismatch)]
+    pub dex_program: AccountInfo<'info>,
+
+    /// CHECK: Whirlpool and its accounts are validated by the DEX program.
+    #[account(mut)]
+    pub whirlpool: AccountInfo<'info>,
+
+    /// CHECK: Validated by the DEX program.
+    #[account(mut)]
+    pub token_vault_a: AccountInfo<'info>,
+
+    /// CHECK: Validated by the DEX program.
+    #[account(mut)]
+    pub token_vault_b: AccountInfo<'info>,
+
+    /// CHECK: Validated by the DEX program.
+    #[account(mut)]
+    pub tick_array_0: AccountInfo<'info>,
+
+    /// CHECK: Validated by the DEX program.
+    #[account(mut)]
+    pub tick_array_1: AccountInfo<'info>,
+
+    /// CHECK: Validated by the DEX program.
+    #[account(mut)]
+    pub tick_array_2: AccountInfo<'info>,
+
+    /// CHECK: Validated by the DEX program.
+    pub whirlpool_oracle: AccountInfo<'info>,
+
+    #[account(address = spl_token::ID @ ErrorCode::UnsupportedTokenProgram)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless: converts vault-held input tokens (rewards or a second
+/// stable) into USDC, bounded by the oracle-implied output via `guarded_swap`.
+pub fn handler(ctx: Context<SwapToUsdc>, amount_in: u64, a_to_b: bool) -> Result<()> {
+    require!(
+        ctx.accounts.vault.swap_input_mint != Pubkey::default(),
+        ErrorCode::SwapRouteMismatch
+    );
+
+    // Pre-swap oracle prices bound the realized execution price
+    let oracle_in = crate::oracles::fetch_oracle_data(
+        &ctx.accounts.source_feed,
+        ctx.accounts.vault.staleness(),
+        false,
+    )?;
+    let oracle_out = crate::oracles::fetch_oracle_data(
+        &ctx.accounts.pyth_feed,
+        ctx.accounts.vault.staleness(),
+        false,
+    )?;
+    require!(
+        !oracle_in.is_stale && !oracle_out.is_stale,
+        ErrorCode::OraclePriceStale
+    );
+    let bound = SwapBound {
+        price_in: oracle_in.price,
+        price_out: oracle_out.price,
+        decimals_in: ctx.accounts.source_mint.decimals,
+        decimals_out: ctx.accounts.usdc_mint.decimals,
+        max_slippage_bps: ctx.accounts.vault.max_slippage_bps,
+    };
+
+    let vault_bump = [ctx.accounts.vault.bump];
+    let vault_seeds: &[&[u8]] = &[b"vault".as_ref(), &vault_bump];
+    let adapter = WhirlpoolSwap {
+        program: ctx.accounts.dex_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        authority: ctx.accounts.vault.to_account_info(),
+        whirlpool: ctx.accounts.whirlpool.to_account_info(),
+        source: ctx.accounts.vault_source.to_account_info(),
+        destination: ctx.accounts.vault_usdc.to_account_info(),
+        token_vault_a: ctx.accounts.token_vault_a.to_account_info(),
+        token_vault_b: ctx.accounts.token_vault_b.to_account_info(),
+        tick_arrays: [
+            ctx.accounts.tick_array_0.to_account_info(),
+            ctx.accounts.tick_array_1.to_account_info(),
+            ctx.accounts.tick_array_2.to_account_info(),
+        ],
+        oracle: ctx.accounts.whirlpool_oracle.to_account_info(),
+        a_to_b,
+        signer_seeds: &[vault_seeds],
+    };
+    let received = guarded_swap(
+        &adapter,
+        &mut ctx.accounts.vault_source,
+        &mut ctx.accounts.vault_usdc,
+        amount_in,
+        &bound,
+    )?;
+
+    msg!("Swapped {} input tokens for {} USDC", amount_in, received);
+
+    Ok(())
+}
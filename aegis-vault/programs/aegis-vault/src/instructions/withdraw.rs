@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{
+    self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
 use crate::state::{Vault, UserPosition};
 use crate::errors::ErrorCode;
 
@@ -8,95 +10,167 @@ pub struct Withdraw<'info> {
     #[account(mut)]
     pub vault: Account<'info, Vault>,
     
+    /// The signer's position, if they have one; holders of transferred
+    /// shares withdraw without it
     #[account(
         mut,
         seeds = [b"user-position", user.key().as_ref()],
         bump = user_position.bump
     )]
-    pub user_position: Account<'info, UserPosition>,
+    pub user_position: Option<Account<'info, UserPosition>>,
     
-    #[account(mut)]
+    /// Owner of `user_shares`: a wallet, or a program's PDA signing by CPI
     pub user: Signer<'info>,
     
+    #[account(
+        address = vault.usdc_mint,
+        mint::token_program = token_program
+    )]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Any token account of the vault's USDC mint: the signer's own, an
+    /// exchange deposit address or a program-owned account
     #[account(
         mut,
-        constraint = user_usdc.mint == vault.usdc_mint,
-        constraint = user_usdc.owner == user.key()
+        constraint = recipient_usdc.mint == vault.usdc_mint
     )]
-    pub user_usdc: Account<'info, TokenAccount>,
+    pub recipient_usdc: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         seeds = [b"vault-usdc"],
         bump
     )]
-    pub vault_usdc: Account<'info, TokenAccount>,
+    pub vault_usdc: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         seeds = [b"share-mint"],
         bump
     )]
-    pub share_mint: Account<'info, Mint>,
+    pub share_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         constraint = user_shares.mint == share_mint.key(),
         constraint = user_shares.owner == user.key()
     )]
-    pub user_shares: Account<'info, TokenAccount>,
+    pub user_shares: InterfaceAccount<'info, TokenAccount>,
     
-    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022, whichever owns the USDC mint
+    pub token_program: Interface<'info, TokenInterface>,
+    /// SPL Token or Token-2022, whichever owns the share mint
+    #[account(address = vault.share_token_program)]
+    pub share_token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
-    let user_position = &mut ctx.accounts.user_position;
-    
-    require!(user_position.shares >= shares, ErrorCode::InsufficientEquity);
-    
-    // Calculate USDC to return
-    let equity = vault.equity();
-    let usdc_amount = (shares as u128)
-        .checked_mul(equity as u128)
-        .and_then(|v| v.checked_div(vault.total_shares as u128))
-        .ok_or(ErrorCode::MathOverflow)? as u64;
+    let flow = WithdrawFlow::new(ctx.accounts);
     
-    require!(ctx.accounts.vault_usdc.amount >= usdc_amount, ErrorCode::InsufficientEquity);
+    // Calculate USDC to return, rounded down: the remainder stays with the remaining holders
+    let total_assets = flow.total_assets()?;
+    let usdc_amount = aegis_core::assets_for_shares(shares, flow.vault.total_shares, total_assets)
+        .ok_or(ErrorCode::MathOverflow)?;
     
-    // Burn shares
-    token::burn(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Burn {
-                mint: ctx.accounts.share_mint.to_account_info(),
-                from: ctx.accounts.user_shares.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        shares,
-    )?;
-    
-    // Transfer USDC to user
-    let vault_seeds = &[b"vault".as_ref(), &[vault.bump]];
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault_usdc.to_account_info(),
-                to: ctx.accounts.user_usdc.to_account_info(),
-                authority: vault.to_account_info(),
-            },
-            &[vault_seeds],
-        ),
-        usdc_amount,
-    )?;
-    
-    // Update state
-    user_position.shares -= shares;
-    vault.total_shares -= shares;
+    flow.execute(shares, usdc_amount, total_assets)
+}
+
+/// Accounts one withdrawal touches, whichever instruction supplied them.
+/// The caller's constraints have already tied each of them to the vault.
+pub(crate) struct WithdrawFlow<'a, 'info> {
+    pub vault: &'a mut Account<'info, Vault>,
+    /// The share owner's position, if they have one
+    pub user_position: Option<&'a mut Account<'info, UserPosition>>,
+    /// Owner of `owner_shares`
+    pub authority: AccountInfo<'info>,
+    pub owner_shares: &'a InterfaceAccount<'info, TokenAccount>,
+    pub recipient_usdc: AccountInfo<'info>,
+    pub usdc_mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault_usdc: &'a InterfaceAccount<'info, TokenAccount>,
+    pub share_mint: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub share_token_program: AccountInfo<'info>,
+}
+
+impl<'a, 'info> WithdrawFlow<'a, 'info> {
+    pub fn new(accounts: &'a mut Withdraw<'info>) -> Self {
+        Self {
+            vault: &mut accounts.vault,
+            user_position: accounts.user_position.as_mut(),
+            authority: accounts.user.to_account_info(),
+            owner_shares: &accounts.user_shares,
+            recipient_usdc: accounts.recipient_usdc.to_account_info(),
+            usdc_mint: &accounts.usdc_mint,
+            vault_usdc: &accounts.vault_usdc,
+            share_mint: accounts.share_mint.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+            share_token_program: accounts.share_token_program.to_account_info(),
+        }
+    }
     
-    msg!("Withdrew {} shares for {} USDC", shares, usdc_amount);
+    /// NAV backing all shares before this withdrawal
+    pub fn total_assets(&self) -> Result<u64> {
+        self.vault
+            .total_assets(self.vault_usdc.amount)
+            .ok_or_else(|| ErrorCode::MathOverflow.into())
+    }
     
-    Ok(())
+    /// Burn `shares` and pay `usdc_amount` out of idle USDC; `total_assets`
+    /// is the pre-withdrawal NAV the amounts were priced at
+    pub fn execute(self, shares: u64, usdc_amount: u64, total_assets: u64) -> Result<()> {
+        let idle = self.vault_usdc.amount;
+        let share_balance = self.owner_shares.amount;
+        let vault = self.vault;
+        
+        // The token balance is authoritative: shares may have been transferred in or out
+        require!(share_balance >= shares, ErrorCode::InsufficientEquity);
+        require!(idle >= usdc_amount, ErrorCode::InsufficientEquity);
+        
+        // Burn shares
+        token_interface::burn(
+            CpiContext::new(
+                self.share_token_program,
+                Burn {
+                    mint: self.share_mint,
+                    from: self.owner_shares.to_account_info(),
+                    authority: self.authority,
+                },
+            ),
+            shares,
+        )?;
+        
+        // Transfer USDC to the recipient; any Token-2022 transfer fee comes out of what they receive
+        let vault_seeds = &[b"vault".as_ref(), &[vault.bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program,
+                TransferChecked {
+                    from: self.vault_usdc.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.recipient_usdc,
+                    authority: vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            usdc_amount,
+            self.usdc_mint.decimals,
+        )?;
+        
+        // Update state
+        if let Some(user_position) = self.user_position {
+            // Resync with the share balance, then realize the burned shares' average cost
+            let mut basis = user_position.cost_basis();
+            basis.sync(share_balance, vault.total_shares, total_assets)
+                .and_then(|_| basis.withdraw(shares, usdc_amount))
+                .ok_or(ErrorCode::MathOverflow)?;
+            user_position.set_cost_basis(basis);
+        }
+        vault.total_shares = vault.total_shares
+            .checked_sub(shares)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        msg!("Withdrew {} shares for {} USDC", shares, usdc_amount);
+        
+        Ok(())
+    }
 }
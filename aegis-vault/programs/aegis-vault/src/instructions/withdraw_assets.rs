@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use super::withdraw::{Withdraw, WithdrawFlow};
+
+/// Same accounts as `withdraw`, but sized by the USDC to pay out: burns the
+/// shares worth exactly `usdc_amount`, rounded up, and fails if that is more
+/// than `max_shares_in`.
+pub fn handler(ctx: Context<Withdraw>, usdc_amount: u64, max_shares_in: u64) -> Result<()> {
+    let flow = WithdrawFlow::new(ctx.accounts);
+
+    // Calculate shares to burn, rounded up: the remainder stays with the remaining holders
+    let total_assets = flow.total_assets()?;
+    let shares = aegis_core::shares_for_withdrawal(usdc_amount, flow.vault.total_shares, total_assets)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(shares <= max_shares_in, ErrorCode::MaxSharesExceeded);
+
+    flow.execute(shares, usdc_amount, total_assets)
+}
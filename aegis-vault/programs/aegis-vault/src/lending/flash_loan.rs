@@ -0,0 +1,156 @@
+// Flash-loan leverage engine.
+// Reaches any target leverage in a constant four venue CPIs instead of
+// iterating supply → borrow → supply:
+//   lever up:  flash borrow → supply full position → borrow → flash repay
+//   unwind:    flash borrow → repay debt → withdraw collateral → flash repay
+use anchor_lang::prelude::*;
+use aegis_core::{mul_div_ceil, mul_div_floor};
+use crate::errors::ErrorCode;
+use super::kamino::calculate_target_borrow;
+use super::venue::LendingVenue;
+
+/// Share of idle vault cash kept out of the venue for withdrawals (PRD §4.1 TARGET_BUFFER).
+pub const IDLE_BUFFER_BPS: u16 = 3_000;
+
+/// Share of debt repaid by each CONTRACT rebalance on a peg or confidence trigger.
+pub const CONTRACT_REDUCTION_PCT: u8 = 50;
+
+/// Share of debt repaid by each CONTRACT rebalance while the oracle is stale
+/// (PRD §7.3): smaller steps, since the true price is unknown.
+pub const STALE_CONTRACT_REDUCTION_PCT: u8 = 25;
+
+/// Amounts for a single-transaction leverage increase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeveragePlan {
+    pub flash_amount: u64,
+    pub flash_fee: u64,
+    pub supply_amount: u64,
+    /// Flash amount plus fee: borrowed from the venue, then flash-repaid
+    pub borrow_amount: u64,
+    pub supplied_after: u64,
+    pub borrowed_after: u64,
+}
+
+/// Amounts for a single-transaction leverage reduction or full unwind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnwindPlan {
+    pub flash_amount: u64,
+    pub flash_fee: u64,
+    /// Flash amount plus fee, owed back at the end of the unwind
+    pub repay_amount: u64,
+    pub withdraw_amount: u64,
+    pub supplied_after: u64,
+    pub borrowed_after: u64,
+}
+
+/// Flash-loan fee, rounded up so the venue is never short-paid.
+pub fn flash_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    mul_div_ceil(amount, fee_bps as u64, 10_000).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+/// Plan a leverage increase to `target_leverage_bps`, deploying `idle` vault
+/// cash (less the idle buffer) as fresh equity. Returns `None` when the
+/// position is already at or above target.
+pub fn plan_leverage(
+    total_supplied: u64,
+    total_borrowed: u64,
+    idle: u64,
+    target_leverage_bps: u16,
+    flash_fee_bps: u16,
+) -> Result<Option<LeveragePlan>> {
+    require!(target_leverage_bps >= 10_000, ErrorCode::InvalidLeverage);
+
+    // Round down: the idle buffer keeps any remainder
+    let deployable = mul_div_floor(idle, (10_000 - IDLE_BUFFER_BPS) as u64, 10_000)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let equity = total_supplied
+        .checked_sub(total_borrowed)
+        .and_then(|e| e.checked_add(deployable))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Round down: never lever past the target
+    let target_supplied = mul_div_floor(equity, target_leverage_bps as u64, 10_000)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let target_borrowed = calculate_target_borrow(target_supplied, target_leverage_bps);
+
+    let flash_amount = target_borrowed.saturating_sub(total_borrowed);
+    if flash_amount == 0 {
+        return Ok(None);
+    }
+
+    let fee = flash_fee(flash_amount, flash_fee_bps)?;
+    let supply_amount = deployable
+        .checked_add(flash_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let borrow_amount = flash_amount
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(Some(LeveragePlan {
+        flash_amount,
+        flash_fee: fee,
+        supply_amount,
+        borrow_amount,
+        supplied_after: total_supplied
+            .checked_add(supply_amount)
+            .ok_or(ErrorCode::MathOverflow)?,
+        borrowed_after: total_borrowed
+            .checked_add(borrow_amount)
+            .ok_or(ErrorCode::MathOverflow)?,
+    }))
+}
+
+/// Plan repaying `repay_amount` of debt. Repaying the full debt withdraws
+/// all collateral; a partial repay withdraws just enough to settle the flash loan.
+pub fn plan_unwind(
+    total_supplied: u64,
+    total_borrowed: u64,
+    repay_amount: u64,
+    flash_fee_bps: u16,
+) -> Result<Option<UnwindPlan>> {
+    let flash_amount = repay_amount.min(total_borrowed);
+    if flash_amount == 0 {
+        return Ok(None);
+    }
+
+    let fee = flash_fee(flash_amount, flash_fee_bps)?;
+    let repay_amount = flash_amount
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let withdraw_amount = if flash_amount == total_borrowed {
+        total_supplied
+    } else {
+        repay_amount
+    };
+    require!(
+        withdraw_amount >= repay_amount,
+        ErrorCode::InsufficientEquity
+    );
+
+    Ok(Some(UnwindPlan {
+        flash_amount,
+        flash_fee: fee,
+        repay_amount,
+        withdraw_amount,
+        supplied_after: total_supplied
+            .checked_sub(withdraw_amount)
+            .ok_or(ErrorCode::InsufficientEquity)?,
+        borrowed_after: total_borrowed - flash_amount,
+    }))
+}
+
+/// Lever up in four CPIs: flash borrow → supply → borrow → flash repay.
+pub fn execute_leverage<V: LendingVenue>(venue: &V, plan: &LeveragePlan) -> Result<()> {
+    venue.flash_borrow(plan.flash_amount)?;
+    venue.supply(plan.supply_amount)?;
+    venue.borrow(plan.borrow_amount)?;
+    venue.flash_repay(plan.borrow_amount)
+}
+
+/// Unwind in four CPIs: flash borrow → repay → withdraw → flash repay.
+pub fn execute_unwind<V: LendingVenue>(venue: &V, plan: &UnwindPlan) -> Result<()> {
+    venue.flash_borrow(plan.flash_amount)?;
+    venue.repay(plan.flash_amount)?;
+    venue.withdraw(plan.withdraw_amount)?;
+    venue.flash_repay(plan.repay_amount)
+}
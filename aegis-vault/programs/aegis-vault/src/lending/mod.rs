@@ -1,4 +1,8 @@
 // Lending protocol integration module
 pub mod kamino;
+pub mod venue;
+pub mod flash_loan;
 
 pub use kamino::*;
+pub use venue::*;
+pub use flash_loan::*;
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::errors::ErrorCode;
+
+// Anchor instruction discriminators (sha256("global:<name>")[..8]) of the
+// venue interface implemented by `programs/mock-lending`.
+const FLASH_BORROW_IX: [u8; 8] = [166, 221, 220, 25, 61, 73, 127, 240];
+const FLASH_REPAY_IX: [u8; 8] = [182, 143, 19, 23, 39, 221, 184, 78];
+const SUPPLY_IX: [u8; 8] = [81, 67, 116, 61, 250, 209, 5, 198];
+const BORROW_IX: [u8; 8] = [228, 253, 131, 202, 207, 116, 89, 18];
+const REPAY_IX: [u8; 8] = [234, 103, 67, 82, 208, 234, 219, 166];
+const WITHDRAW_IX: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+
+// Reserve account layout of the same interface:
+// discriminator (sha256("account:Reserve")[..8]), mint, liquidity, ltv_bps, flash_fee_bps
+const RESERVE_DISCRIMINATOR: [u8; 8] = [43, 242, 204, 202, 26, 247, 59, 127];
+const RESERVE_FLASH_FEE_OFFSET: usize = 8 + 32 + 32 + 2;
+
+/// Flash-loan fee the venue charges on `reserve`, in basis points.
+pub fn reserve_flash_fee_bps(reserve: &AccountInfo) -> Result<u16> {
+    let data = reserve.try_borrow_data()?;
+    require!(
+        data.len() >= RESERVE_FLASH_FEE_OFFSET + 2 && data[..8] == RESERVE_DISCRIMINATOR,
+        ErrorCode::LendingVenueMismatch
+    );
+    Ok(u16::from_le_bytes([
+        data[RESERVE_FLASH_FEE_OFFSET],
+        data[RESERVE_FLASH_FEE_OFFSET + 1],
+    ]))
+}
+
+/// A lending venue able to issue flash loans against the vault's reserve.
+/// Every method moves tokens between the venue and the vault's USDC account.
+pub trait LendingVenue {
+    fn flash_borrow(&self, amount: u64) -> Result<()>;
+    fn flash_repay(&self, amount: u64) -> Result<()>;
+    fn supply(&self, amount: u64) -> Result<()>;
+    fn borrow(&self, amount: u64) -> Result<()>;
+    fn repay(&self, amount: u64) -> Result<()>;
+    fn withdraw(&self, amount: u64) -> Result<()>;
+}
+
+/// CPI adapter for venues exposing the mock-lending instruction interface.
+/// All operations share one account list:
+/// `[reserve, obligation, liquidity, vault_usdc, vault (signer), token_program]`.
+pub struct CpiLendingVenue<'a, 'info> {
+    pub program: AccountInfo<'info>,
+    pub reserve: AccountInfo<'info>,
+    pub obligation: AccountInfo<'info>,
+    pub liquidity: AccountInfo<'info>,
+    pub vault_usdc: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+impl<'a, 'info> CpiLendingVenue<'a, 'info> {
+    fn invoke(&self, discriminator: [u8; 8], amount: u64) -> Result<()> {
+        let mut data = Vec::with_capacity(16);
+        data.extend_from_slice(&discriminator);
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let ix = Instruction {
+            program_id: self.program.key(),
+            accounts: vec![
+                AccountMeta::new(self.reserve.key(), false),
+                AccountMeta::new(self.obligation.key(), false),
+                AccountMeta::new(self.liquidity.key(), false),
+                AccountMeta::new(self.vault_usdc.key(), false),
+                AccountMeta::new_readonly(self.vault.key(), true),
+                AccountMeta::new_readonly(self.token_program.key(), false),
+            ],
+            data,
+        };
+
+        invoke_signed(
+            &ix,
+            &[
+                self.reserve.clone(),
+                self.obligation.clone(),
+                self.liquidity.clone(),
+                self.vault_usdc.clone(),
+                self.vault.clone(),
+                self.token_program.clone(),
+                self.program.clone(),
+            ],
+            self.signer_seeds,
+        )
+        .map_err(Into::into)
+    }
+}
+
+impl<'a, 'info> LendingVenue for CpiLendingVenue<'a, 'info> {
+    fn flash_borrow(&self, amount: u64) -> Result<()> {
+        self.invoke(FLASH_BORROW_IX, amount)
+    }
+
+    fn flash_repay(&self, amount: u64) -> Result<()> {
+        self.invoke(FLASH_REPAY_IX, amount)
+    }
+
+    fn supply(&self, amount: u64) -> Result<()> {
+        self.invoke(SUPPLY_IX, amount)
+    }
+
+    fn borrow(&self, amount: u64) -> Result<()> {
+        self.invoke(BORROW_IX, amount)
+    }
+
+    fn repay(&self, amount: u64) -> Result<()> {
+        self.invoke(REPAY_IX, amount)
+    }
+
+    fn withdraw(&self, amount: u64) -> Result<()> {
+        self.invoke(WITHDRAW_IX, amount)
+    }
+}
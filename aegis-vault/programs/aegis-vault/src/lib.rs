@@ -5,7 +5,9 @@ pub mod logic;
 pub mod instructions;
 pub mod oracles;
 pub mod lending;
+pub mod dex;
 pub mod errors;
+pub mod events;
 
 use instructions::*;
 
@@ -23,15 +25,145 @@ pub mod aegis_vault {
         instructions::initialize_vault::handler(ctx, max_leverage_bps, hf_floor_bps)
     }
 
+    pub fn initialize_vault_2022(
+        ctx: Context<InitializeVault2022>,
+        max_leverage_bps: u16,
+        hf_floor_bps: u16,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::initialize_vault_2022::handler(
+            ctx,
+            max_leverage_bps,
+            hf_floor_bps,
+            name,
+            symbol,
+            uri,
+        )
+    }
+
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        instructions::migrate_vault::handler(ctx)
+    }
+
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         instructions::deposit::handler(ctx, amount)
     }
 
+    pub fn deposit_for(ctx: Context<DepositFor>, amount: u64) -> Result<()> {
+        instructions::deposit_for::handler(ctx, amount)
+    }
+
+    pub fn deposit_cpi(ctx: Context<DepositCpi>, amount: u64) -> Result<()> {
+        instructions::deposit_cpi::handler(ctx, amount)
+    }
+
+    pub fn open_position(ctx: Context<OpenPosition>, owner: Pubkey) -> Result<()> {
+        instructions::open_position::handler(ctx, owner)
+    }
+
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+        instructions::migrate_position::handler(ctx)
+    }
+
+    pub fn init_referrer(ctx: Context<InitReferrer>, referrer: Pubkey) -> Result<()> {
+        instructions::init_referrer::handler(ctx, referrer)
+    }
+
     pub fn withdraw(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
         instructions::withdraw::handler(ctx, shares)
     }
 
+    pub fn withdraw_assets(
+        ctx: Context<Withdraw>,
+        usdc_amount: u64,
+        max_shares_in: u64,
+    ) -> Result<()> {
+        instructions::withdraw_assets::handler(ctx, usdc_amount, max_shares_in)
+    }
+
+    pub fn position_pnl(ctx: Context<PositionPnl>) -> Result<PnlReport> {
+        instructions::position_pnl::handler(ctx)
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>, close_share_account: bool) -> Result<()> {
+        instructions::close_position::handler(ctx, close_share_account)
+    }
+
     pub fn rebalance(ctx: Context<Rebalance>) -> Result<()> {
         instructions::rebalance::handler(ctx)
     }
+
+    pub fn init_observations(ctx: Context<InitObservations>) -> Result<()> {
+        instructions::init_observations::handler(ctx)
+    }
+
+    pub fn record_observation(ctx: Context<RecordObservation>) -> Result<()> {
+        instructions::record_observation::handler(ctx)
+    }
+
+    pub fn init_oracle_registry(
+        ctx: Context<InitOracleRegistry>,
+        min_quorum: u8,
+        max_disagreement_bps: u16,
+    ) -> Result<()> {
+        instructions::init_oracle_registry::handler(ctx, min_quorum, max_disagreement_bps)
+    }
+
+    pub fn add_oracle_feed(
+        ctx: Context<AddOracleFeed>,
+        kind: state::OracleKind,
+        weight: u16,
+        max_age_slots: u64,
+        max_age_sec: i64,
+        max_conf_bps: u16,
+    ) -> Result<()> {
+        instructions::add_oracle_feed::handler(
+            ctx,
+            kind,
+            weight,
+            max_age_slots,
+            max_age_sec,
+            max_conf_bps,
+        )
+    }
+
+    pub fn remove_oracle_feed(ctx: Context<RemoveOracleFeed>, feed: Pubkey) -> Result<()> {
+        instructions::remove_oracle_feed::handler(ctx, feed)
+    }
+
+    pub fn set_debt_asset(ctx: Context<SetDebtAsset>) -> Result<()> {
+        instructions::set_debt_asset::handler(ctx)
+    }
+
+    pub fn set_lending_venue(ctx: Context<SetLendingVenue>, flash_fee_bps: u16) -> Result<()> {
+        instructions::set_lending_venue::handler(ctx, flash_fee_bps)
+    }
+
+    pub fn set_oracle_config(
+        ctx: Context<SetOracleConfig>,
+        max_conf_bps: u16,
+        peg_use_conf_edge: bool,
+        oracle_stale_slots: u64,
+        oracle_max_age_sec: i64,
+        stale_grace_slots: u64,
+    ) -> Result<()> {
+        instructions::set_oracle_config::handler(
+            ctx,
+            max_conf_bps,
+            peg_use_conf_edge,
+            oracle_stale_slots,
+            oracle_max_age_sec,
+            stale_grace_slots,
+        )
+    }
+
+    pub fn set_swap_route(ctx: Context<SetSwapRoute>, max_slippage_bps: u16) -> Result<()> {
+        instructions::set_swap_route::handler(ctx, max_slippage_bps)
+    }
+
+    pub fn swap_to_usdc(ctx: Context<SwapToUsdc>, amount_in: u64, a_to_b: bool) -> Result<()> {
+        instructions::swap_to_usdc::handler(ctx, amount_in, a_to_b)
+    }
 }
@@ -1,3 +1,5 @@
 pub mod state_machine;
+pub mod twap;
 
 pub use state_machine::*;
+pub use twap::*;
@@ -1,50 +1,33 @@
-use anchor_lang::prelude::*;
+// Thin adapter over `aegis_core`: the decision logic itself lives in the
+// dependency-free core crate so keepers and the simulator run the same code.
 use crate::state::Vault;
+use aegis_core::Conditions;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum VaultState {
-    Idle,
-    Loop,
-    Contract,
-    Exit,
-    Panic,
-}
+pub use aegis_core::{
+    calculate_conf_bps, calculate_cross_rate_bps, calculate_peg_bps, two_asset_peg_bps,
+    VaultState,
+};
 
-/// Determine the vault's current state based on conditions
+/// Determine the vault's current state based on conditions.
+/// `peg_bps` drives escalation (normally the TWAP deviation); LOOP additionally
+/// requires the worst deviation over the window, `peg_window_max_bps`, to be healthy.
+/// `oracle_stale_for` is how many slots the oracle has been stale, if it is.
 pub fn determine_vault_state(
     peg_bps: u16,
-    oracle_stale: bool,
-    oracle_conflict: bool,
+    peg_window_max_bps: u16,
+    oracle_stale_for: Option<u64>,
+    oracle_quorum_lost: bool,
+    oracle_unreliable: bool,
     health_factor_bps: u16,
     vault: &Vault,
 ) -> VaultState {
-    // PANIC: Oracle issues or severe depeg
-    if oracle_stale || oracle_conflict || peg_bps > vault.peg_panic_bps {
-        return VaultState::Panic;
-    }
-    
-    // EXIT: Major depeg
-    if peg_bps > vault.peg_exit_bps {
-        return VaultState::Exit;
-    }
-    
-    // CONTRACT: Moderate depeg or low health factor
-    if peg_bps > vault.peg_warn_bps || health_factor_bps < vault.hf_floor_bps {
-        return VaultState::Contract;
-    }
-    
-    // LOOP: Healthy conditions
-    if peg_bps < vault.peg_warn_bps && health_factor_bps >= vault.hf_floor_bps {
-        return VaultState::Loop;
-    }
-    
-    // Default: IDLE
-    VaultState::Idle
-}
-
-/// Calculate peg deviation in basis points
-pub fn calculate_peg_bps(price: i64) -> u16 {
-    let target = 1_000_000i64; // $1.00 in 6 decimals
-    let deviation = (target - price).abs();
-    ((deviation * 10000) / target) as u16
+    let conditions = Conditions {
+        peg_bps,
+        peg_window_max_bps,
+        oracle_stale_for,
+        oracle_quorum_lost,
+        oracle_unreliable,
+        health_factor_bps,
+    };
+    aegis_core::determine_vault_state(&conditions, &vault.thresholds())
 }
@@ -0,0 +1,67 @@
+use crate::state::{Observation, PegObservations};
+use super::calculate_peg_bps;
+
+/// Time-weighted price and confidence over a window (6dp)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Twap {
+    pub price: i64,
+    pub conf: u64,
+}
+
+/// Each sample is in effect from its slot until the next sample (or `now_slot`
+/// for the latest); yields the part of that span inside `[window_start, now_slot]`.
+fn weighted_samples(
+    observations: &PegObservations,
+    now_slot: u64,
+    window_slots: u64,
+) -> impl Iterator<Item = (&Observation, u64)> {
+    let window_start = now_slot.saturating_sub(window_slots);
+    let mut samples = observations.iter().peekable();
+    std::iter::from_fn(move || {
+        let sample = samples.next()?;
+        let end = samples.peek().map_or(now_slot, |next| next.slot).min(now_slot);
+        let start = sample.slot.max(window_start);
+        Some((sample, end.saturating_sub(start)))
+    })
+}
+
+/// Time-weighted average over the last `window_slots`. Falls back to the
+/// latest sample when no time has elapsed inside the window.
+pub fn twap(observations: &PegObservations, now_slot: u64, window_slots: u64) -> Option<Twap> {
+    let latest = *observations.latest()?;
+
+    let (mut price_sum, mut conf_sum, mut weight_sum) = (0i128, 0u128, 0u128);
+    for (sample, weight) in weighted_samples(observations, now_slot, window_slots) {
+        price_sum += sample.price as i128 * weight as i128;
+        conf_sum += sample.conf as u128 * weight as u128;
+        weight_sum += weight as u128;
+    }
+
+    if weight_sum == 0 {
+        return Some(Twap { price: latest.price, conf: latest.conf });
+    }
+    Some(Twap {
+        price: (price_sum / weight_sum as i128) as i64,
+        conf: (conf_sum / weight_sum) as u64,
+    })
+}
+
+/// Worst peg deviation of any sample in effect during the last `window_slots`,
+/// including the latest sample.
+pub fn max_peg_deviation_bps(
+    observations: &PegObservations,
+    now_slot: u64,
+    window_slots: u64,
+    use_conf_edge: bool,
+) -> Option<u16> {
+    let latest = observations.latest()?;
+    let latest_bps = calculate_peg_bps(latest.price, use_conf_edge.then_some(latest.conf));
+
+    let window_max = weighted_samples(observations, now_slot, window_slots)
+        .filter(|(_, weight)| *weight > 0)
+        .map(|(s, _)| calculate_peg_bps(s.price, use_conf_edge.then_some(s.conf)))
+        .max()
+        .unwrap_or(0);
+
+    Some(window_max.max(latest_bps))
+}
@@ -0,0 +1,101 @@
+// Median-of-N aggregation across the feeds of an oracle registry.
+// Stale or low-confidence feeds are dropped, the rest vote on a weighted
+// median, and only feeds close enough to that median count towards quorum —
+// a single outlier is outvoted instead of halting the vault. Falling short
+// of quorum is only a disagreement when the valid feeds are split; feeds
+// that are merely stale or unusable leave the vault on its stale path.
+use crate::logic::calculate_conf_bps;
+
+/// One feed's normalised reading (6dp) and the limits it is held to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedReading {
+    pub price: i64,
+    pub conf: u64,
+    pub weight: u16,
+    pub is_stale: bool,
+    pub max_conf_bps: u16,
+}
+
+impl FeedReading {
+    /// Fresh, positive and within its own confidence limit
+    pub fn is_valid(&self) -> bool {
+        !self.is_stale
+            && self.price > 0
+            && self.weight > 0
+            && calculate_conf_bps(self.price, self.conf) <= self.max_conf_bps
+    }
+}
+
+/// Aggregated view of all valid readings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aggregate {
+    /// Weighted median price (6dp)
+    pub price: i64,
+    /// Confidence of the feed that supplied the median (6dp)
+    pub conf: u64,
+    /// Valid feeds within the disagreement tolerance of the median
+    pub quorum: u8,
+    /// Widest spread between any two valid feeds, in bps of the median
+    pub max_disagreement_bps: u16,
+}
+
+/// Distance between two prices in bps of `reference` (saturating)
+fn spread_bps(a: i64, b: i64, reference: i64) -> u16 {
+    let diff = (a as i128 - b as i128).unsigned_abs();
+    ((diff * 10_000) / reference as u128).min(u16::MAX as u128) as u16
+}
+
+/// Weighted median of the valid readings: the lowest price at which the
+/// cumulative weight reaches half the total. `None` if no reading is valid.
+pub fn weighted_median(readings: &[FeedReading]) -> Option<FeedReading> {
+    let mut valid: Vec<FeedReading> = readings.iter().copied().filter(FeedReading::is_valid).collect();
+    valid.sort_by_key(|r| r.price);
+
+    let total: u32 = valid.iter().map(|r| r.weight as u32).sum();
+    let mut cumulative = 0u32;
+    valid.into_iter().find(|r| {
+        cumulative += r.weight as u32;
+        cumulative * 2 >= total
+    })
+}
+
+/// Aggregate readings into a median, a quorum count and the max pairwise
+/// disagreement. `None` if no reading is valid.
+pub fn aggregate(readings: &[FeedReading], max_disagreement_bps: u16) -> Option<Aggregate> {
+    let median = weighted_median(readings)?;
+    let valid = || readings.iter().filter(|r| r.is_valid());
+
+    let quorum = valid()
+        .filter(|r| spread_bps(r.price, median.price, median.price) <= max_disagreement_bps)
+        .count();
+    let min = valid().map(|r| r.price).min().unwrap_or(median.price);
+    let max = valid().map(|r| r.price).max().unwrap_or(median.price);
+
+    Some(Aggregate {
+        price: median.price,
+        conf: median.conf,
+        quorum: quorum.min(u8::MAX as usize) as u8,
+        max_disagreement_bps: spread_bps(max, min, median.price),
+    })
+}
+
+/// Why an aggregation did or did not reach quorum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuorumStatus {
+    /// At least `min_quorum` valid feeds agree with the median
+    Met,
+    /// Short of quorum with no split among the valid feeds: the rest are
+    /// stale or unusable, so the price is missing rather than contested
+    Stale,
+    /// Short of quorum and the valid feeds disagree beyond tolerance
+    Lost,
+}
+
+/// Classify `agg` (from `aggregate` with the same tolerance) against `min_quorum`
+pub fn quorum_status(agg: Option<&Aggregate>, min_quorum: u8, max_disagreement_bps: u16) -> QuorumStatus {
+    match agg {
+        Some(agg) if agg.quorum >= min_quorum => QuorumStatus::Met,
+        Some(agg) if agg.max_disagreement_bps > max_disagreement_bps => QuorumStatus::Lost,
+        _ => QuorumStatus::Stale,
+    }
+}
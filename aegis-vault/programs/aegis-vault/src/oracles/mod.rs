@@ -1,54 +1,146 @@
-// Oracle integration module — Pyth only for v1.
-// Switchboard dual-feed validation is a v2 feature (requires compatible SDK).
+// Oracle integration module.
+// Without an oracle registry the vault's single Pyth feed drives decisions;
+// with one, every registered feed is read and aggregated into a median-of-N.
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::logic::{calculate_conf_bps, calculate_peg_bps};
+use crate::state::{OracleFeed, OracleKind, OracleRegistry};
 
+pub mod aggregate;
+pub mod normalize;
 pub mod pyth;
+pub mod staleness;
+pub mod switchboard;
 
+pub use aggregate::*;
+pub use normalize::*;
 pub use pyth::*;
+pub use staleness::*;
+pub use switchboard::*;
 
-/// Oracle data fetched from Pyth
+/// Oracle data fetched from a single feed or aggregated across a registry
 #[derive(Debug, Clone)]
 pub struct OracleData {
-    pub pyth_price: i64,
-    /// Always false in v1 (single oracle). Will be re-enabled in v2 with dual-feed.
-    pub has_conflict: bool,
+    /// Price (the weighted median when aggregated)
+    pub price: i64,
+    pub conf: u64,
+    /// Confidence interval as bps of price
+    pub conf_bps: u16,
+    /// Feeds agreeing with the median (1 for a single feed)
+    pub quorum: u8,
+    /// Widest spread between any two valid feeds, in bps of price
+    pub max_disagreement_bps: u16,
+    /// The registry's valid feeds are split, leaving fewer than `min_quorum`
+    /// in agreement — never set for a single feed
+    pub quorum_lost: bool,
     pub is_stale: bool,
     pub peg_deviation_bps: u16,
 }
 
 /// Fetch price from Pyth and calculate peg metrics.
-/// Price is normalised to 6 decimal places ($1.000000 = 1_000_000).
+/// Price and confidence are normalised to 6 decimal places ($1.000000 = 1_000_000).
+/// With `use_conf_edge`, peg deviation is measured at the worst edge of the confidence band.
 pub fn fetch_oracle_data(
     pyth_feed: &AccountInfo,
-    max_stale_slots: u64,
+    staleness: StalenessConfig,
+    use_conf_edge: bool,
 ) -> Result<OracleData> {
-    let clock = Clock::get()?;
-
     let pyth_price = pyth::get_pyth_price(pyth_feed)?;
-    let is_stale = pyth::is_pyth_stale(pyth_feed, max_stale_slots, clock.slot)?;
+    let pyth_conf = pyth::get_pyth_confidence(pyth_feed)?;
+    let is_stale = pyth::is_pyth_stale(pyth_feed, staleness)?;
 
-    // Peg deviation from $1.00 (= 1_000_000 in 6dp)
-    let target_price = 1_000_000i64;
-    let peg_diff = (target_price - pyth_price).unsigned_abs();
-    let peg_deviation_bps = ((peg_diff as u128 * 10_000) / target_price as u128) as u16;
+    let peg_deviation_bps = calculate_peg_bps(pyth_price, use_conf_edge.then_some(pyth_conf));
 
     Ok(OracleData {
-        pyth_price,
-        has_conflict: false, // v1: single feed, no conflict possible
+        price: pyth_price,
+        conf: pyth_conf,
+        conf_bps: calculate_conf_bps(pyth_price, pyth_conf),
+        quorum: 1,
+        max_disagreement_bps: 0,
+        quorum_lost: false, // single feed, no quorum to lose
         is_stale,
         peg_deviation_bps,
     })
 }
 
-/// Validate Pyth price quality — confidence interval must be < 1% of price.
-pub fn validate_oracle_quality(pyth_feed: &AccountInfo) -> Result<bool> {
-    let pyth_conf = pyth::get_pyth_confidence(pyth_feed)?;
-    let pyth_price = pyth::get_pyth_price(pyth_feed)?;
+/// Read one registry feed. Read failures are reported as a stale reading so
+/// that a broken feed is outvoted rather than failing the whole aggregation.
+pub fn read_feed(account: &AccountInfo, feed: &OracleFeed) -> FeedReading {
+    let reading = match feed.kind {
+        OracleKind::Pyth => pyth::get_pyth_price(account).and_then(|price| {
+            Ok((
+                price,
+                pyth::get_pyth_confidence(account)?,
+                pyth::is_pyth_stale(account, feed.staleness())?,
+            ))
+        }),
+        OracleKind::Switchboard => switchboard::get_switchboard_price(account).and_then(|price| {
+            Ok((
+                price,
+                switchboard::get_switchboard_confidence(account)?,
+                switchboard::is_switchboard_stale(account, feed.staleness())?,
+            ))
+        }),
+    };
+    let (price, conf, is_stale) = reading.unwrap_or((0, 0, true));
+
+    FeedReading {
+        price,
+        conf,
+        weight: feed.weight,
+        is_stale,
+        max_conf_bps: feed.max_conf_bps,
+    }
+}
+
+/// Read every registered feed from `accounts` (in any order) and aggregate
+/// them into a weighted median. Quorum is lost only when the valid feeds are
+/// split so that fewer than `min_quorum` sit within `max_disagreement_bps` of
+/// the median. Short of quorum for any other reason (stale or unusable
+/// feeds), the result is stale and carries `last_good_price`, so the vault
+/// contracts and then exits past the grace period instead of halting.
+pub fn fetch_aggregated_oracle_data(
+    registry: &OracleRegistry,
+    accounts: &[AccountInfo],
+    use_conf_edge: bool,
+    last_good_price: i64,
+) -> Result<OracleData> {
+    let mut readings = Vec::with_capacity(registry.feeds().len());
+    for feed in registry.feeds() {
+        let account = accounts
+            .iter()
+            .find(|a| a.key() == feed.feed)
+            .ok_or(ErrorCode::OracleFeedMismatch)?;
+        readings.push(read_feed(account, feed));
+    }
 
-    if pyth_price == 0 {
-        return Ok(false);
+    let agg = aggregate(&readings, registry.max_disagreement_bps);
+    let status = quorum_status(agg.as_ref(), registry.min_quorum, registry.max_disagreement_bps);
+    match (status, agg) {
+        (QuorumStatus::Met | QuorumStatus::Lost, Some(agg)) => Ok(OracleData {
+            price: agg.price,
+            conf: agg.conf,
+            conf_bps: calculate_conf_bps(agg.price, agg.conf),
+            quorum: agg.quorum,
+            max_disagreement_bps: agg.max_disagreement_bps,
+            quorum_lost: status == QuorumStatus::Lost,
+            is_stale: false,
+            peg_deviation_bps: calculate_peg_bps(agg.price, use_conf_edge.then_some(agg.conf)),
+        }),
+        (_, agg) => Ok(OracleData {
+            price: last_good_price,
+            conf: 0,
+            conf_bps: calculate_conf_bps(last_good_price, 0),
+            quorum: agg.map_or(0, |a| a.quorum),
+            max_disagreement_bps: agg.map_or(0, |a| a.max_disagreement_bps),
+            quorum_lost: false,
+            is_stale: true,
+            peg_deviation_bps: calculate_peg_bps(last_good_price, None),
+        }),
     }
+}
 
-    let conf_bps = ((pyth_conf as u128 * 10_000) / pyth_price.unsigned_abs() as u128) as u16;
-    Ok(conf_bps <= 100) // confidence must be < 1%
+/// Validate price quality — confidence interval must be ≤ `max_conf_bps` of price.
+pub fn validate_oracle_quality(oracle_data: &OracleData, max_conf_bps: u16) -> bool {
+    oracle_data.price > 0 && oracle_data.conf_bps <= max_conf_bps
 }
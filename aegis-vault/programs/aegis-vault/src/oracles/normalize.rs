@@ -0,0 +1,61 @@
+// Fixed-point price normalisation shared by every oracle adapter.
+// All prices are returned as i64 with PRICE_DECIMALS decimals ($1.00 = 1_000_000).
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Decimals of every normalised price and confidence
+pub const PRICE_DECIMALS: i64 = 6;
+
+// 10^38 is the largest power of ten that fits in i128
+const MAX_POW10_I128: i64 = 38;
+
+/// Rescale a non-negative `value × 10^exponent` to PRICE_DECIMALS, truncating.
+/// `None` if the result does not fit in i128.
+fn rescale(value: i128, exponent: i64) -> Option<i128> {
+    let shift = exponent.checked_add(PRICE_DECIMALS)?;
+    if shift >= 0 {
+        if value == 0 {
+            return Some(0);
+        }
+        if shift > MAX_POW10_I128 {
+            return None;
+        }
+        value.checked_mul(10i128.pow(shift as u32))
+    } else if -shift > MAX_POW10_I128 {
+        Some(0) // divisor exceeds any i128 value
+    } else {
+        Some(value / 10i128.pow((-shift) as u32))
+    }
+}
+
+/// Normalise a strictly positive mantissa with a base-10 exponent.
+/// Rejects non-positive inputs and results that overflow or truncate to zero.
+fn normalize_positive(mantissa: i128, exponent: i64) -> Result<i64> {
+    require!(mantissa > 0, ErrorCode::NonPositivePrice);
+    let scaled = rescale(mantissa, exponent).ok_or(ErrorCode::PriceOutOfRange)?;
+    require!(scaled > 0, ErrorCode::PriceOutOfRange);
+    i64::try_from(scaled).map_err(|_| error!(ErrorCode::PriceOutOfRange))
+}
+
+/// Pyth price (`price × 10^expo`) → 6dp
+pub fn normalize_pyth_price(price: i64, expo: i32) -> Result<i64> {
+    normalize_positive(price as i128, expo as i64)
+}
+
+/// Pyth confidence (`conf × 10^expo`) → 6dp; zero is allowed
+pub fn normalize_pyth_conf(conf: u64, expo: i32) -> Result<u64> {
+    let scaled = rescale(conf as i128, expo as i64).ok_or(ErrorCode::PriceOutOfRange)?;
+    u64::try_from(scaled).map_err(|_| error!(ErrorCode::PriceOutOfRange))
+}
+
+/// Switchboard decimal (`mantissa × 10^-scale`) → 6dp
+pub fn normalize_switchboard_decimal(mantissa: i128, scale: u32) -> Result<i64> {
+    normalize_positive(mantissa, -(scale as i64))
+}
+
+/// Switchboard decimal confidence (e.g. std deviation) → 6dp; zero is allowed
+pub fn normalize_switchboard_conf(mantissa: i128, scale: u32) -> Result<u64> {
+    require!(mantissa >= 0, ErrorCode::PriceOutOfRange);
+    let scaled = rescale(mantissa, -(scale as i64)).ok_or(ErrorCode::PriceOutOfRange)?;
+    u64::try_from(scaled).map_err(|_| error!(ErrorCode::PriceOutOfRange))
+}
@@ -1,53 +1,73 @@
 use anchor_lang::prelude::*;
 use pyth_sdk_solana::load_price_feed_from_account_info;
+use pyth_sdk_solana::state::{load_price_account, PriceStatus};
+use super::normalize::{normalize_pyth_conf, normalize_pyth_price};
+use super::staleness::{is_stale_now, PublishInfo, StalenessConfig};
 
-/// Fetch the current USDC price from Pyth oracle
+/// Fetch the current USDC price from Pyth oracle. A feed that is not
+/// `Trading` yields its previous price here; `is_pyth_stale` reports such a
+/// feed as stale so that price is never acted on as current.
 pub fn get_pyth_price(price_feed: &AccountInfo) -> Result<i64> {
     let price_feed_data = load_price_feed_from_account_info(price_feed)
         .map_err(|_| error!(crate::errors::ErrorCode::PythPriceUnavailable))?;
     
-    let price = price_feed_data
-        .get_current_price()
-        .ok_or(crate::errors::ErrorCode::PythPriceUnavailable)?;
+    // Freshness is judged separately from the publish slot/time
+    let price = price_feed_data.get_price_unchecked();
     
-    // Pyth returns price with exponent, normalize to 6 decimals (USDC standard)
-    // For USDC/USD, exponent is typically -8, price is in format: price * 10^exponent
-    let normalized_price = if price.expo >= 0 {
-        price.price * 10i64.pow(price.expo as u32) * 1_000_000
-    } else {
-        let divisor = 10i64.pow((-price.expo) as u32);
-        (price.price * 1_000_000) / divisor
-    };
-    
-    Ok(normalized_price)
+    // Pyth returns price × 10^expo; normalize to 6 decimals (USDC standard)
+    normalize_pyth_price(price.price, price.expo)
 }
 
-/// Check if Pyth price feed is stale
-pub fn is_pyth_stale(price_feed: &AccountInfo, max_age_slots: u64, current_slot: u64) -> Result<bool> {
+/// Read when the current Pyth price was published. The legacy price account
+/// reports its aggregate publish slot; other formats fall back to time only.
+pub fn get_pyth_publish_info(price_feed: &AccountInfo) -> Result<PublishInfo> {
     let price_feed_data = load_price_feed_from_account_info(price_feed)
         .map_err(|_| error!(crate::errors::ErrorCode::PythPriceUnavailable))?;
     
-    let price = price_feed_data
-        .get_current_price()
-        .ok_or(crate::errors::ErrorCode::PythPriceUnavailable)?;
+    // Freshness is judged separately from the publish slot/time
+    let price = price_feed_data.get_price_unchecked();
     
-    // Pyth publish_time is in Unix timestamp, need to convert to slots
-    // Approximate: 1 slot = 400ms, so slots_since = (current_time - publish_time) / 0.4
-    let clock = Clock::get()?;
-    let time_diff = clock.unix_timestamp - price.publish_time;
-    let slots_since = (time_diff as u64 * 10) / 4; // Convert seconds to slots
+    let data = price_feed.try_borrow_data()?;
+    let publish_slot = load_price_account(&data).ok().map(|account| {
+        match account.agg.status {
+            PriceStatus::Trading => account.agg.pub_slot,
+            _ => account.prev_slot,
+        }
+    });
     
-    Ok(slots_since > max_age_slots)
+    Ok(PublishInfo {
+        publish_slot,
+        publish_time: price.publish_time,
+    })
+}
+
+/// False when a legacy price account's aggregate is halted, in auction or of
+/// unknown status. Formats that carry no status count as trading.
+pub fn is_pyth_trading(price_feed: &AccountInfo) -> Result<bool> {
+    let data = price_feed.try_borrow_data()?;
+    Ok(!matches!(
+        load_price_account(&data),
+        Ok(account) if account.agg.status != PriceStatus::Trading
+    ))
+}
+
+/// Check if Pyth price feed is stale by slot and wall-clock age. A feed that
+/// is not trading is always stale, however recent its last price.
+pub fn is_pyth_stale(price_feed: &AccountInfo, config: StalenessConfig) -> Result<bool> {
+    if !is_pyth_trading(price_feed)? {
+        return Ok(true);
+    }
+    let publish = get_pyth_publish_info(price_feed)?;
+    is_stale_now(publish, config)
 }
 
-/// Get Pyth price confidence interval
+/// Get Pyth price confidence interval, normalised to 6 decimals like the price
 pub fn get_pyth_confidence(price_feed: &AccountInfo) -> Result<u64> {
     let price_feed_data = load_price_feed_from_account_info(price_feed)
         .map_err(|_| error!(crate::errors::ErrorCode::PythPriceUnavailable))?;
     
-    let price = price_feed_data
-        .get_current_price()
-        .ok_or(crate::errors::ErrorCode::PythPriceUnavailable)?;
+    // Freshness is judged separately from the publish slot/time
+    let price = price_feed_data.get_price_unchecked();
     
-    Ok(price.conf)
+    normalize_pyth_conf(price.conf, price.expo)
 }
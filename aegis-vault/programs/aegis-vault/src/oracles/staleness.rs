@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+/// Publish timestamps up to this far ahead of the validator clock are
+/// treated as age 0 (publisher/validator clock skew); beyond it the feed is
+/// considered untrustworthy and reported stale.
+pub const MAX_FUTURE_SKEW_SEC: i64 = 10;
+
+/// Maximum oracle age, by slot and by wall clock (both must pass)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StalenessConfig {
+    pub max_age_slots: u64,
+    pub max_age_sec: i64,
+}
+
+/// When a price was published, as far as the feed format reports it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublishInfo {
+    /// Slot the aggregate was published in; `None` if the format lacks it
+    pub publish_slot: Option<u64>,
+    pub publish_time: i64,
+}
+
+/// Slot-accurate staleness check.
+/// - Slot age is measured against the feed's own publish slot when available;
+///   a publish slot after `current_slot` is impossible and reported stale.
+/// - Wall-clock age always applies; future timestamps within
+///   `MAX_FUTURE_SKEW_SEC` count as fresh, beyond it as stale.
+pub fn is_stale(
+    publish: PublishInfo,
+    current_slot: u64,
+    now: i64,
+    config: StalenessConfig,
+) -> bool {
+    if let Some(publish_slot) = publish.publish_slot {
+        match current_slot.checked_sub(publish_slot) {
+            Some(slots_since) if slots_since <= config.max_age_slots => {}
+            _ => return true,
+        }
+    }
+
+    match now.checked_sub(publish.publish_time) {
+        Some(age) if age >= 0 => age > config.max_age_sec,
+        Some(age) => -age > MAX_FUTURE_SKEW_SEC,
+        None => true,
+    }
+}
+
+/// Convenience wrapper reading the current clock
+pub fn is_stale_now(publish: PublishInfo, config: StalenessConfig) -> Result<bool> {
+    let clock = Clock::get()?;
+    Ok(is_stale(publish, clock.slot, clock.unix_timestamp, config))
+}
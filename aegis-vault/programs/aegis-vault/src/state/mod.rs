@@ -1,5 +1,11 @@
 pub mod vault;
 pub mod user_position;
+pub mod peg_observations;
+pub mod oracle_registry;
+pub mod referrer_stats;
 
 pub use vault::*;
 pub use user_position::*;
+pub use peg_observations::*;
+pub use oracle_registry::*;
+pub use referrer_stats::*;
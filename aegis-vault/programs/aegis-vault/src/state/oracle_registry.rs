@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::oracles::StalenessConfig;
+
+/// Price source behind a registry entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum OracleKind {
+    #[default]
+    Pyth,
+    Switchboard,
+}
+
+/// One price feed and the limits its readings are held to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct OracleFeed {
+    pub feed: Pubkey,
+    pub kind: OracleKind,
+    pub weight: u16,
+    pub max_age_slots: u64,
+    pub max_age_sec: i64,
+    pub max_conf_bps: u16,
+}
+
+impl OracleFeed {
+    pub const LEN: usize = 32 + 1 + 2 + 8 + 8 + 2;
+
+    /// Staleness limits for this feed (slot and wall-clock)
+    pub fn staleness(&self) -> StalenessConfig {
+        StalenessConfig {
+            max_age_slots: self.max_age_slots,
+            max_age_sec: self.max_age_sec,
+        }
+    }
+}
+
+#[account]
+pub struct OracleRegistry {
+    /// Vault this registry belongs to
+    pub vault: Pubkey,
+
+    /// Agreeing feeds required; fewer on a price split → PANIC, fewer
+    /// because feeds are stale → stale handling (CONTRACT, then EXIT)
+    pub min_quorum: u8,
+
+    /// Max distance from the median for a feed to count towards quorum
+    pub max_disagreement_bps: u16,
+
+    /// Number of configured feeds (≤ CAPACITY)
+    pub len: u8,
+
+    pub feeds: [OracleFeed; OracleRegistry::CAPACITY],
+
+    pub bump: u8,
+}
+
+impl OracleRegistry {
+    pub const CAPACITY: usize = 8;
+
+    pub const LEN: usize = 8 // discriminator
+        + 32 // vault
+        + 1 // min_quorum
+        + 2 // max_disagreement_bps
+        + 1 // len
+        + OracleFeed::LEN * Self::CAPACITY // feeds
+        + 1; // bump
+
+    /// Configured feeds, in registration order
+    pub fn feeds(&self) -> &[OracleFeed] {
+        &self.feeds[..self.len as usize]
+    }
+
+    pub fn contains(&self, feed: &Pubkey) -> bool {
+        self.feeds().iter().any(|f| f.feed == *feed)
+    }
+
+    /// Append a feed; false if the registry is full
+    pub fn push(&mut self, feed: OracleFeed) -> bool {
+        if self.len as usize >= Self::CAPACITY {
+            return false;
+        }
+        self.feeds[self.len as usize] = feed;
+        self.len += 1;
+        true
+    }
+
+    /// Remove a feed, keeping the order of the rest; false if not registered
+    pub fn remove(&mut self, feed: &Pubkey) -> bool {
+        let Some(idx) = self.feeds().iter().position(|f| f.feed == *feed) else {
+            return false;
+        };
+        let len = self.len as usize;
+        self.feeds.copy_within(idx + 1..len, idx);
+        self.feeds[len - 1] = OracleFeed::default();
+        self.len -= 1;
+        true
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+/// A single oracle sample (price and conf normalised to 6dp)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Observation {
+    pub slot: u64,
+    pub price: i64,
+    pub conf: u64,
+}
+
+#[account]
+pub struct PegObservations {
+    /// Vault this buffer belongs to
+    pub vault: Pubkey,
+
+    /// Index the next sample is written to
+    pub head: u16,
+
+    /// Number of valid samples (≤ CAPACITY)
+    pub len: u16,
+
+    /// Ring buffer of samples
+    pub samples: [Observation; PegObservations::CAPACITY],
+
+    pub bump: u8,
+}
+
+impl PegObservations {
+    pub const CAPACITY: usize = 32;
+
+    pub const LEN: usize = 8 // discriminator
+        + 32 // vault
+        + 2 // head
+        + 2 // len
+        + 24 * Self::CAPACITY // samples
+        + 1; // bump
+
+    /// Most recent sample, if any
+    pub fn latest(&self) -> Option<&Observation> {
+        if self.len == 0 {
+            return None;
+        }
+        let idx = (self.head as usize + Self::CAPACITY - 1) % Self::CAPACITY;
+        Some(&self.samples[idx])
+    }
+
+    /// True if at least `interval_slots` have passed since the latest sample
+    pub fn is_due(&self, slot: u64, interval_slots: u64) -> bool {
+        match self.latest() {
+            Some(latest) => slot >= latest.slot.saturating_add(interval_slots),
+            None => true,
+        }
+    }
+
+    /// Append a sample, overwriting the oldest once full
+    pub fn push(&mut self, observation: Observation) {
+        self.samples[self.head as usize] = observation;
+        self.head = ((self.head as usize + 1) % Self::CAPACITY) as u16;
+        self.len = (self.len + 1).min(Self::CAPACITY as u16);
+    }
+
+    /// Samples ordered oldest → newest
+    pub fn iter(&self) -> impl Iterator<Item = &Observation> {
+        let start = self.head as usize + Self::CAPACITY - self.len as usize;
+        (0..self.len as usize).map(move |i| &self.samples[(start + i) % Self::CAPACITY])
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// Running totals of deposits attributed to one referrer, the basis for
+/// sharing management fees with them later
+#[account]
+pub struct ReferrerStats {
+    /// Vault the referrals were made into
+    pub vault: Pubkey,
+
+    /// Wallet credited with the referrals
+    pub referrer: Pubkey,
+
+    /// Deposits made with this referrer
+    pub deposit_count: u64,
+
+    /// USDC credited to the vault by those deposits
+    pub total_deposited: u64,
+
+    /// Shares minted by those deposits
+    pub total_shares: u64,
+
+    pub bump: u8,
+}
+
+impl ReferrerStats {
+    /// Smallest deposit, in USDC base units received, credited to a referrer
+    pub const MIN_DEPOSIT: u64 = 10_000_000;
+
+    pub const LEN: usize = 8 // discriminator
+        + 32 // vault
+        + 32 // referrer
+        + 8 // deposit_count
+        + 8 // total_deposited
+        + 8 // total_shares
+        + 1; // bump
+}
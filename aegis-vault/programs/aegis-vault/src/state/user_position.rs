@@ -1,23 +1,65 @@
 use anchor_lang::prelude::*;
 
+/// Per-depositor record. Shares are ordinary SPL tokens and can be
+/// transferred, so the share token account — not this record — decides what
+/// a wallet can redeem: any holder may withdraw, with or without a position.
 #[account]
 pub struct UserPosition {
     /// Owner of this position
     pub owner: Pubkey,
     
-    /// Number of vault shares owned
+    /// Owner's share balance as of their last deposit or withdrawal through
+    /// the vault. Informational only; lags the token account after transfers.
     pub shares: u64,
     
     /// Timestamp when first deposited
     pub deposited_at: i64,
     
     pub bump: u8,
+    
+    /// Assets paid for `shares` (average-cost method); shares that arrived
+    /// by transfer are costed at NAV when the position next resyncs
+    pub cost_basis: u64,
+    
+    /// Cumulative assets credited by deposits
+    pub total_deposited: u64,
+    
+    /// Cumulative assets paid out by withdrawals, before any transfer fee
+    pub total_withdrawn: u64,
+    
+    /// Withdrawal proceeds minus the cost basis they released
+    pub realized_pnl: i64,
 }
 
 impl UserPosition {
-    pub const LEN: usize = 8 + // discriminator
+    /// Size of positions opened before the cost-basis fields were appended
+    pub const LEGACY_LEN: usize = 8 + // discriminator
         32 + // owner
         8 + // shares
         8 + // deposited_at
         1; // bump
+
+    pub const LEN: usize = Self::LEGACY_LEN +
+        8 + // cost_basis
+        8 + // total_deposited
+        8 + // total_withdrawn
+        8; // realized_pnl
+    
+    pub fn cost_basis(&self) -> aegis_core::CostBasis {
+        aegis_core::CostBasis {
+            shares: self.shares,
+            cost: self.cost_basis,
+            total_deposited: self.total_deposited,
+            total_withdrawn: self.total_withdrawn,
+            realized_pnl: self.realized_pnl,
+        }
+    }
+    
+    pub fn set_cost_basis(&mut self, basis: aegis_core::CostBasis) {
+        self.shares = basis.shares;
+        self.cost_basis = basis.cost;
+        self.total_deposited = basis.total_deposited;
+        self.total_withdrawn = basis.total_withdrawn;
+        self.realized_pnl = basis.realized_pnl;
+    }
 }
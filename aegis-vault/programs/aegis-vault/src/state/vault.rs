@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use aegis_core::Thresholds;
+use crate::oracles::StalenessConfig;
 
 #[account]
 pub struct Vault {
@@ -20,7 +22,7 @@ pub struct Vault {
     /// Vault metrics
     pub total_supplied: u64,   // Total USDC supplied to lending (v2: Kamino)
     pub total_borrowed: u64,   // Total USDC borrowed
-    pub total_shares: u64,     // Total vault shares minted
+    pub total_shares: u64,     // Total vault shares minted, plus the first deposit's DEAD_SHARES
 
     /// State tracking
     pub last_rebalance_slot: u64,
@@ -37,10 +39,53 @@ pub struct Vault {
     pub reexpansion_delay_sec: i64, // 30,000 seconds after depeg recovery
 
     pub bump: u8,
+
+    // Everything below was appended to the original layout; vaults created
+    // before it are grown and filled in by `migrate_vault`
+
+    /// Token program owning the share mint: SPL Token for `initialize_vault`,
+    /// Token-2022 for `initialize_vault_2022`
+    pub share_token_program: Pubkey,
+
+    /// Borrowed asset and its Pyth feed when it differs from the USDC
+    /// collateral (default until `set_debt_asset` is called)
+    pub debt_mint: Pubkey,
+    pub debt_feed: Pubkey,
+
+    /// Multi-feed oracle registry (default until it holds `min_quorum` feeds)
+    pub oracle_registry: Pubkey,
+
+    /// Lending venue (default pubkeys until `set_lending_venue` is called)
+    pub lending_program: Pubkey,
+    pub lending_reserve: Pubkey,
+    pub lending_obligation: Pubkey,
+    pub flash_fee_bps: u16,
+
+    /// Swap route: DEX program (Orca Whirlpool by default) and the
+    /// non-USDC input token with its Pyth feed (default until `set_swap_route`)
+    pub dex_program: Pubkey,
+    pub swap_input_mint: Pubkey,
+    pub swap_input_feed: Pubkey,
+    /// Largest shortfall of a swap's output below the oracle-implied amount
+    pub max_slippage_bps: u16,
+
+    /// Oracle freshness tracking
+    pub last_good_price: i64,  // last fresh, reliable oracle price (6dp); 0 = none yet
+    pub last_good_slot: u64,
+    pub stale_since_slot: u64, // first rebalance that saw the oracle stale; 0 = fresh
+
+    /// Oracle and peg parameters (PRD defaults, set at init)
+    pub oracle_max_age_sec: i64,    // 60 seconds wall-clock
+    pub stale_grace_slots: u64,     // 750 slots (~5 minutes) stale in CONTRACT before EXIT
+    pub max_conf_bps: u16,          // 100 bps; wider confidence → CONTRACT
+    pub peg_use_conf_edge: bool,    // measure peg at worst edge of price ± conf
+    pub twap_window_slots: u64,     // 750 slots (~5 minutes) of peg observations
+    pub observation_interval_slots: u64, // 25 slots (~10 seconds) between samples
 }
 
 impl Vault {
-    pub const LEN: usize = 8    // discriminator
+    /// Size of vaults created before fields were appended after `bump`
+    pub const LEGACY_LEN: usize = 8    // discriminator
         + 32  // authority
         + 32  // usdc_mint
         + 32  // vault_usdc
@@ -61,17 +106,93 @@ impl Vault {
         + 8   // reexpansion_delay_sec
         + 1;  // bump
 
+    pub const LEN: usize = Self::LEGACY_LEN
+        + 32  // share_token_program
+        + 32  // debt_mint
+        + 32  // debt_feed
+        + 32  // oracle_registry
+        + 32  // lending_program
+        + 32  // lending_reserve
+        + 32  // lending_obligation
+        + 2   // flash_fee_bps
+        + 32  // dex_program
+        + 32  // swap_input_mint
+        + 32  // swap_input_feed
+        + 2   // max_slippage_bps
+        + 8   // last_good_price
+        + 8   // last_good_slot
+        + 8   // stale_since_slot
+        + 8   // oracle_max_age_sec
+        + 8   // stale_grace_slots
+        + 2   // max_conf_bps
+        + 1   // peg_use_conf_edge
+        + 8   // twap_window_slots
+        + 8;  // observation_interval_slots
+
     /// Current equity = supplied − borrowed (saturating to prevent underflow)
     pub fn equity(&self) -> u64 {
-        self.total_supplied.saturating_sub(self.total_borrowed)
+        aegis_core::equity(self.total_supplied, self.total_borrowed)
+    }
+
+    /// Assets backing the shares: venue equity plus `idle` USDC held by the vault
+    pub fn total_assets(&self, idle: u64) -> Option<u64> {
+        aegis_core::total_assets(self.total_supplied, self.total_borrowed, idle)
     }
 
     /// Current leverage in basis points (10000 = 1.00x)
     pub fn leverage_bps(&self) -> u16 {
-        let eq = self.equity();
-        if eq == 0 {
-            return 0;
+        aegis_core::leverage_bps(self.total_supplied, self.total_borrowed)
+    }
+
+    /// State machine thresholds
+    pub fn thresholds(&self) -> Thresholds {
+        Thresholds {
+            peg_warn_bps: self.peg_warn_bps,
+            peg_exit_bps: self.peg_exit_bps,
+            peg_panic_bps: self.peg_panic_bps,
+            hf_floor_bps: self.hf_floor_bps,
+            stale_grace_slots: self.stale_grace_slots,
         }
-        ((self.total_supplied as u128 * 10_000) / eq as u128) as u16
+    }
+
+    /// Oracle staleness limits (slot and wall-clock)
+    pub fn staleness(&self) -> StalenessConfig {
+        StalenessConfig {
+            max_age_slots: self.oracle_stale_slots,
+            max_age_sec: self.oracle_max_age_sec,
+        }
+    }
+
+    /// Track oracle freshness across rebalances: a fresh, reliable price
+    /// becomes the last-known-good price; a stale one starts (or continues)
+    /// the stale period. Returns how long the oracle has been stale, if it is.
+    pub fn track_oracle(&mut self, price: i64, is_stale: bool, reliable: bool, slot: u64) -> Option<u64> {
+        if !is_stale {
+            self.stale_since_slot = 0;
+            if reliable {
+                self.last_good_price = price;
+                self.last_good_slot = slot;
+            }
+            return None;
+        }
+        if self.stale_since_slot == 0 {
+            self.stale_since_slot = slot;
+        }
+        Some(slot.saturating_sub(self.stale_since_slot))
+    }
+
+    /// True when the borrowed asset differs from the collateral
+    pub fn has_debt_asset(&self) -> bool {
+        self.debt_mint != Pubkey::default()
+    }
+
+    /// True once an oracle registry replaces the single Pyth feed
+    pub fn has_oracle_registry(&self) -> bool {
+        self.oracle_registry != Pubkey::default()
+    }
+
+    /// True once a lending venue has been configured for the leverage engine
+    pub fn has_lending_venue(&self) -> bool {
+        self.lending_program != Pubkey::default()
     }
 }
@@ -0,0 +1,111 @@
+// Closing a position after a full exit returns its rent, and optionally the
+// share ATA's, to the owner; an ATA that is already gone can be left out
+mod harness;
+
+use aegis_vault::errors::ErrorCode;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token_2022::spl_token_2022;
+use harness::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+/// `close_position` for `holder`, passing `user_shares` as their share ATA
+async fn close_position(
+    s: &mut Scenario,
+    holder: &Holder,
+    user_shares: Option<Pubkey>,
+    close_share_account: bool,
+) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: aegis_vault::ID,
+        accounts: aegis_vault::accounts::ClosePosition {
+            vault: s.vault,
+            user_position: holder.position(),
+            user: holder.wallet.pubkey(),
+            share_mint: s.share_mint,
+            user_shares,
+            share_token_program: s.share_token_program,
+        }
+        .to_account_metas(None),
+        data: aegis_vault::instruction::ClosePosition {
+            close_share_account,
+        }
+        .data(),
+    };
+    send(&mut s.ctx, &[ix], &[&holder.wallet]).await
+}
+
+#[tokio::test]
+async fn full_exit_then_close_returns_rent_to_the_owner() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    s.withdraw_as(&alice, DEPOSIT).await.unwrap();
+
+    let wallet = s.lamports(alice.wallet.pubkey()).await;
+    let rent = s.lamports(alice.position()).await + s.lamports(alice.shares).await;
+    close_position(&mut s, &alice, Some(alice.shares), true)
+        .await
+        .unwrap();
+    assert_eq!(s.lamports(alice.wallet.pubkey()).await, wallet + rent);
+    assert!(s.fetch_position(&alice).await.is_none());
+    assert_eq!(s.lamports(alice.shares).await, 0);
+
+    // Depositing again recreates both accounts, with fresh history
+    s.deposit_as(&alice, DEPOSIT / 2).await.unwrap();
+    let position = s.fetch_position(&alice).await.unwrap();
+    assert_eq!(position.total_deposited, DEPOSIT / 2);
+    assert_eq!(position.total_withdrawn, 0);
+    assert_eq!(s.token_balance(alice.shares).await, DEPOSIT / 2);
+}
+
+#[tokio::test]
+async fn close_requires_an_empty_share_account() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    let bob = s.add_holder(0).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    let result = close_position(&mut s, &alice, Some(alice.shares), false).await;
+    assert_vault_error(result, ErrorCode::PositionNotEmpty);
+
+    // Shares sent elsewhere count as gone; the ATA stays open when asked to
+    s.transfer_shares(&alice, &bob, DEPOSIT).await.unwrap();
+    close_position(&mut s, &alice, Some(alice.shares), false)
+        .await
+        .unwrap();
+    assert!(s.fetch_position(&alice).await.is_none());
+    assert_eq!(s.token_balance(alice.shares).await, 0);
+}
+
+#[tokio::test]
+async fn close_without_the_share_account_checks_the_position() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    let bob = s.add_holder(0).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+
+    // Shares transferred away still show on the position until it resyncs
+    s.transfer_shares(&alice, &bob, DEPOSIT).await.unwrap();
+    let result = close_position(&mut s, &alice, None, false).await;
+    assert_vault_error(result, ErrorCode::PositionNotEmpty);
+
+    // After a full exit the ATA can be closed first, and the position after
+    s.transfer_shares(&bob, &alice, DEPOSIT).await.unwrap();
+    s.withdraw_as(&alice, DEPOSIT).await.unwrap();
+    let ix = spl_token_2022::instruction::close_account(
+        &s.share_token_program,
+        &alice.shares,
+        &alice.wallet.pubkey(),
+        &alice.wallet.pubkey(),
+        &[],
+    )
+    .unwrap();
+    send(&mut s.ctx, &[ix], &[&alice.wallet]).await.unwrap();
+
+    let result = close_position(&mut s, &alice, None, true).await;
+    assert_vault_error(result, ErrorCode::ShareAccountMissing);
+    close_position(&mut s, &alice, None, false).await.unwrap();
+    assert!(s.fetch_position(&alice).await.is_none());
+}
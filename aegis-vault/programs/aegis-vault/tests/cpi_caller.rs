@@ -0,0 +1,143 @@
+// Composability: `aegis-cpi-example` holds shares in a PDA and deposits and
+// withdraws through `aegis-vault-cpi`, with every account created up front
+mod harness;
+
+use aegis_vault::errors::ErrorCode;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account;
+use anchor_spl::token_2022::spl_token_2022;
+use harness::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_program;
+
+/// The `aegis-cpi-example` treasury PDA and its token accounts
+struct CpiTreasury {
+    address: Pubkey,
+    usdc: Pubkey,
+    shares: Pubkey,
+}
+
+/// Set up the `aegis-cpi-example` treasury the way an off-chain client
+/// would: its USDC (funded with `usdc`) and share ATAs, and its position
+async fn setup_treasury(s: &mut Scenario, usdc: u64) -> CpiTreasury {
+    let address =
+        Pubkey::find_program_address(&[aegis_cpi_example::TREASURY_SEED], &aegis_cpi_example::ID).0;
+    let treasury = CpiTreasury {
+        address,
+        usdc: get_associated_token_address_with_program_id(
+            &address,
+            &s.usdc_mint,
+            &s.usdc_token_program,
+        ),
+        shares: s.share_account_of(&address),
+    };
+    let payer = s.ctx.payer.pubkey();
+    let instructions = [
+        create_associated_token_account(&payer, &address, &s.usdc_mint, &s.usdc_token_program),
+        create_associated_token_account(&payer, &address, &s.share_mint, &s.share_token_program),
+        spl_token_2022::instruction::mint_to(
+            &s.usdc_token_program,
+            &s.usdc_mint,
+            &treasury.usdc,
+            &payer,
+            &[],
+            usdc,
+        )
+        .unwrap(),
+        Instruction {
+            program_id: aegis_vault::ID,
+            accounts: aegis_vault::accounts::OpenPosition {
+                user_position: position_of(&address),
+                payer,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: aegis_vault::instruction::OpenPosition { owner: address }.data(),
+        },
+    ];
+    send(&mut s.ctx, &instructions, &[]).await.unwrap();
+    treasury
+}
+
+fn treasury_accounts(s: &Scenario, treasury: &CpiTreasury) -> Vec<AccountMeta> {
+    aegis_cpi_example::accounts::Treasury {
+        treasury: treasury.address,
+        vault: s.vault,
+        user_position: position_of(&treasury.address),
+        usdc_mint: s.usdc_mint,
+        treasury_usdc: treasury.usdc,
+        vault_usdc: s.vault_usdc,
+        share_mint: s.share_mint,
+        treasury_shares: treasury.shares,
+        token_program: s.usdc_token_program,
+        share_token_program: s.share_token_program,
+        aegis_vault_program: aegis_vault::ID,
+    }
+    .to_account_metas(None)
+}
+
+/// The example program deposits `amount` of its treasury's USDC by CPI
+async fn treasury_invest(
+    s: &mut Scenario,
+    treasury: &CpiTreasury,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: aegis_cpi_example::ID,
+        accounts: treasury_accounts(s, treasury),
+        data: aegis_cpi_example::instruction::Invest { amount }.data(),
+    };
+    send(&mut s.ctx, &[ix], &[]).await
+}
+
+/// The example program withdraws `usdc_amount` to its treasury by CPI
+async fn treasury_redeem(
+    s: &mut Scenario,
+    treasury: &CpiTreasury,
+    usdc_amount: u64,
+    max_shares_in: u64,
+) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: aegis_cpi_example::ID,
+        accounts: treasury_accounts(s, treasury),
+        data: aegis_cpi_example::instruction::Redeem {
+            usdc_amount,
+            max_shares_in,
+        }
+        .data(),
+    };
+    send(&mut s.ctx, &[ix], &[]).await
+}
+
+#[tokio::test]
+async fn pda_treasury_deposits_and_withdraws_by_cpi() {
+    let mut s = Scenario::new().await;
+    let treasury = setup_treasury(&mut s, DEPOSIT).await;
+
+    treasury_invest(&mut s, &treasury, DEPOSIT).await.unwrap();
+    assert_eq!(s.token_balance(treasury.usdc).await, 0);
+    assert_eq!(s.token_balance(treasury.shares).await, DEPOSIT);
+    let position = s.fetch_position_of(&treasury.address).await.unwrap();
+    assert_eq!(position.owner, treasury.address);
+    assert_eq!(position.total_deposited, DEPOSIT);
+    assert!(position.deposited_at > 0);
+
+    // NAV 1.10: 110 USDC costs 100 shares and realizes 10 USDC of gain
+    s.set_nav_bps(11_000).await;
+    let result = treasury_redeem(&mut s, &treasury, 110_000_000, 99_999_999).await;
+    assert_vault_error(result, ErrorCode::MaxSharesExceeded);
+    treasury_redeem(&mut s, &treasury, 110_000_000, 100_000_000)
+        .await
+        .unwrap();
+    assert_eq!(s.token_balance(treasury.usdc).await, 110_000_000);
+    assert_eq!(
+        s.token_balance(treasury.shares).await,
+        DEPOSIT - 100_000_000
+    );
+    let position = s.fetch_position_of(&treasury.address).await.unwrap();
+    assert_eq!(position.realized_pnl, 10_000_000);
+}
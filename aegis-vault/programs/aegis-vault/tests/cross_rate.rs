@@ -0,0 +1,32 @@
+use aegis_vault::logic::{calculate_cross_rate_bps, two_asset_peg_bps};
+
+#[test]
+fn cross_rate_is_relative_to_the_debt_price() {
+    // USDC $1.0000 supplied, USDT $0.9970 borrowed: collateral 30.09 bps rich
+    assert_eq!(calculate_cross_rate_bps(1_000_000, 997_000, None), 30);
+    assert_eq!(calculate_cross_rate_bps(997_000, 1_000_000, None), 30);
+}
+
+#[test]
+fn both_legs_off_peg_together_have_no_cross_deviation() {
+    assert_eq!(calculate_cross_rate_bps(995_000, 995_000, None), 0);
+}
+
+#[test]
+fn conf_edges_widen_the_cross_rate() {
+    assert_eq!(calculate_cross_rate_bps(1_000_000, 1_000_000, Some((500, 500))), 10);
+}
+
+#[test]
+fn non_positive_debt_price_is_maximal_deviation() {
+    assert_eq!(calculate_cross_rate_bps(1_000_000, 0, None), u16::MAX);
+    assert_eq!(calculate_cross_rate_bps(1_000_000, -1, None), u16::MAX);
+}
+
+#[test]
+fn worst_of_legs_and_cross_rate_drives_the_vault() {
+    assert_eq!(two_asset_peg_bps(5, 0, 0), 5);
+    assert_eq!(two_asset_peg_bps(0, 12, 3), 12);
+    // Both legs inside warn, but they have drifted apart
+    assert_eq!(two_asset_peg_bps(8, 8, 16), 16);
+}
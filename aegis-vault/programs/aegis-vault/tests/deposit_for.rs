@@ -0,0 +1,172 @@
+// Deposits on behalf of another owner: the payer funds them, the beneficiary
+// gets the shares and the position, and a third-party referrer can be credited
+mod harness;
+
+use aegis_vault::errors::ErrorCode;
+use aegis_vault::state::ReferrerStats;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use harness::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_program;
+
+/// `payer` deposits `amount` of their USDC for `beneficiary`, crediting
+/// the referrer behind `referrer_stats` if given
+async fn deposit_for(
+    s: &mut Scenario,
+    payer: &Holder,
+    beneficiary: Pubkey,
+    amount: u64,
+    referrer_stats: Option<Pubkey>,
+) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: aegis_vault::ID,
+        accounts: aegis_vault::accounts::DepositFor {
+            vault: s.vault,
+            user_position: position_of(&beneficiary),
+            payer: payer.wallet.pubkey(),
+            beneficiary,
+            usdc_mint: s.usdc_mint,
+            payer_usdc: payer.usdc,
+            vault_usdc: s.vault_usdc,
+            share_mint: s.share_mint,
+            beneficiary_shares: s.share_account_of(&beneficiary),
+            referrer_stats,
+            token_program: s.usdc_token_program,
+            share_token_program: s.share_token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: aegis_vault::instruction::DepositFor { amount }.data(),
+    };
+    send(&mut s.ctx, &[ix], &[&payer.wallet]).await
+}
+
+/// Open `referrer`'s stats account, returning its address
+async fn init_referrer(s: &mut Scenario, referrer: Pubkey) -> Pubkey {
+    let stats = Pubkey::find_program_address(
+        &[b"referrer-stats", s.vault.as_ref(), referrer.as_ref()],
+        &aegis_vault::ID,
+    )
+    .0;
+    let ix = Instruction {
+        program_id: aegis_vault::ID,
+        accounts: aegis_vault::accounts::InitReferrer {
+            vault: s.vault,
+            referrer_stats: stats,
+            payer: s.ctx.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: aegis_vault::instruction::InitReferrer { referrer }.data(),
+    };
+    send(&mut s.ctx, &[ix], &[]).await.unwrap();
+    stats
+}
+
+async fn fetch_referrer_stats(s: &mut Scenario, stats: Pubkey) -> ReferrerStats {
+    let account = s
+        .ctx
+        .banks_client
+        .get_account(stats)
+        .await
+        .unwrap()
+        .unwrap();
+    ReferrerStats::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn payer_deposits_for_a_beneficiary() {
+    let mut s = Scenario::new().await;
+    let treasury = s.add_holder(DEPOSIT).await;
+    // Any key can own shares, including a PDA with no share ATA yet
+    let beneficiary = Pubkey::new_unique();
+    deposit_for(&mut s, &treasury, beneficiary, DEPOSIT, None)
+        .await
+        .unwrap();
+
+    let shares = s.share_account_of(&beneficiary);
+    assert_eq!(s.token_balance(shares).await, DEPOSIT);
+    let position = s.fetch_position_of(&beneficiary).await.unwrap();
+    assert_eq!(position.owner, beneficiary);
+    assert_eq!(position.shares, DEPOSIT);
+    assert_eq!(position.total_deposited, DEPOSIT);
+
+    // Nothing accrues to the payer
+    assert_eq!(s.token_balance(treasury.usdc).await, 0);
+    assert_eq!(s.token_balance(treasury.shares).await, 0);
+    assert!(s.fetch_position(&treasury).await.is_none());
+    assert_eq!(s.fetch_vault().await.total_shares, 2 * DEPOSIT);
+}
+
+#[tokio::test]
+async fn referred_deposits_accumulate_in_referrer_stats() {
+    let mut s = Scenario::new().await;
+    let partner = Pubkey::new_unique();
+    let treasury = s.add_holder(DEPOSIT).await;
+    let bob = s.add_holder(0).await;
+    let stats = init_referrer(&mut s, partner).await;
+
+    let bob_key = bob.wallet.pubkey();
+    deposit_for(&mut s, &treasury, bob_key, DEPOSIT / 2, Some(stats))
+        .await
+        .unwrap();
+    deposit_for(&mut s, &treasury, bob_key, DEPOSIT / 2, Some(stats))
+        .await
+        .unwrap();
+    let referrals = fetch_referrer_stats(&mut s, stats).await;
+    assert_eq!(referrals.referrer, partner);
+    assert_eq!(referrals.deposit_count, 2);
+    assert_eq!(referrals.total_deposited, DEPOSIT);
+    assert_eq!(referrals.total_shares, DEPOSIT);
+
+    // The beneficiary redeems like any depositor
+    s.withdraw_as(&bob, DEPOSIT).await.unwrap();
+    assert_eq!(s.token_balance(bob.usdc).await, DEPOSIT);
+}
+
+#[tokio::test]
+async fn referrers_cannot_credit_themselves() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    let bob = s.add_holder(DEPOSIT).await;
+    let alice_key = alice.wallet.pubkey();
+    let stats = init_referrer(&mut s, alice_key).await;
+
+    // Neither by paying, nor by taking the shares
+    let bob_key = bob.wallet.pubkey();
+    let result = deposit_for(&mut s, &alice, bob_key, DEPOSIT, Some(stats)).await;
+    assert_vault_error(result, ErrorCode::SelfReferral);
+    let result = deposit_for(&mut s, &alice, alice_key, DEPOSIT, Some(stats)).await;
+    assert_vault_error(result, ErrorCode::SelfReferral);
+    let result = deposit_for(&mut s, &bob, alice_key, DEPOSIT, Some(stats)).await;
+    assert_vault_error(result, ErrorCode::SelfReferral);
+    assert_eq!(fetch_referrer_stats(&mut s, stats).await.deposit_count, 0);
+}
+
+#[tokio::test]
+async fn dust_deposits_are_not_credited() {
+    let mut s = Scenario::new().await;
+    let treasury = s.add_holder(DEPOSIT).await;
+    let stats = init_referrer(&mut s, Pubkey::new_unique()).await;
+
+    let beneficiary = Pubkey::new_unique();
+    let dust = ReferrerStats::MIN_DEPOSIT - 1;
+    let result = deposit_for(&mut s, &treasury, beneficiary, dust, Some(stats)).await;
+    assert_vault_error(result, ErrorCode::ReferredDepositTooSmall);
+
+    // The same deposit goes through without a referrer
+    deposit_for(&mut s, &treasury, beneficiary, dust, None)
+        .await
+        .unwrap();
+    let min = ReferrerStats::MIN_DEPOSIT;
+    deposit_for(&mut s, &treasury, beneficiary, min, Some(stats))
+        .await
+        .unwrap();
+    let referrals = fetch_referrer_stats(&mut s, stats).await;
+    assert_eq!(referrals.deposit_count, 1);
+    assert_eq!(referrals.total_deposited, min);
+}
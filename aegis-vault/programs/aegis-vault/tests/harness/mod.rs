@@ -0,0 +1,954 @@
+// program-test harness for end-to-end scenarios: the vault and the mock
+// lending venue run as native processors, oracle accounts are rewritten in
+// place and the clock is warped slot by slot (0.4s per slot).
+#![allow(dead_code)]
+
+use aegis_vault::errors::ErrorCode;
+use aegis_vault::instructions::PnlReport;
+use aegis_vault::lending::{plan_leverage, LeveragePlan};
+use aegis_vault::state::{OracleKind, UserPosition, Vault};
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config;
+use anchor_spl::token_2022::spl_token_2022::extension::{ExtensionType, StateWithExtensions};
+use mock_lending::Obligation;
+use mock_oracles::program_test::{add_oracle, set_oracle};
+use mock_oracles::{OracleFormat, OraclePrice};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::clock::Clock;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_sdk::{system_instruction, system_program, sysvar};
+
+/// $1.00 in 6dp
+pub const PEG: i64 = 1_000_000;
+/// Unix time at slot 0
+pub const START_TS: i64 = 1_700_000_000;
+pub const START_SLOT: u64 = 100;
+
+// Vault parameters used by the TypeScript suite; the rest are program defaults
+pub const MAX_LEVERAGE_BPS: u16 = 15_000;
+pub const HF_FLOOR_BPS: u16 = 24_000;
+pub const COOLDOWN_SLOTS: u64 = 30_000;
+pub const TWAP_WINDOW_SLOTS: u64 = 750;
+
+pub const LTV_BPS: u16 = 8_000;
+pub const FLASH_FEE_BPS: u16 = 9;
+/// 1,000 USDC from the single depositor
+pub const DEPOSIT: u64 = 1_000_000_000;
+/// Shares the opening deposit mints: `DEPOSIT` less the dead shares it locks
+pub const OPENING_SHARES: u64 = DEPOSIT - aegis_core::DEAD_SHARES;
+pub const VENUE_LIQUIDITY: u64 = 10_000_000_000;
+
+fn process_vault(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    aegis_vault::entry(program_id, accounts, data)
+}
+
+fn process_share_hook(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    aegis_share_hook::entry(program_id, accounts, data)
+}
+
+fn process_cpi_example(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    aegis_cpi_example::entry(program_id, accounts, data)
+}
+
+fn process_lending(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    mock_lending::entry(program_id, accounts, data)
+}
+
+/// Clock time of `slot`
+pub fn timestamp(slot: u64) -> i64 {
+    START_TS + (slot * 2 / 5) as i64
+}
+
+/// `price` (6dp) published at `slot`, stored at Pyth's usual expo −8
+pub fn oracle_price(price: i64, slot: u64) -> OraclePrice {
+    OraclePrice::from_e6(price, 0, slot, timestamp(slot)).with_expo(-8)
+}
+
+/// The opening lever: the whole deposit is idle, 70% of it goes to work
+pub fn initial_lever() -> LeveragePlan {
+    plan_leverage(0, 0, DEPOSIT, MAX_LEVERAGE_BPS, FLASH_FEE_BPS)
+        .unwrap()
+        .unwrap()
+}
+
+/// Share mint flavour a scenario is created with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shares {
+    /// `initialize_vault`: classic SPL Token mint
+    Spl,
+    /// `initialize_vault_2022`: Token-2022 mint with metadata, optionally
+    /// with `aegis-share-hook` attached and its allowlist enabled
+    Token2022 { hook: bool },
+}
+
+/// Base stablecoin flavour a scenario is created with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Usdc {
+    Spl,
+    /// Token-2022 mint (PYUSD-style) charging `fee_bps` on every transfer.
+    /// `mock-lending` only speaks SPL Token, so these scenarios have no venue.
+    Token2022 {
+        fee_bps: u16,
+    },
+}
+
+pub const SHARE_NAME: &str = "Aegis USDC Carry";
+pub const SHARE_SYMBOL: &str = "aUSDC-carry";
+pub const SHARE_URI: &str = "https://example.com/ausdc-carry.json";
+
+/// Assert that a single-instruction transaction failed with `error`
+pub fn assert_vault_error(result: Result<(), BanksClientError>, error: ErrorCode) {
+    let err = result.expect_err("transaction should have failed");
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+    );
+}
+
+pub async fn send(
+    ctx: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let mut all_signers = vec![&ctx.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&ctx.payer.pubkey()),
+        &all_signers,
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(tx).await
+}
+
+/// One vault with a funded venue and a single depositor
+pub struct Scenario {
+    pub ctx: ProgramTestContext,
+    pub slot: u64,
+    pub usdc_mint: Pubkey,
+    pub usdc_token_program: Pubkey,
+    pub pyth_feed: Pubkey,
+    pub vault: Pubkey,
+    pub vault_usdc: Pubkey,
+    pub share_mint: Pubkey,
+    pub share_token_program: Pubkey,
+    /// Set when the share mint has `aegis-share-hook` attached
+    pub share_hook: bool,
+    pub peg_observations: Pubkey,
+    pub oracle_registry: Option<Pubkey>,
+    pub oracle_feeds: Vec<Pubkey>,
+    pub reserve: Pubkey,
+    pub liquidity: Pubkey,
+    pub obligation: Pubkey,
+    /// The depositor funded with `DEPOSIT` USDC
+    pub user: Holder,
+}
+
+/// A wallet with a USDC account and a share ATA
+pub struct Holder {
+    pub wallet: Keypair,
+    pub usdc: Pubkey,
+    pub shares: Pubkey,
+}
+
+impl Holder {
+    pub fn position(&self) -> Pubkey {
+        position_of(&self.wallet.pubkey())
+    }
+}
+
+/// `owner`'s `UserPosition` address
+pub fn position_of(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"user-position", owner.as_ref()], &aegis_vault::ID).0
+}
+
+impl Scenario {
+    /// Vault initialized with a lending venue, `DEPOSIT` deposited and idle
+    pub async fn new() -> Self {
+        Self::with(Shares::Spl, Usdc::Spl).await
+    }
+
+    /// `new`, with the share mint created as `shares` and USDC as `usdc`
+    pub async fn with(shares: Shares, usdc: Usdc) -> Self {
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(false);
+        program_test.add_program("aegis_vault", aegis_vault::ID, processor!(process_vault));
+        program_test.add_program(
+            "aegis_share_hook",
+            aegis_share_hook::ID,
+            processor!(process_share_hook),
+        );
+        program_test.add_program(
+            "aegis_cpi_example",
+            aegis_cpi_example::ID,
+            processor!(process_cpi_example),
+        );
+        program_test.add_program(
+            "mock_lending",
+            mock_lending::ID,
+            processor!(process_lending),
+        );
+        let pyth_feed = Pubkey::new_unique();
+        add_oracle(
+            &mut program_test,
+            pyth_feed,
+            OracleFormat::PythLegacy,
+            &oracle_price(PEG, 0),
+        );
+
+        let ctx = program_test.start_with_context().await;
+        let vault = Pubkey::find_program_address(&[b"vault"], &aegis_vault::ID).0;
+        let usdc_mint = Keypair::new();
+        let user_usdc = Keypair::new();
+        let user = Keypair::new();
+        let (reserve, _) = Pubkey::find_program_address(
+            &[b"reserve", usdc_mint.pubkey().as_ref()],
+            &mock_lending::ID,
+        );
+        let (liquidity, _) =
+            Pubkey::find_program_address(&[b"liquidity", reserve.as_ref()], &mock_lending::ID);
+        let share_mint = Pubkey::find_program_address(&[b"share-mint"], &aegis_vault::ID).0;
+        let share_token_program = match shares {
+            Shares::Spl => spl_token::ID,
+            Shares::Token2022 { .. } => spl_token_2022::ID,
+        };
+
+        let mut scenario = Self {
+            ctx,
+            slot: 0,
+            usdc_mint: usdc_mint.pubkey(),
+            usdc_token_program: match usdc {
+                Usdc::Spl => spl_token::ID,
+                Usdc::Token2022 { .. } => spl_token_2022::ID,
+            },
+            pyth_feed,
+            vault,
+            vault_usdc: Pubkey::find_program_address(&[b"vault-usdc"], &aegis_vault::ID).0,
+            share_mint,
+            share_token_program,
+            share_hook: shares == Shares::Token2022 { hook: true },
+            peg_observations: Pubkey::find_program_address(
+                &[b"peg-observations", vault.as_ref()],
+                &aegis_vault::ID,
+            )
+            .0,
+            oracle_registry: None,
+            oracle_feeds: Vec::new(),
+            reserve,
+            liquidity,
+            obligation: Pubkey::find_program_address(
+                &[b"obligation", reserve.as_ref(), vault.as_ref()],
+                &mock_lending::ID,
+            )
+            .0,
+            user: Holder {
+                usdc: user_usdc.pubkey(),
+                shares: get_associated_token_address_with_program_id(
+                    &user.pubkey(),
+                    &share_mint,
+                    &share_token_program,
+                ),
+                wallet: user,
+            },
+        };
+        scenario.warp(START_SLOT).await;
+        scenario.set_pyth_price(PEG, START_SLOT);
+        scenario.create_usdc(&usdc_mint, &user_usdc, usdc).await;
+        scenario.initialize_vault(shares).await;
+        if usdc == Usdc::Spl {
+            scenario.init_venue().await;
+        }
+        scenario.deposit(DEPOSIT).await.unwrap();
+        scenario
+    }
+
+    /// `new`, then levered to 1.5x by the first rebalance at $1.00
+    pub async fn levered() -> Self {
+        let mut scenario = Self::new().await;
+        let slot = scenario.slot + COOLDOWN_SLOTS;
+        scenario.warp(slot).await;
+        scenario.set_pyth_price(PEG, slot);
+        scenario.rebalance().await.unwrap();
+
+        let plan = initial_lever();
+        let vault = scenario.fetch_vault().await;
+        assert_eq!(
+            (vault.total_supplied, vault.total_borrowed),
+            (plan.supplied_after, plan.borrowed_after)
+        );
+        assert!(vault.leverage_bps() >= MAX_LEVERAGE_BPS);
+        scenario
+    }
+
+    /// Warp to `slot`, with the clock's unix time following at 0.4s per slot
+    pub async fn warp(&mut self, slot: u64) {
+        self.ctx.warp_to_slot(slot).unwrap();
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.slot = slot;
+        clock.unix_timestamp = timestamp(slot);
+        self.ctx.set_sysvar(&clock);
+        self.slot = slot;
+    }
+
+    pub fn set_pyth_price(&mut self, price: i64, publish_slot: u64) {
+        let price = oracle_price(price, publish_slot);
+        set_oracle(
+            &mut self.ctx,
+            &self.pyth_feed,
+            OracleFormat::PythLegacy,
+            &price,
+        );
+    }
+
+    pub fn add_switchboard_feed(&mut self, price: i64) -> Pubkey {
+        let feed = Pubkey::new_unique();
+        self.set_switchboard_price(feed, price, self.slot);
+        feed
+    }
+
+    pub fn set_switchboard_price(&mut self, feed: Pubkey, price: i64, publish_slot: u64) {
+        let price = oracle_price(price, publish_slot);
+        set_oracle(&mut self.ctx, &feed, OracleFormat::Switchboard, &price);
+    }
+
+    async fn create_usdc(&mut self, mint: &Keypair, user_usdc: &Keypair, usdc: Usdc) {
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let payer = self.ctx.payer.pubkey();
+        let program = self.usdc_token_program;
+        let extensions = match usdc {
+            Usdc::Spl => vec![],
+            Usdc::Token2022 { .. } => vec![ExtensionType::TransferFeeConfig],
+        };
+        let mint_len =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)
+                .unwrap();
+        let token_account_len =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+                &ExtensionType::get_required_init_account_extensions(&extensions),
+            )
+            .unwrap();
+        let mut instructions = vec![system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(mint_len),
+            mint_len as u64,
+            &program,
+        )];
+        if let Usdc::Token2022 { fee_bps } = usdc {
+            instructions.push(
+                initialize_transfer_fee_config(
+                    &program,
+                    &mint.pubkey(),
+                    Some(&payer),
+                    Some(&payer),
+                    fee_bps,
+                    u64::MAX,
+                )
+                .unwrap(),
+            );
+        }
+        instructions.extend([
+            spl_token_2022::instruction::initialize_mint2(
+                &program,
+                &mint.pubkey(),
+                &payer,
+                None,
+                6,
+            )
+            .unwrap(),
+            system_instruction::create_account(
+                &payer,
+                &user_usdc.pubkey(),
+                rent.minimum_balance(token_account_len),
+                token_account_len as u64,
+                &program,
+            ),
+            spl_token_2022::instruction::initialize_account3(
+                &program,
+                &user_usdc.pubkey(),
+                &mint.pubkey(),
+                &self.user.wallet.pubkey(),
+            )
+            .unwrap(),
+            spl_token_2022::instruction::mint_to(
+                &program,
+                &mint.pubkey(),
+                &user_usdc.pubkey(),
+                &payer,
+                &[],
+                DEPOSIT,
+            )
+            .unwrap(),
+            system_instruction::transfer(&payer, &self.user.wallet.pubkey(), 10_000_000_000),
+        ]);
+        send(&mut self.ctx, &instructions, &[mint, user_usdc])
+            .await
+            .unwrap();
+    }
+
+    async fn initialize_vault(&mut self, shares: Shares) {
+        let authority = self.ctx.payer.pubkey();
+        let initialize = match shares {
+            Shares::Spl => Instruction {
+                program_id: aegis_vault::ID,
+                accounts: aegis_vault::accounts::InitializeVault {
+                    vault: self.vault,
+                    authority,
+                    usdc_mint: self.usdc_mint,
+                    vault_usdc: self.vault_usdc,
+                    share_mint: self.share_mint,
+                    pyth_usdc_feed: self.pyth_feed,
+                    system_program: system_program::ID,
+                    token_program: self.usdc_token_program,
+                    share_token_program: spl_token::ID,
+                    rent: sysvar::rent::ID,
+                }
+                .to_account_metas(None),
+                data: aegis_vault::instruction::InitializeVault {
+                    max_leverage_bps: MAX_LEVERAGE_BPS,
+                    hf_floor_bps: HF_FLOOR_BPS,
+                }
+                .data(),
+            },
+            Shares::Token2022 { hook } => Instruction {
+                program_id: aegis_vault::ID,
+                accounts: aegis_vault::accounts::InitializeVault2022 {
+                    vault: self.vault,
+                    authority,
+                    usdc_mint: self.usdc_mint,
+                    vault_usdc: self.vault_usdc,
+                    share_mint: self.share_mint,
+                    pyth_usdc_feed: self.pyth_feed,
+                    transfer_hook_program: hook.then_some(aegis_share_hook::ID),
+                    system_program: system_program::ID,
+                    token_program: self.usdc_token_program,
+                    share_token_program: spl_token_2022::ID,
+                    rent: sysvar::rent::ID,
+                }
+                .to_account_metas(None),
+                data: aegis_vault::instruction::InitializeVault2022 {
+                    max_leverage_bps: MAX_LEVERAGE_BPS,
+                    hf_floor_bps: HF_FLOOR_BPS,
+                    name: SHARE_NAME.to_string(),
+                    symbol: SHARE_SYMBOL.to_string(),
+                    uri: SHARE_URI.to_string(),
+                }
+                .data(),
+            },
+        };
+        let mut instructions = vec![
+            initialize,
+            Instruction {
+                program_id: aegis_vault::ID,
+                accounts: aegis_vault::accounts::InitObservations {
+                    vault: self.vault,
+                    peg_observations: self.peg_observations,
+                    payer: authority,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: aegis_vault::instruction::InitObservations {}.data(),
+            },
+        ];
+        if self.share_hook {
+            instructions.push(Instruction {
+                program_id: aegis_share_hook::ID,
+                accounts: aegis_share_hook::accounts::Initialize {
+                    authority,
+                    mint: self.share_mint,
+                    config: self.hook_config(),
+                    extra_account_meta_list: self.hook_extra_account_metas(),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: aegis_share_hook::instruction::Initialize {
+                    allowlist_enabled: true,
+                }
+                .data(),
+            });
+        }
+        send(&mut self.ctx, &instructions, &[]).await.unwrap();
+    }
+
+    /// Reserve seeded with `VENUE_LIQUIDITY`, and the vault's obligation pinned as its venue
+    async fn init_venue(&mut self) {
+        let payer = self.ctx.payer.pubkey();
+        let instructions = [
+            Instruction {
+                program_id: mock_lending::ID,
+                accounts: mock_lending::accounts::InitReserve {
+                    reserve: self.reserve,
+                    mint: self.usdc_mint,
+                    liquidity: self.liquidity,
+                    payer,
+                    system_program: system_program::ID,
+                    token_program: spl_token::ID,
+                    rent: sysvar::rent::ID,
+                }
+                .to_account_metas(None),
+                data: mock_lending::instruction::InitReserve {
+                    ltv_bps: LTV_BPS,
+                    flash_fee_bps: FLASH_FEE_BPS,
+                }
+                .data(),
+            },
+            Instruction {
+                program_id: mock_lending::ID,
+                accounts: mock_lending::accounts::InitObligation {
+                    reserve: self.reserve,
+                    obligation: self.obligation,
+                    owner: self.vault,
+                    payer,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: mock_lending::instruction::InitObligation {}.data(),
+            },
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &self.usdc_mint,
+                &self.liquidity,
+                &payer,
+                &[],
+                VENUE_LIQUIDITY,
+            )
+            .unwrap(),
+            Instruction {
+                program_id: aegis_vault::ID,
+                accounts: aegis_vault::accounts::SetLendingVenue {
+                    vault: self.vault,
+                    authority: payer,
+                    lending_program: mock_lending::ID,
+                    lending_reserve: self.reserve,
+                    lending_obligation: self.obligation,
+                }
+                .to_account_metas(None),
+                data: aegis_vault::instruction::SetLendingVenue {
+                    flash_fee_bps: FLASH_FEE_BPS,
+                }
+                .data(),
+            },
+        ];
+        send(&mut self.ctx, &instructions, &[]).await.unwrap();
+    }
+
+    /// A new wallet with SOL for fees, `usdc` USDC and an empty share ATA
+    pub async fn add_holder(&mut self, usdc: u64) -> Holder {
+        let wallet = Keypair::new();
+        let payer = self.ctx.payer.pubkey();
+        let holder = Holder {
+            usdc: get_associated_token_address_with_program_id(
+                &wallet.pubkey(),
+                &self.usdc_mint,
+                &self.usdc_token_program,
+            ),
+            shares: get_associated_token_address_with_program_id(
+                &wallet.pubkey(),
+                &self.share_mint,
+                &self.share_token_program,
+            ),
+            wallet,
+        };
+        let owner = holder.wallet.pubkey();
+        let instructions = [
+            system_instruction::transfer(&payer, &owner, 10_000_000_000),
+            create_associated_token_account(
+                &payer,
+                &owner,
+                &self.usdc_mint,
+                &self.usdc_token_program,
+            ),
+            create_associated_token_account(
+                &payer,
+                &owner,
+                &self.share_mint,
+                &self.share_token_program,
+            ),
+            spl_token_2022::instruction::mint_to(
+                &self.usdc_token_program,
+                &self.usdc_mint,
+                &holder.usdc,
+                &payer,
+                &[],
+                usdc,
+            )
+            .unwrap(),
+        ];
+        send(&mut self.ctx, &instructions, &[]).await.unwrap();
+        holder
+    }
+
+    fn deposit_ix(&self, holder: &Holder, amount: u64) -> Instruction {
+        Instruction {
+            program_id: aegis_vault::ID,
+            accounts: aegis_vault::accounts::Deposit {
+                vault: self.vault,
+                user_position: holder.position(),
+                user: holder.wallet.pubkey(),
+                usdc_mint: self.usdc_mint,
+                user_usdc: holder.usdc,
+                vault_usdc: self.vault_usdc,
+                share_mint: self.share_mint,
+                user_shares: holder.shares,
+                token_program: self.usdc_token_program,
+                share_token_program: self.share_token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: aegis_vault::instruction::Deposit { amount }.data(),
+        }
+    }
+
+    /// `address`, if an account exists there
+    pub async fn existing(&mut self, address: Pubkey) -> Option<Pubkey> {
+        let account = self.ctx.banks_client.get_account(address).await.unwrap();
+        account.map(|_| address)
+    }
+
+    /// Withdraw accounts paying `recipient`, passing the holder's position
+    /// only when it exists
+    pub fn withdraw_accounts(
+        &self,
+        holder: &Holder,
+        position: Option<Pubkey>,
+        recipient: Pubkey,
+    ) -> Vec<AccountMeta> {
+        aegis_vault::accounts::Withdraw {
+            vault: self.vault,
+            user_position: position,
+            user: holder.wallet.pubkey(),
+            usdc_mint: self.usdc_mint,
+            recipient_usdc: recipient,
+            vault_usdc: self.vault_usdc,
+            share_mint: self.share_mint,
+            user_shares: holder.shares,
+            token_program: self.usdc_token_program,
+            share_token_program: self.share_token_program,
+        }
+        .to_account_metas(None)
+    }
+
+    fn withdraw_ix(&self, holder: &Holder, position: Option<Pubkey>, shares: u64) -> Instruction {
+        Instruction {
+            program_id: aegis_vault::ID,
+            accounts: self.withdraw_accounts(holder, position, holder.usdc),
+            data: aegis_vault::instruction::Withdraw { shares }.data(),
+        }
+    }
+
+    pub async fn deposit(&mut self, amount: u64) -> Result<(), BanksClientError> {
+        let ix = self.deposit_ix(&self.user, amount);
+        send(&mut self.ctx, &[ix], &[&self.user.wallet]).await
+    }
+
+    pub async fn withdraw(&mut self, shares: u64) -> Result<(), BanksClientError> {
+        let position = self.existing(self.user.position()).await;
+        let ix = self.withdraw_ix(&self.user, position, shares);
+        send(&mut self.ctx, &[ix], &[&self.user.wallet]).await
+    }
+
+    pub async fn deposit_as(
+        &mut self,
+        holder: &Holder,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let ix = self.deposit_ix(holder, amount);
+        send(&mut self.ctx, &[ix], &[&holder.wallet]).await
+    }
+
+    pub async fn withdraw_as(
+        &mut self,
+        holder: &Holder,
+        shares: u64,
+    ) -> Result<(), BanksClientError> {
+        let position = self.existing(holder.position()).await;
+        let ix = self.withdraw_ix(holder, position, shares);
+        send(&mut self.ctx, &[ix], &[&holder.wallet]).await
+    }
+
+    /// `owner`'s share ATA
+    pub fn share_account_of(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(
+            owner,
+            &self.share_mint,
+            &self.share_token_program,
+        )
+    }
+
+    /// Mint `amount` USDC straight into the vault: yield that raises NAV
+    /// for every holder
+    pub async fn donate(&mut self, amount: u64) {
+        let payer = self.ctx.payer.pubkey();
+        let ix = spl_token_2022::instruction::mint_to(
+            &self.usdc_token_program,
+            &self.usdc_mint,
+            &self.vault_usdc,
+            &payer,
+            &[],
+            amount,
+        )
+        .unwrap();
+        send(&mut self.ctx, &[ix], &[]).await.unwrap();
+    }
+
+    /// Donate enough USDC to lift NAV to `nav_bps` per share (10_000 = 1.00)
+    pub async fn set_nav_bps(&mut self, nav_bps: u64) {
+        let vault = self.fetch_vault().await;
+        let idle = self.token_balance(self.vault_usdc).await;
+        let assets = vault.total_assets(idle).unwrap();
+        let target = (vault.total_shares as u128 * nav_bps as u128 / 10_000) as u64;
+        self.donate(target - assets).await;
+    }
+
+    /// `transfer_checked` of vault shares between two holders' ATAs, with
+    /// the hook's extra accounts when one is attached
+    pub async fn transfer_shares(
+        &mut self,
+        from: &Holder,
+        to: &Holder,
+        shares: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut ix = spl_token_2022::instruction::transfer_checked(
+            &self.share_token_program,
+            &from.shares,
+            &self.share_mint,
+            &to.shares,
+            &from.wallet.pubkey(),
+            &[],
+            shares,
+            6,
+        )
+        .unwrap();
+        if self.share_hook {
+            ix.accounts.extend([
+                AccountMeta::new_readonly(self.hook_extra_account_metas(), false),
+                AccountMeta::new_readonly(self.hook_config(), false),
+                AccountMeta::new_readonly(self.hook_allow_entry(&to.wallet.pubkey()), false),
+                AccountMeta::new_readonly(aegis_share_hook::ID, false),
+            ]);
+        }
+        send(&mut self.ctx, &[ix], &[&from.wallet]).await
+    }
+
+    pub fn hook_config(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"hook-config", self.share_mint.as_ref()],
+            &aegis_share_hook::ID,
+        )
+        .0
+    }
+
+    pub fn hook_extra_account_metas(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"extra-account-metas", self.share_mint.as_ref()],
+            &aegis_share_hook::ID,
+        )
+        .0
+    }
+
+    pub fn hook_allow_entry(&self, owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"allow", self.share_mint.as_ref(), owner.as_ref()],
+            &aegis_share_hook::ID,
+        )
+        .0
+    }
+
+    /// Keeper rebalance through the venue, passing the registry and its feeds once set
+    pub async fn rebalance(&mut self) -> Result<(), BanksClientError> {
+        let mut accounts = aegis_vault::accounts::Rebalance {
+            vault: self.vault,
+            keeper: self.ctx.payer.pubkey(),
+            pyth_feed: self.pyth_feed,
+            vault_usdc: self.vault_usdc,
+            peg_observations: Some(self.peg_observations),
+            oracle_registry: self.oracle_registry,
+            debt_feed: None,
+            lending_program: Some(mock_lending::ID),
+            lending_reserve: Some(self.reserve),
+            lending_obligation: Some(self.obligation),
+            lending_liquidity: Some(self.liquidity),
+            token_program: self.usdc_token_program,
+        }
+        .to_account_metas(None);
+        accounts.extend(
+            self.oracle_feeds
+                .iter()
+                .map(|feed| AccountMeta::new_readonly(*feed, false)),
+        );
+        let ix = Instruction {
+            program_id: aegis_vault::ID,
+            accounts,
+            data: aegis_vault::instruction::Rebalance {}.data(),
+        };
+        send(&mut self.ctx, &[ix], &[]).await
+    }
+
+    pub async fn record_observation(&mut self) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: aegis_vault::ID,
+            accounts: aegis_vault::accounts::RecordObservation {
+                vault: self.vault,
+                peg_observations: self.peg_observations,
+                pyth_feed: self.pyth_feed,
+            }
+            .to_account_metas(None),
+            data: aegis_vault::instruction::RecordObservation {}.data(),
+        };
+        send(&mut self.ctx, &[ix], &[]).await
+    }
+
+    pub async fn init_oracle_registry(&mut self, min_quorum: u8, max_disagreement_bps: u16) {
+        let registry = Pubkey::find_program_address(
+            &[b"oracle-registry", self.vault.as_ref()],
+            &aegis_vault::ID,
+        )
+        .0;
+        let ix = Instruction {
+            program_id: aegis_vault::ID,
+            accounts: aegis_vault::accounts::InitOracleRegistry {
+                vault: self.vault,
+                oracle_registry: registry,
+                authority: self.ctx.payer.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: aegis_vault::instruction::InitOracleRegistry {
+                min_quorum,
+                max_disagreement_bps,
+            }
+            .data(),
+        };
+        send(&mut self.ctx, &[ix], &[]).await.unwrap();
+        self.oracle_registry = Some(registry);
+    }
+
+    /// Register `feed` with the vault defaults: 150 slots / 60s, 100bps confidence
+    pub async fn add_oracle_feed(&mut self, kind: OracleKind, feed: Pubkey) {
+        let ix = Instruction {
+            program_id: aegis_vault::ID,
+            accounts: aegis_vault::accounts::AddOracleFeed {
+                vault: self.vault,
+                oracle_registry: self.oracle_registry.expect("registry not initialized"),
+                feed,
+                authority: self.ctx.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: aegis_vault::instruction::AddOracleFeed {
+                kind,
+                weight: 1,
+                max_age_slots: 150,
+                max_age_sec: 60,
+                max_conf_bps: 100,
+            }
+            .data(),
+        };
+        send(&mut self.ctx, &[ix], &[]).await.unwrap();
+        self.oracle_feeds.push(feed);
+    }
+
+    pub async fn fetch_vault(&mut self) -> Vault {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(self.vault)
+            .await
+            .unwrap()
+            .unwrap();
+        Vault::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn fetch_obligation(&mut self) -> Obligation {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(self.obligation)
+            .await
+            .unwrap()
+            .unwrap();
+        Obligation::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn fetch_position(&mut self, holder: &Holder) -> Option<UserPosition> {
+        self.fetch_position_of(&holder.wallet.pubkey()).await
+    }
+
+    pub async fn fetch_position_of(&mut self, owner: &Pubkey) -> Option<UserPosition> {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(position_of(owner))
+            .await
+            .unwrap()?;
+        Some(UserPosition::try_deserialize(&mut account.data.as_slice()).unwrap())
+    }
+
+    /// Run `position_pnl` for `holder` in simulation and decode its return data
+    pub async fn position_pnl(&mut self, holder: &Holder) -> PnlReport {
+        let ix = Instruction {
+            program_id: aegis_vault::ID,
+            accounts: aegis_vault::accounts::PositionPnl {
+                vault: self.vault,
+                user_position: holder.position(),
+                owner_shares: holder.shares,
+                vault_usdc: self.vault_usdc,
+            }
+            .to_account_metas(None),
+            data: aegis_vault::instruction::PositionPnl {}.data(),
+        };
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.ctx.payer.pubkey()),
+            &[&self.ctx.payer],
+            self.ctx.last_blockhash,
+        );
+        let simulation = self
+            .ctx
+            .banks_client
+            .simulate_transaction(tx)
+            .await
+            .unwrap();
+        simulation.result.unwrap().unwrap();
+        let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+        assert_eq!(return_data.program_id, aegis_vault::ID);
+        PnlReport::deserialize(&mut return_data.data.as_slice()).unwrap()
+    }
+
+    /// Lamports held at `address`; 0 once the account is closed
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(address).await.unwrap()
+    }
+
+    pub async fn token_balance(&mut self, token_account: Pubkey) -> u64 {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(token_account)
+            .await
+            .unwrap()
+            .unwrap();
+        // Token-2022's layout extends SPL Token's, so this reads either
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+            .unwrap()
+            .base
+            .amount
+    }
+}
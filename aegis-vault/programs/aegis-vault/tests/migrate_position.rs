@@ -0,0 +1,82 @@
+// Positions opened before cost-basis tracking are grown in place by
+// `migrate_position`, costed at the NAV of the shares they record
+mod harness;
+
+use aegis_vault::errors::ErrorCode;
+use aegis_vault::state::UserPosition;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use harness::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_program;
+
+/// Cut `holder`'s position back to the original layout
+async fn make_legacy(s: &mut Scenario, holder: &Holder) {
+    let mut account = s
+        .ctx
+        .banks_client
+        .get_account(holder.position())
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = s.ctx.banks_client.get_rent().await.unwrap();
+    account.data.truncate(UserPosition::LEGACY_LEN);
+    account.lamports = rent.minimum_balance(UserPosition::LEGACY_LEN);
+    s.ctx.set_account(&holder.position(), &account.into());
+}
+
+/// `migrate_position` for `holder`, paid for by the test payer
+async fn migrate_position(s: &mut Scenario, holder: &Holder) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: aegis_vault::ID,
+        accounts: aegis_vault::accounts::MigratePosition {
+            vault: s.vault,
+            user_position: holder.position(),
+            vault_usdc: s.vault_usdc,
+            payer: s.ctx.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: aegis_vault::instruction::MigratePosition {}.data(),
+    };
+    send(&mut s.ctx, &[ix], &[]).await
+}
+
+#[tokio::test]
+async fn legacy_position_is_costed_at_current_nav() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(2 * DEPOSIT).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    s.set_nav_bps(11_000).await;
+    make_legacy(&mut s, &alice).await;
+
+    // Unloadable until migrated
+    assert!(s.deposit_as(&alice, DEPOSIT).await.is_err());
+    migrate_position(&mut s, &alice).await.unwrap();
+
+    let position = s.fetch_position(&alice).await.unwrap();
+    assert_eq!(position.owner, alice.wallet.pubkey());
+    assert_eq!(position.shares, DEPOSIT);
+    assert_eq!(position.cost_basis, DEPOSIT + DEPOSIT / 10);
+    assert_eq!(position.total_deposited, 0);
+    assert_eq!(position.realized_pnl, 0);
+    assert_eq!(s.position_pnl(&alice).await.unrealized_pnl, 0);
+
+    // From here on it tracks like any other position
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    let position = s.fetch_position(&alice).await.unwrap();
+    assert_eq!(position.total_deposited, DEPOSIT);
+    assert_eq!(position.cost_basis, 2 * DEPOSIT + DEPOSIT / 10);
+}
+
+#[tokio::test]
+async fn current_positions_are_left_alone() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    assert_vault_error(
+        migrate_position(&mut s, &alice).await,
+        ErrorCode::AlreadyMigrated,
+    );
+}
@@ -0,0 +1,113 @@
+// Vaults created with the original layout are grown in place by
+// `migrate_vault`: the original fields keep their offsets, the appended ones
+// start at the defaults of a new vault
+mod harness;
+
+use aegis_vault::errors::ErrorCode;
+use aegis_vault::state::Vault;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use harness::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+
+/// Cut the vault back to the original layout, which the current one extends
+async fn make_legacy(s: &mut Scenario) {
+    let mut account = s
+        .ctx
+        .banks_client
+        .get_account(s.vault)
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = s.ctx.banks_client.get_rent().await.unwrap();
+    account.data.truncate(Vault::LEGACY_LEN);
+    account.lamports = rent.minimum_balance(Vault::LEGACY_LEN);
+    s.ctx.set_account(&s.vault, &account.into());
+}
+
+/// `migrate_vault` signed by `authority`, or by the vault authority (the payer)
+async fn migrate_vault(
+    s: &mut Scenario,
+    authority: Option<&Keypair>,
+) -> Result<(), BanksClientError> {
+    let ix = Instruction {
+        program_id: aegis_vault::ID,
+        accounts: aegis_vault::accounts::MigrateVault {
+            vault: s.vault,
+            authority: authority.unwrap_or(&s.ctx.payer).pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: aegis_vault::instruction::MigrateVault {}.data(),
+    };
+    let signers: Vec<&Keypair> = authority.into_iter().collect();
+    send(&mut s.ctx, &[ix], &signers).await
+}
+
+#[tokio::test]
+async fn legacy_vault_keeps_its_fields_and_gets_new_defaults() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    let created = s.fetch_vault().await;
+    make_legacy(&mut s).await;
+
+    // Unloadable until migrated
+    assert!(s.deposit_as(&alice, DEPOSIT).await.is_err());
+    migrate_vault(&mut s, None).await.unwrap();
+
+    let account = s
+        .ctx
+        .banks_client
+        .get_account(s.vault)
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = s.ctx.banks_client.get_rent().await.unwrap();
+    assert_eq!(account.data.len(), Vault::LEN);
+    assert_eq!(account.lamports, rent.minimum_balance(Vault::LEN));
+
+    let vault = s.fetch_vault().await;
+    assert_eq!(vault.authority, created.authority);
+    assert_eq!(vault.share_mint, created.share_mint);
+    assert_eq!(vault.total_shares, created.total_shares);
+    assert_eq!(vault.max_leverage_bps, created.max_leverage_bps);
+    assert_eq!(vault.cooldown_slots, created.cooldown_slots);
+    assert_eq!(vault.bump, created.bump);
+
+    assert_eq!(vault.share_token_program, spl_token::ID);
+    assert_eq!(vault.dex_program, created.dex_program);
+    assert_eq!(vault.max_slippage_bps, created.max_slippage_bps);
+    assert_eq!(vault.oracle_max_age_sec, created.oracle_max_age_sec);
+    assert_eq!(vault.stale_grace_slots, created.stale_grace_slots);
+    assert_eq!(vault.max_conf_bps, created.max_conf_bps);
+    assert_eq!(vault.peg_use_conf_edge, created.peg_use_conf_edge);
+    assert_eq!(vault.twap_window_slots, created.twap_window_slots);
+    assert_eq!(
+        vault.observation_interval_slots,
+        created.observation_interval_slots
+    );
+    assert!(!vault.has_lending_venue());
+    assert!(!vault.has_oracle_registry());
+
+    // Deposits and withdrawals work again
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    s.withdraw_as(&alice, DEPOSIT).await.unwrap();
+    assert_eq!(s.token_balance(alice.usdc).await, DEPOSIT);
+}
+
+#[tokio::test]
+async fn only_the_authority_migrates_and_only_once() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(0).await;
+    assert_vault_error(
+        migrate_vault(&mut s, None).await,
+        ErrorCode::AlreadyMigrated,
+    );
+
+    make_legacy(&mut s).await;
+    assert!(migrate_vault(&mut s, Some(&alice.wallet)).await.is_err());
+    migrate_vault(&mut s, None).await.unwrap();
+}
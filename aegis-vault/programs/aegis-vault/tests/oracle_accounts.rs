@@ -0,0 +1,103 @@
+// The vault's oracle readers against byte-accurate mock accounts
+use aegis_vault::oracles::{
+    get_pyth_confidence, get_pyth_price, get_pyth_publish_info, get_switchboard_confidence,
+    get_switchboard_price, get_switchboard_publish_info, has_sufficient_responses, is_pyth_trading,
+    PublishInfo,
+};
+use anchor_lang::prelude::AccountInfo;
+use mock_oracles::{OracleFormat, OraclePrice};
+use pyth_sdk_solana::state::{PriceAccount, PriceInfo, PriceStatus};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+const SLOT: u64 = 1_000;
+const TIME: i64 = 1_700_000_000;
+
+fn with_account_info<R>(account: &mut Account, f: impl FnOnce(&AccountInfo) -> R) -> R {
+    let key = Pubkey::new_unique();
+    let info = AccountInfo::new(
+        &key,
+        false,
+        false,
+        &mut account.lamports,
+        &mut account.data,
+        &account.owner,
+        false,
+        0,
+    );
+    f(&info)
+}
+
+#[test]
+fn pyth_reader_normalises_any_exponent() {
+    for expo in [-10, -8, -6, -3] {
+        let price = OraclePrice::from_e6(998_500, 1_200, SLOT, TIME).with_expo(expo);
+        // Digits below the account's precision are gone at −3
+        let expected = price.with_expo(-6);
+        let mut account = OracleFormat::PythLegacy.account(&price);
+        with_account_info(&mut account, |info| {
+            assert_eq!(get_pyth_price(info).unwrap(), expected.price, "expo {expo}");
+            assert_eq!(
+                get_pyth_confidence(info).unwrap(),
+                expected.conf,
+                "expo {expo}"
+            );
+            assert_eq!(
+                get_pyth_publish_info(info).unwrap(),
+                PublishInfo {
+                    publish_slot: Some(SLOT),
+                    publish_time: TIME
+                }
+            );
+        });
+    }
+}
+
+#[test]
+fn pyth_feed_outside_trading_is_flagged() {
+    let price = OraclePrice::from_e6(1_000_000, 100, SLOT, TIME);
+    let mut account = OracleFormat::PythLegacy.account(&price);
+    with_account_info(&mut account, |info| assert!(is_pyth_trading(info).unwrap()));
+
+    let offset = std::mem::offset_of!(PriceAccount, agg) + std::mem::offset_of!(PriceInfo, status);
+    for status in [
+        PriceStatus::Unknown,
+        PriceStatus::Halted,
+        PriceStatus::Auction,
+    ] {
+        account.data[offset..offset + 4].copy_from_slice(&(status as u32).to_le_bytes());
+        with_account_info(&mut account, |info| {
+            assert!(!is_pyth_trading(info).unwrap(), "{status:?}");
+        });
+    }
+}
+
+#[test]
+fn switchboard_reader_sees_the_latest_round() {
+    for expo in [-9, -6, -2] {
+        let price = OraclePrice::from_e6(1_002_000, 3_000, SLOT, TIME).with_expo(expo);
+        let expected = price.with_expo(-6);
+        let mut account = OracleFormat::Switchboard.account(&price);
+        with_account_info(&mut account, |info| {
+            assert_eq!(
+                get_switchboard_price(info).unwrap(),
+                expected.price,
+                "expo {expo}"
+            );
+            assert_eq!(
+                get_switchboard_confidence(info).unwrap(),
+                expected.conf,
+                "expo {expo}"
+            );
+            assert_eq!(
+                get_switchboard_publish_info(info).unwrap(),
+                PublishInfo {
+                    publish_slot: Some(SLOT),
+                    publish_time: TIME
+                }
+            );
+            assert!(has_sufficient_responses(info, 1).unwrap());
+            assert!(!has_sufficient_responses(info, 2).unwrap());
+        });
+    }
+}
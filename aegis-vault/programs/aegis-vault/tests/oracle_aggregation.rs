@@ -0,0 +1,124 @@
+use aegis_vault::oracles::{aggregate, quorum_status, weighted_median, FeedReading, QuorumStatus};
+use aegis_vault::state::{OracleFeed, OracleRegistry};
+use anchor_lang::prelude::Pubkey;
+
+fn reading(price: i64) -> FeedReading {
+    FeedReading {
+        price,
+        conf: 100,
+        weight: 1,
+        is_stale: false,
+        max_conf_bps: 100,
+    }
+}
+
+#[test]
+fn median_of_three_outvotes_an_outlier() {
+    let readings = [reading(1_000_100), reading(950_000), reading(999_900)];
+    let agg = aggregate(&readings, 10).unwrap();
+
+    assert_eq!(agg.price, 999_900);
+    assert_eq!(agg.quorum, 2);
+    // (1.0001 − 0.95) / 0.9999
+    assert_eq!(agg.max_disagreement_bps, 501);
+}
+
+#[test]
+fn stale_and_wide_feeds_do_not_vote() {
+    let stale = FeedReading { is_stale: true, ..reading(900_000) };
+    let wide = FeedReading { conf: 20_000, ..reading(900_000) };
+    let agg = aggregate(&[stale, wide, reading(1_000_000)], 10).unwrap();
+
+    assert_eq!(agg.price, 1_000_000);
+    assert_eq!(agg.quorum, 1);
+    assert_eq!(agg.max_disagreement_bps, 0);
+}
+
+#[test]
+fn no_valid_feed_yields_none() {
+    let stale = FeedReading { is_stale: true, ..reading(1_000_000) };
+    let zero = reading(0);
+    assert_eq!(aggregate(&[stale, zero], 10), None);
+    assert_eq!(aggregate(&[], 10), None);
+}
+
+#[test]
+fn weights_shift_the_median() {
+    let heavy = FeedReading { weight: 3, ..reading(1_001_000) };
+    let readings = [reading(999_000), reading(1_000_000), heavy];
+    assert_eq!(weighted_median(&readings).unwrap().price, 1_001_000);
+
+    // Even total weight: lower median
+    let readings = [reading(999_000), reading(1_001_000)];
+    assert_eq!(weighted_median(&readings).unwrap().price, 999_000);
+}
+
+#[test]
+fn two_feed_split_keeps_only_the_median_side() {
+    let agg = aggregate(&[reading(1_000_000), reading(990_000)], 50).unwrap();
+    assert_eq!(agg.price, 990_000);
+    assert_eq!(agg.quorum, 1);
+}
+
+#[test]
+fn quorum_is_met_despite_an_outlier() {
+    let readings = [reading(1_000_100), reading(950_000), reading(999_900)];
+    let agg = aggregate(&readings, 10);
+    assert_eq!(quorum_status(agg.as_ref(), 2, 10), QuorumStatus::Met);
+}
+
+#[test]
+fn only_a_price_split_loses_quorum() {
+    let agg = aggregate(&[reading(1_000_000), reading(998_500)], 10);
+    assert_eq!(quorum_status(agg.as_ref(), 2, 10), QuorumStatus::Lost);
+
+    // A stale third feed does not hide the split between the fresh two
+    let stale = FeedReading { is_stale: true, ..reading(1_000_000) };
+    let agg = aggregate(&[reading(1_000_000), reading(998_500), stale], 10);
+    assert_eq!(quorum_status(agg.as_ref(), 2, 10), QuorumStatus::Lost);
+}
+
+#[test]
+fn stale_feeds_short_of_quorum_are_stale_not_lost() {
+    let stale = FeedReading { is_stale: true, ..reading(1_000_000) };
+
+    // Partially stale: one fresh feed left of the two required
+    let agg = aggregate(&[reading(1_000_000), stale], 10);
+    assert_eq!(quorum_status(agg.as_ref(), 2, 10), QuorumStatus::Stale);
+
+    // All stale: nothing to aggregate
+    let agg = aggregate(&[stale, stale], 10);
+    assert_eq!(agg, None);
+    assert_eq!(quorum_status(agg.as_ref(), 2, 10), QuorumStatus::Stale);
+
+    // Fresh but unusable (too wide) counts the same as stale
+    let wide = FeedReading { conf: 20_000, ..reading(1_000_000) };
+    let agg = aggregate(&[reading(1_000_000), wide], 10);
+    assert_eq!(quorum_status(agg.as_ref(), 2, 10), QuorumStatus::Stale);
+}
+
+#[test]
+fn registry_push_and_remove_keep_order() {
+    let mut registry = OracleRegistry {
+        vault: Pubkey::default(),
+        min_quorum: 2,
+        max_disagreement_bps: 20,
+        len: 0,
+        feeds: [OracleFeed::default(); OracleRegistry::CAPACITY],
+        bump: 0,
+    };
+    let keys: Vec<Pubkey> = (0..OracleRegistry::CAPACITY).map(|_| Pubkey::new_unique()).collect();
+    for key in &keys {
+        assert!(registry.push(OracleFeed { feed: *key, weight: 1, ..Default::default() }));
+    }
+    assert!(!registry.push(OracleFeed { feed: Pubkey::new_unique(), ..Default::default() }));
+
+    assert!(registry.remove(&keys[1]));
+    assert!(!registry.remove(&keys[1]));
+    assert!(!registry.contains(&keys[1]));
+
+    let remaining: Vec<Pubkey> = registry.feeds().iter().map(|f| f.feed).collect();
+    let expected: Vec<Pubkey> = keys.iter().copied().filter(|k| *k != keys[1]).collect();
+    assert_eq!(remaining, expected);
+    assert_eq!(registry.feeds[OracleRegistry::CAPACITY - 1], OracleFeed::default());
+}
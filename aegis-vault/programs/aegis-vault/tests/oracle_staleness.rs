@@ -0,0 +1,65 @@
+use aegis_vault::oracles::{is_stale, PublishInfo, StalenessConfig, MAX_FUTURE_SKEW_SEC};
+
+const CONFIG: StalenessConfig = StalenessConfig {
+    max_age_slots: 150,
+    max_age_sec: 60,
+};
+const SLOT: u64 = 1_000_000;
+const NOW: i64 = 1_700_000_000;
+
+fn publish(slot: Option<u64>, time: i64) -> PublishInfo {
+    PublishInfo {
+        publish_slot: slot,
+        publish_time: time,
+    }
+}
+
+#[test]
+fn fresh_at_slot_boundary() {
+    assert!(!is_stale(publish(Some(SLOT - 150), NOW), SLOT, NOW, CONFIG));
+    assert!(is_stale(publish(Some(SLOT - 151), NOW), SLOT, NOW, CONFIG));
+}
+
+#[test]
+fn fresh_at_wall_clock_boundary() {
+    assert!(!is_stale(publish(Some(SLOT), NOW - 60), SLOT, NOW, CONFIG));
+    assert!(is_stale(publish(Some(SLOT), NOW - 61), SLOT, NOW, CONFIG));
+}
+
+#[test]
+fn slot_age_is_independent_of_slot_time_drift() {
+    // 100 slots but 90 seconds (slow slots): stale by wall clock only
+    assert!(is_stale(publish(Some(SLOT - 100), NOW - 90), SLOT, NOW, CONFIG));
+    // 200 slots in 40 seconds (fast slots): stale by slot only
+    assert!(is_stale(publish(Some(SLOT - 200), NOW - 40), SLOT, NOW, CONFIG));
+}
+
+#[test]
+fn missing_publish_slot_falls_back_to_wall_clock() {
+    assert!(!is_stale(publish(None, NOW - 60), SLOT, NOW, CONFIG));
+    assert!(is_stale(publish(None, NOW - 61), SLOT, NOW, CONFIG));
+}
+
+#[test]
+fn future_publish_slot_is_stale() {
+    assert!(is_stale(publish(Some(SLOT + 1), NOW), SLOT, NOW, CONFIG));
+}
+
+#[test]
+fn future_timestamp_within_skew_is_fresh() {
+    let time = NOW + MAX_FUTURE_SKEW_SEC;
+    assert!(!is_stale(publish(Some(SLOT), time), SLOT, NOW, CONFIG));
+}
+
+#[test]
+fn future_timestamp_beyond_skew_is_stale() {
+    let time = NOW + MAX_FUTURE_SKEW_SEC + 1;
+    assert!(is_stale(publish(Some(SLOT), time), SLOT, NOW, CONFIG));
+    assert!(is_stale(publish(None, i64::MAX), SLOT, NOW, CONFIG));
+}
+
+#[test]
+fn extreme_timestamps_do_not_overflow() {
+    assert!(is_stale(publish(None, i64::MIN), SLOT, NOW, CONFIG));
+    assert!(is_stale(publish(Some(0), NOW), u64::MAX, NOW, CONFIG));
+}
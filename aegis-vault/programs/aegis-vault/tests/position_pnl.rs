@@ -0,0 +1,80 @@
+// Cost basis and PnL: deposits add at cost, withdrawals realize against the
+// average entry price, and `position_pnl` values the rest at current NAV
+mod harness;
+
+use aegis_vault::instructions::PnlReport;
+use harness::*;
+
+#[tokio::test]
+async fn withdrawals_realize_gains_at_the_average_entry_price() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+
+    // 10% yield on the 2,000 USDC in the vault
+    s.set_nav_bps(11_000).await;
+    assert_eq!(
+        s.position_pnl(&alice).await,
+        PnlReport {
+            shares: DEPOSIT,
+            value: DEPOSIT + DEPOSIT / 10,
+            cost_basis: DEPOSIT,
+            avg_entry_price_e6: 1_000_000,
+            total_deposited: DEPOSIT,
+            total_withdrawn: 0,
+            realized_pnl: 0,
+            unrealized_pnl: (DEPOSIT / 10) as i64,
+        }
+    );
+
+    // Redeeming half realizes half the gain; the rest stays unrealized
+    s.withdraw_as(&alice, DEPOSIT / 2).await.unwrap();
+    let position = s.fetch_position(&alice).await.unwrap();
+    assert_eq!(position.cost_basis, DEPOSIT / 2);
+    assert_eq!(position.total_withdrawn, DEPOSIT / 2 + DEPOSIT / 20);
+    assert_eq!(position.realized_pnl, (DEPOSIT / 20) as i64);
+    let pnl = s.position_pnl(&alice).await;
+    assert_eq!(pnl.value, DEPOSIT / 2 + DEPOSIT / 20);
+    assert_eq!(pnl.unrealized_pnl, (DEPOSIT / 20) as i64);
+
+    // A full exit releases the remaining cost exactly
+    s.withdraw_as(&alice, DEPOSIT / 2).await.unwrap();
+    let pnl = s.position_pnl(&alice).await;
+    assert_eq!((pnl.shares, pnl.value, pnl.cost_basis), (0, 0, 0));
+    assert_eq!(pnl.realized_pnl, (DEPOSIT / 10) as i64);
+    assert_eq!(pnl.unrealized_pnl, 0);
+    assert_eq!(pnl.total_withdrawn, DEPOSIT + DEPOSIT / 10);
+}
+
+#[tokio::test]
+async fn transferred_shares_move_cost_out_and_enter_at_nav() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    let bob = s.add_holder(DEPOSIT).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    s.set_nav_bps(11_000).await;
+    s.transfer_shares(&alice, &bob, DEPOSIT / 2).await.unwrap();
+
+    // Bob's received shares are costed at NAV 1.10 when his deposit resyncs
+    s.deposit_as(&bob, DEPOSIT / 10 + DEPOSIT / 100)
+        .await
+        .unwrap();
+    let position = s.fetch_position(&bob).await.unwrap();
+    assert_eq!(position.shares, DEPOSIT / 2 + DEPOSIT / 10);
+    assert_eq!(
+        position.cost_basis,
+        DEPOSIT / 2 + DEPOSIT / 20 + DEPOSIT / 10 + DEPOSIT / 100
+    );
+    assert_eq!(position.total_deposited, DEPOSIT / 10 + DEPOSIT / 100);
+    let pnl = s.position_pnl(&bob).await;
+    assert_eq!(pnl.avg_entry_price_e6, 1_100_000);
+    assert_eq!(pnl.unrealized_pnl, 0);
+
+    // Alice's view drops the sent half at her cost without realizing anything
+    let pnl = s.position_pnl(&alice).await;
+    assert_eq!(pnl.shares, DEPOSIT / 2);
+    assert_eq!(pnl.cost_basis, DEPOSIT / 2);
+    assert_eq!(pnl.unrealized_pnl, (DEPOSIT / 20) as i64);
+    assert_eq!(pnl.realized_pnl, 0);
+    assert_eq!(s.fetch_position(&alice).await.unwrap().shares, DEPOSIT);
+}
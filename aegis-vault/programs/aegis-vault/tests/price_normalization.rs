@@ -0,0 +1,95 @@
+use aegis_vault::errors::ErrorCode;
+use aegis_vault::oracles::{
+    normalize_pyth_conf, normalize_pyth_price, normalize_switchboard_decimal,
+};
+use anchor_lang::error::Error;
+use num_bigint::BigInt;
+use proptest::prelude::*;
+
+/// Big-integer reference: `mantissa × 10^(exponent + 6)`, truncated toward zero.
+/// Shifts are clamped to ±64, which already takes any i128 mantissa out of i64
+/// range (or to zero), so extreme exponents stay cheap to evaluate.
+fn reference(mantissa: i128, exponent: i64) -> BigInt {
+    let shift = (exponent + 6).clamp(-64, 64);
+    let ten = BigInt::from(10);
+    if shift >= 0 {
+        BigInt::from(mantissa) * ten.pow(shift as u32)
+    } else {
+        BigInt::from(mantissa) / ten.pow((-shift) as u32)
+    }
+}
+
+fn expected_price(mantissa: i128, exponent: i64) -> Result<i64, Error> {
+    if mantissa <= 0 {
+        return Err(ErrorCode::NonPositivePrice.into());
+    }
+    let value = reference(mantissa, exponent);
+    match i64::try_from(value) {
+        Ok(v) if v > 0 => Ok(v),
+        _ => Err(ErrorCode::PriceOutOfRange.into()),
+    }
+}
+
+proptest! {
+    #[test]
+    fn pyth_price_matches_reference(price in any::<i64>(), expo in any::<i32>()) {
+        prop_assert_eq!(normalize_pyth_price(price, expo), expected_price(price as i128, expo as i64));
+    }
+
+    #[test]
+    fn pyth_price_matches_reference_near_usual_exponents(price in any::<i64>(), expo in -20i32..20) {
+        prop_assert_eq!(normalize_pyth_price(price, expo), expected_price(price as i128, expo as i64));
+    }
+
+    #[test]
+    fn pyth_conf_matches_reference(conf in any::<u64>(), expo in -45i32..45) {
+        let expected = u64::try_from(reference(conf as i128, expo as i64))
+            .map_err(|_| Error::from(ErrorCode::PriceOutOfRange));
+        prop_assert_eq!(normalize_pyth_conf(conf, expo), expected);
+    }
+
+    #[test]
+    fn switchboard_matches_reference(mantissa in any::<i128>(), scale in any::<u32>()) {
+        prop_assert_eq!(
+            normalize_switchboard_decimal(mantissa, scale),
+            expected_price(mantissa, -(scale as i64))
+        );
+    }
+
+    #[test]
+    fn switchboard_matches_reference_near_usual_scales(mantissa in any::<i128>(), scale in 0u32..45) {
+        prop_assert_eq!(
+            normalize_switchboard_decimal(mantissa, scale),
+            expected_price(mantissa, -(scale as i64))
+        );
+    }
+}
+
+#[test]
+fn usdc_examples() {
+    assert_eq!(normalize_pyth_price(99_985_000, -8).unwrap(), 999_850);
+    assert_eq!(normalize_pyth_conf(12_345, -8).unwrap(), 123);
+    assert_eq!(normalize_switchboard_decimal(9_998_500_000, 10).unwrap(), 999_850);
+    assert_eq!(normalize_switchboard_decimal(1, 0).unwrap(), 1_000_000);
+}
+
+#[test]
+fn rejects_non_positive_prices() {
+    assert_eq!(normalize_pyth_price(0, -8), Err(ErrorCode::NonPositivePrice.into()));
+    assert_eq!(normalize_pyth_price(-1, -8), Err(ErrorCode::NonPositivePrice.into()));
+    assert_eq!(
+        normalize_switchboard_decimal(i128::MIN, 18),
+        Err(ErrorCode::NonPositivePrice.into())
+    );
+}
+
+#[test]
+fn rejects_overflow_and_underflow() {
+    assert_eq!(normalize_pyth_price(i64::MAX, 0), Err(ErrorCode::PriceOutOfRange.into()));
+    assert_eq!(normalize_pyth_price(1, i32::MAX), Err(ErrorCode::PriceOutOfRange.into()));
+    assert_eq!(normalize_pyth_price(1, -7), Err(ErrorCode::PriceOutOfRange.into()));
+    assert_eq!(
+        normalize_switchboard_decimal(i128::MAX, u32::MAX),
+        Err(ErrorCode::PriceOutOfRange.into())
+    );
+}
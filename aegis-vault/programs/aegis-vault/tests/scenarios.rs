@@ -0,0 +1,232 @@
+// PRD §7 stress scenarios, run end to end against the program in program-test
+mod harness;
+
+use aegis_vault::errors::ErrorCode;
+use aegis_vault::lending::{calculate_repay_amount, plan_unwind, STALE_CONTRACT_REDUCTION_PCT};
+use aegis_vault::state::OracleKind;
+use harness::*;
+
+/// What the opening depositor redeems from an unwound vault holding `idle`:
+/// the locked dead shares' part stays behind
+fn opening_payout(idle: u64) -> u64 {
+    aegis_core::assets_for_shares(OPENING_SHARES, DEPOSIT, idle).unwrap()
+}
+
+#[tokio::test]
+async fn oracle_split_beyond_tolerance_halts_rebalancing() {
+    // §7.1: Pyth at $1.0000, Switchboard manipulated to $0.9985 — a 15bps
+    // split against a 10bps tolerance leaves only one of two feeds in quorum
+    let mut s = Scenario::levered().await;
+    let levered = s.fetch_vault().await;
+    let idle = s.token_balance(s.vault_usdc).await;
+
+    let switchboard = s.add_switchboard_feed(PEG);
+    s.init_oracle_registry(2, 10).await;
+    s.add_oracle_feed(OracleKind::Pyth, s.pyth_feed).await;
+    s.add_oracle_feed(OracleKind::Switchboard, switchboard)
+        .await;
+
+    let split = s.slot + COOLDOWN_SLOTS;
+    s.warp(split).await;
+    s.set_pyth_price(PEG, split);
+    s.set_switchboard_price(switchboard, 998_500, split);
+    assert_vault_error(s.rebalance().await, ErrorCode::VaultInPanicState);
+
+    let vault = s.fetch_vault().await;
+    assert_eq!(
+        (vault.total_supplied, vault.total_borrowed),
+        (levered.total_supplied, levered.total_borrowed)
+    );
+    assert_eq!(vault.last_rebalance_slot, levered.last_rebalance_slot);
+    assert_eq!(s.token_balance(s.vault_usdc).await, idle);
+
+    // Feeds back within tolerance: the vault resumes and deploys its idle cash
+    s.warp(split + 1).await;
+    s.set_pyth_price(PEG, split + 1);
+    s.set_switchboard_price(switchboard, 999_500, split + 1);
+    s.rebalance().await.unwrap();
+    assert!(s.fetch_vault().await.total_borrowed > levered.total_borrowed);
+}
+
+#[tokio::test]
+async fn flash_depeg_halts_then_unwinds_on_partial_recovery() {
+    // §7.2: the peg breaks to $0.98 and holds through a TWAP window
+    let mut s = Scenario::levered().await;
+    let levered = s.fetch_vault().await;
+
+    let depeg = s.slot + COOLDOWN_SLOTS;
+    s.warp(depeg).await;
+    s.set_pyth_price(980_000, depeg);
+    s.record_observation().await.unwrap();
+
+    let panic = depeg + TWAP_WINDOW_SLOTS;
+    s.warp(panic).await;
+    s.set_pyth_price(980_000, panic);
+    // The program refuses to act in PANIC: the position is frozen as is,
+    // rather than unwound into a broken market
+    assert_vault_error(s.rebalance().await, ErrorCode::VaultInPanicState);
+    let vault = s.fetch_vault().await;
+    assert_eq!(
+        (vault.total_supplied, vault.total_borrowed),
+        (levered.total_supplied, levered.total_borrowed)
+    );
+
+    // Recovery to $0.997 is still past the 25bps exit band: once the TWAP
+    // has moved there, the next rebalance unwinds the whole position
+    let recovery = panic + 10;
+    s.warp(recovery).await;
+    s.set_pyth_price(997_000, recovery);
+    s.record_observation().await.unwrap();
+
+    let exit = recovery + TWAP_WINDOW_SLOTS;
+    s.warp(exit).await;
+    s.set_pyth_price(997_000, exit);
+    s.rebalance().await.unwrap();
+
+    let vault = s.fetch_vault().await;
+    assert_eq!((vault.total_supplied, vault.total_borrowed), (0, 0));
+    assert_eq!(vault.leverage_bps(), 0);
+    let obligation = s.fetch_obligation().await;
+    assert_eq!((obligation.deposited, obligation.borrowed), (0, 0));
+
+    // The depositor leaves with their deposit less the two flash-loan fees
+    let unwind = plan_unwind(
+        levered.total_supplied,
+        levered.total_borrowed,
+        levered.total_borrowed,
+        FLASH_FEE_BPS,
+    )
+    .unwrap()
+    .unwrap();
+    let expected = DEPOSIT - initial_lever().flash_fee - unwind.flash_fee;
+    assert_eq!(s.token_balance(s.vault_usdc).await, expected);
+    s.withdraw(OPENING_SHARES).await.unwrap();
+    let payout = opening_payout(expected);
+    assert_eq!(s.token_balance(s.user.usdc).await, payout);
+    assert_eq!(s.token_balance(s.vault_usdc).await, expected - payout);
+}
+
+#[tokio::test]
+async fn congested_oracle_contracts_then_exits_past_grace() {
+    // §7.3: network congestion — the last Pyth update lands, then none for 200 slots
+    let mut s = Scenario::levered().await;
+    let levered = s.fetch_vault().await;
+
+    let last_update = s.slot + COOLDOWN_SLOTS;
+    s.warp(last_update).await;
+    s.set_pyth_price(PEG, last_update);
+
+    // 200 slots (80s) old: past both the 150-slot and 60s limits, within grace
+    let stale = last_update + 200;
+    s.warp(stale).await;
+    s.rebalance().await.unwrap();
+
+    let contract = plan_unwind(
+        levered.total_supplied,
+        levered.total_borrowed,
+        calculate_repay_amount(levered.total_borrowed, STALE_CONTRACT_REDUCTION_PCT),
+        FLASH_FEE_BPS,
+    )
+    .unwrap()
+    .unwrap();
+    let vault = s.fetch_vault().await;
+    assert_eq!(
+        (vault.total_supplied, vault.total_borrowed),
+        (contract.supplied_after, contract.borrowed_after)
+    );
+    assert!(vault.leverage_bps() < levered.leverage_bps());
+    assert_eq!(vault.stale_since_slot, stale);
+    assert_eq!(vault.last_good_price, PEG);
+    assert_eq!(
+        vault.reexpansion_unlocked_at,
+        timestamp(stale) + vault.reexpansion_delay_sec
+    );
+
+    // Still no update a cooldown later — far past the 750-slot grace: full exit
+    s.warp(stale + COOLDOWN_SLOTS).await;
+    s.rebalance().await.unwrap();
+
+    let exit = plan_unwind(
+        contract.supplied_after,
+        contract.borrowed_after,
+        contract.borrowed_after,
+        FLASH_FEE_BPS,
+    )
+    .unwrap()
+    .unwrap();
+    let vault = s.fetch_vault().await;
+    assert_eq!((vault.total_supplied, vault.total_borrowed), (0, 0));
+    assert_eq!(s.fetch_obligation().await.borrowed, 0);
+
+    let expected = DEPOSIT - initial_lever().flash_fee - contract.flash_fee - exit.flash_fee;
+    s.withdraw(OPENING_SHARES).await.unwrap();
+    assert_eq!(s.token_balance(s.user.usdc).await, opening_payout(expected));
+
+    // A fresh $1.00 price does not re-lever inside the re-expansion delay
+    let fresh = s.slot + COOLDOWN_SLOTS;
+    s.warp(fresh).await;
+    s.set_pyth_price(PEG, fresh);
+    assert!(timestamp(fresh) < vault.reexpansion_unlocked_at);
+    assert_vault_error(s.rebalance().await, ErrorCode::ReexpansionCooldown);
+}
+
+#[tokio::test]
+async fn congested_registry_contracts_then_exits_past_grace() {
+    // §7.3 with an oracle registry: staleness must not read as lost quorum
+    let mut s = Scenario::levered().await;
+    let levered = s.fetch_vault().await;
+
+    let switchboard = s.add_switchboard_feed(PEG);
+    s.init_oracle_registry(2, 10).await;
+    s.add_oracle_feed(OracleKind::Pyth, s.pyth_feed).await;
+    s.add_oracle_feed(OracleKind::Switchboard, switchboard)
+        .await;
+
+    let last_update = s.slot + COOLDOWN_SLOTS;
+    s.warp(last_update).await;
+    s.set_pyth_price(PEG, last_update);
+    s.set_switchboard_price(switchboard, PEG, last_update);
+
+    // 200 slots later Pyth is stale while Switchboard still updates: one
+    // fresh feed of the two required is short of quorum, but not split
+    let stale = last_update + 200;
+    s.warp(stale).await;
+    s.set_switchboard_price(switchboard, PEG, stale);
+    s.rebalance().await.unwrap();
+
+    let contract = plan_unwind(
+        levered.total_supplied,
+        levered.total_borrowed,
+        calculate_repay_amount(levered.total_borrowed, STALE_CONTRACT_REDUCTION_PCT),
+        FLASH_FEE_BPS,
+    )
+    .unwrap()
+    .unwrap();
+    let vault = s.fetch_vault().await;
+    assert_eq!(
+        (vault.total_supplied, vault.total_borrowed),
+        (contract.supplied_after, contract.borrowed_after)
+    );
+    assert_eq!(vault.stale_since_slot, stale);
+    assert_eq!(vault.last_good_price, PEG);
+
+    // Both feeds silent past the 750-slot grace: full exit
+    s.warp(stale + COOLDOWN_SLOTS).await;
+    s.rebalance().await.unwrap();
+
+    let exit = plan_unwind(
+        contract.supplied_after,
+        contract.borrowed_after,
+        contract.borrowed_after,
+        FLASH_FEE_BPS,
+    )
+    .unwrap()
+    .unwrap();
+    let vault = s.fetch_vault().await;
+    assert_eq!((vault.total_supplied, vault.total_borrowed), (0, 0));
+    assert_eq!(s.fetch_obligation().await.borrowed, 0);
+
+    let expected = DEPOSIT - initial_lever().flash_fee - contract.flash_fee - exit.flash_fee;
+    s.withdraw(OPENING_SHARES).await.unwrap();
+    assert_eq!(s.token_balance(s.user.usdc).await, opening_payout(expected));
+}
@@ -0,0 +1,149 @@
+// Token-2022 share mints: metadata for wallets, the same deposit/withdraw
+// flow as classic SPL shares, and the opt-in allowlist transfer hook
+mod harness;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHook;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
+use harness::*;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_program;
+
+/// Allow (`true`) or revoke (`false`) `holder` on the hook's allowlist
+async fn set_allowed(s: &mut Scenario, holder: &Holder, allowed: bool) {
+    let owner = holder.wallet.pubkey();
+    let ix = match allowed {
+        true => Instruction {
+            program_id: aegis_share_hook::ID,
+            accounts: aegis_share_hook::accounts::Allow {
+                authority: s.ctx.payer.pubkey(),
+                config: s.hook_config(),
+                entry: s.hook_allow_entry(&owner),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: aegis_share_hook::instruction::Allow { owner }.data(),
+        },
+        false => Instruction {
+            program_id: aegis_share_hook::ID,
+            accounts: aegis_share_hook::accounts::Revoke {
+                authority: s.ctx.payer.pubkey(),
+                config: s.hook_config(),
+                entry: s.hook_allow_entry(&owner),
+            }
+            .to_account_metas(None),
+            data: aegis_share_hook::instruction::Revoke { owner }.data(),
+        },
+    };
+    send(&mut s.ctx, &[ix], &[]).await.unwrap();
+}
+
+async fn set_allowlist_enabled(s: &mut Scenario, enabled: bool) {
+    let ix = Instruction {
+        program_id: aegis_share_hook::ID,
+        accounts: aegis_share_hook::accounts::UpdateConfig {
+            authority: s.ctx.payer.pubkey(),
+            config: s.hook_config(),
+        }
+        .to_account_metas(None),
+        data: aegis_share_hook::instruction::SetAllowlistEnabled { enabled }.data(),
+    };
+    send(&mut s.ctx, &[ix], &[]).await.unwrap();
+}
+
+/// Raw share mint account, for reading Token-2022 extensions
+async fn share_mint_data(s: &mut Scenario) -> Vec<u8> {
+    let account = s
+        .ctx
+        .banks_client
+        .get_account(s.share_mint)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.owner, s.share_token_program);
+    account.data
+}
+
+#[tokio::test]
+async fn token_2022_shares_carry_metadata_and_redeem() {
+    let mut s = Scenario::with(Shares::Token2022 { hook: false }, Usdc::Spl).await;
+    assert_eq!(
+        s.fetch_vault().await.share_token_program,
+        spl_token_2022::ID
+    );
+
+    let data = share_mint_data(&mut s).await;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data).unwrap();
+    assert_eq!(mint.base.decimals, 6);
+    assert_eq!(mint.base.mint_authority, Some(s.vault).into());
+    assert_eq!(mint.base.supply, DEPOSIT);
+    let pointer = mint.get_extension::<MetadataPointer>().unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(pointer.metadata_address),
+        Some(s.share_mint)
+    );
+    assert!(mint.get_extension::<TransferHook>().is_err());
+    let metadata = mint.get_variable_len_extension::<TokenMetadata>().unwrap();
+    assert_eq!(
+        (
+            metadata.name.as_str(),
+            metadata.symbol.as_str(),
+            metadata.uri.as_str()
+        ),
+        (SHARE_NAME, SHARE_SYMBOL, SHARE_URI)
+    );
+
+    // Without a hook, shares move and redeem exactly like classic SPL shares
+    let alice = s.add_holder(DEPOSIT).await;
+    let bob = s.add_holder(0).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    s.transfer_shares(&alice, &bob, DEPOSIT / 2).await.unwrap();
+    s.withdraw_as(&bob, DEPOSIT / 2).await.unwrap();
+    s.withdraw_as(&alice, DEPOSIT / 2).await.unwrap();
+    assert_eq!(s.token_balance(bob.usdc).await, DEPOSIT / 2);
+    assert_eq!(s.token_balance(alice.usdc).await, DEPOSIT / 2);
+    assert_eq!(s.fetch_vault().await.total_shares, DEPOSIT);
+}
+
+#[tokio::test]
+async fn allowlist_hook_gates_share_transfers_but_not_redemption() {
+    let mut s = Scenario::with(Shares::Token2022 { hook: true }, Usdc::Spl).await;
+    let data = share_mint_data(&mut s).await;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data).unwrap();
+    let hook = mint.get_extension::<TransferHook>().unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(hook.program_id),
+        Some(aegis_share_hook::ID)
+    );
+
+    // Bob can deposit — minting bypasses the hook — but can't receive transfers
+    let alice = s.add_holder(DEPOSIT).await;
+    let bob = s.add_holder(DEPOSIT).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    s.deposit_as(&bob, DEPOSIT / 2).await.unwrap();
+    assert!(s.transfer_shares(&alice, &bob, DEPOSIT / 4).await.is_err());
+
+    set_allowed(&mut s, &bob, true).await;
+    s.transfer_shares(&alice, &bob, DEPOSIT / 4).await.unwrap();
+    assert_eq!(s.token_balance(bob.shares).await, DEPOSIT / 2 + DEPOSIT / 4);
+
+    // Revoking blocks further transfers; shares already held still redeem
+    set_allowed(&mut s, &bob, false).await;
+    assert!(s.transfer_shares(&alice, &bob, DEPOSIT / 4).await.is_err());
+    s.withdraw_as(&bob, DEPOSIT / 2 + DEPOSIT / 4)
+        .await
+        .unwrap();
+    assert_eq!(s.token_balance(bob.usdc).await, DEPOSIT + DEPOSIT / 4);
+
+    // With the allowlist switched off the hook lets everything through
+    set_allowlist_enabled(&mut s, false).await;
+    s.transfer_shares(&alice, &bob, DEPOSIT / 4).await.unwrap();
+    assert_eq!(s.token_balance(bob.shares).await, DEPOSIT / 4);
+}
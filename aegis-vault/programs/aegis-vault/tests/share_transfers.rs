@@ -0,0 +1,58 @@
+// Shares are plain SPL tokens: whoever holds them can redeem, and positions
+// only mirror the holder's balance as of their last deposit or withdrawal
+mod harness;
+
+use aegis_vault::errors::ErrorCode;
+use harness::*;
+
+#[tokio::test]
+async fn transferred_shares_are_redeemable_by_the_recipient() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    let bob = s.add_holder(0).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    s.transfer_shares(&alice, &bob, DEPOSIT / 2).await.unwrap();
+
+    // Bob never deposited and has no position, but his shares redeem at NAV
+    assert!(s.fetch_position(&bob).await.is_none());
+    s.withdraw_as(&bob, DEPOSIT / 2).await.unwrap();
+    assert_eq!(s.token_balance(bob.usdc).await, DEPOSIT / 2);
+    assert_eq!(s.token_balance(bob.shares).await, 0);
+
+    // Alice's position still reads her full deposit; only her balance counts
+    assert_eq!(s.fetch_position(&alice).await.unwrap().shares, DEPOSIT);
+    let result = s.withdraw_as(&alice, DEPOSIT).await;
+    assert_vault_error(result, ErrorCode::InsufficientEquity);
+    s.withdraw_as(&alice, DEPOSIT / 2).await.unwrap();
+    assert_eq!(s.token_balance(alice.usdc).await, DEPOSIT / 2);
+    assert_eq!(s.fetch_position(&alice).await.unwrap().shares, 0);
+
+    // Only the original depositor's shares remain
+    let vault = s.fetch_vault().await;
+    assert_eq!(vault.total_shares, DEPOSIT);
+    assert_eq!(s.token_balance(s.vault_usdc).await, DEPOSIT);
+}
+
+#[tokio::test]
+async fn deposit_resyncs_the_position_with_the_share_balance() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    let bob = s.add_holder(DEPOSIT).await;
+    s.deposit_as(&alice, DEPOSIT / 2).await.unwrap();
+    s.transfer_shares(&alice, &bob, DEPOSIT / 4).await.unwrap();
+
+    // Bob's first deposit opens a position that counts the shares he received
+    s.deposit_as(&bob, DEPOSIT / 2).await.unwrap();
+    let bob_balance = s.token_balance(bob.shares).await;
+    assert_eq!(bob_balance, DEPOSIT / 4 + DEPOSIT / 2);
+    assert_eq!(s.fetch_position(&bob).await.unwrap().shares, bob_balance);
+
+    // Alice's next deposit drops the shares she sent away
+    s.deposit_as(&alice, DEPOSIT / 2).await.unwrap();
+    let alice_balance = s.token_balance(alice.shares).await;
+    assert_eq!(alice_balance, DEPOSIT / 4 + DEPOSIT / 2);
+    assert_eq!(
+        s.fetch_position(&alice).await.unwrap().shares,
+        alice_balance
+    );
+}
@@ -0,0 +1,70 @@
+use aegis_vault::logic::{determine_vault_state, VaultState};
+use aegis_vault::state::Vault;
+use anchor_lang::AccountDeserialize;
+
+const HEALTHY_HF: u16 = 31_200;
+
+fn vault() -> Vault {
+    let data = vec![0u8; Vault::LEN];
+    let mut vault = Vault::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+    vault.hf_floor_bps = 24_000;
+    vault.peg_warn_bps = 10;
+    vault.peg_exit_bps = 25;
+    vault.peg_panic_bps = 50;
+    vault.stale_grace_slots = 750;
+    vault
+}
+
+fn state(peg_bps: u16, stale_for: Option<u64>, quorum_lost: bool) -> VaultState {
+    determine_vault_state(peg_bps, peg_bps, stale_for, quorum_lost, false, HEALTHY_HF, &vault())
+}
+
+#[test]
+fn stale_within_grace_contracts_instead_of_panicking() {
+    assert_eq!(state(0, Some(0), false), VaultState::Contract);
+    assert_eq!(state(0, Some(750), false), VaultState::Contract);
+}
+
+#[test]
+fn prolonged_staleness_escalates_to_exit() {
+    assert_eq!(state(0, Some(751), false), VaultState::Exit);
+}
+
+#[test]
+fn lost_quorum_panics() {
+    assert_eq!(state(0, None, true), VaultState::Panic);
+    assert_eq!(state(0, Some(0), true), VaultState::Panic);
+}
+
+#[test]
+fn last_good_peg_still_escalates_while_stale() {
+    assert_eq!(state(30, Some(10), false), VaultState::Exit);
+    assert_eq!(state(60, Some(10), false), VaultState::Panic);
+}
+
+#[test]
+fn fresh_healthy_oracle_loops() {
+    assert_eq!(state(0, None, false), VaultState::Loop);
+}
+
+#[test]
+fn track_oracle_keeps_last_good_price_and_times_stale_period() {
+    let mut vault = vault();
+
+    assert_eq!(vault.track_oracle(999_900, false, true, 100), None);
+    assert_eq!((vault.last_good_price, vault.last_good_slot), (999_900, 100));
+
+    // Unreliable fresh readings do not replace the last good price
+    assert_eq!(vault.track_oracle(990_000, false, false, 150), None);
+    assert_eq!(vault.last_good_price, 999_900);
+
+    // Stale period starts at the first stale rebalance and is kept until fresh
+    assert_eq!(vault.track_oracle(0, true, false, 200), Some(0));
+    assert_eq!(vault.track_oracle(0, true, false, 500), Some(300));
+    assert_eq!(vault.stale_since_slot, 200);
+    assert_eq!(vault.last_good_price, 999_900);
+
+    assert_eq!(vault.track_oracle(1_000_000, false, true, 600), None);
+    assert_eq!(vault.stale_since_slot, 0);
+    assert_eq!((vault.last_good_price, vault.last_good_slot), (1_000_000, 600));
+}
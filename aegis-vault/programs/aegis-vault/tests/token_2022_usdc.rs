@@ -0,0 +1,96 @@
+// Token-2022 base stablecoins (PYUSD-style): deposits and withdrawals go
+// through `transfer_checked`, and a transfer fee is charged to the depositor
+// rather than diluting existing holders; swaps refuse them outright
+mod harness;
+
+use aegis_core::DEAD_SHARES;
+use aegis_vault::errors::ErrorCode;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use harness::*;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+const FEE_BPS: u16 = 10;
+
+/// Token-2022's fee on `amount`: rounded up, uncapped in these scenarios
+fn transfer_fee(amount: u64) -> u64 {
+    (amount as u128 * FEE_BPS as u128).div_ceil(10_000) as u64
+}
+
+#[tokio::test]
+async fn deposit_credits_the_amount_received_after_the_fee() {
+    let mut s = Scenario::with(Shares::Spl, Usdc::Token2022 { fee_bps: FEE_BPS }).await;
+
+    // The opening deposit mints 1:1 against what reached the vault, less the dead shares
+    let received = DEPOSIT - transfer_fee(DEPOSIT);
+    assert_eq!(s.token_balance(s.vault_usdc).await, received);
+    assert_eq!(s.token_balance(s.user.shares).await, received - DEAD_SHARES);
+    assert_eq!(s.fetch_vault().await.total_shares, received);
+
+    // A second depositor pays their own fee: the share price is unchanged
+    let alice = s.add_holder(DEPOSIT).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    assert_eq!(s.token_balance(alice.shares).await, received);
+    assert_eq!(s.token_balance(s.vault_usdc).await, 2 * received);
+    let vault = s.fetch_vault().await;
+    assert_eq!(vault.total_shares, 2 * received);
+    assert_eq!(vault.total_assets(2 * received), Some(vault.total_shares));
+}
+
+#[tokio::test]
+async fn withdrawal_fee_comes_out_of_the_redeemed_amount() {
+    let mut s = Scenario::with(Shares::Spl, Usdc::Token2022 { fee_bps: FEE_BPS }).await;
+    let shares = s.token_balance(s.user.shares).await;
+
+    // The vault pays out the full NAV of the shares; the fee is withheld on arrival
+    s.withdraw(shares).await.unwrap();
+    assert_eq!(s.token_balance(s.vault_usdc).await, DEAD_SHARES);
+    assert_eq!(
+        s.token_balance(s.user.usdc).await,
+        shares - transfer_fee(shares)
+    );
+    assert_eq!(s.fetch_vault().await.total_shares, DEAD_SHARES);
+}
+
+#[tokio::test]
+async fn token_2022_usdc_works_with_token_2022_shares() {
+    let mut s = Scenario::with(
+        Shares::Token2022 { hook: false },
+        Usdc::Token2022 { fee_bps: 0 },
+    )
+    .await;
+    assert_eq!(s.token_balance(s.user.shares).await, OPENING_SHARES);
+    s.withdraw(OPENING_SHARES).await.unwrap();
+    assert_eq!(s.token_balance(s.user.usdc).await, OPENING_SHARES);
+}
+
+#[tokio::test]
+async fn swap_route_refuses_a_token_2022_input_mint() {
+    let mut s = Scenario::with(
+        Shares::Token2022 { hook: false },
+        Usdc::Token2022 { fee_bps: 0 },
+    )
+    .await;
+    // Any executable stands in for the DEX; the mint is rejected first
+    let ix = Instruction {
+        program_id: aegis_vault::ID,
+        accounts: aegis_vault::accounts::SetSwapRoute {
+            vault: s.vault,
+            authority: s.ctx.payer.pubkey(),
+            dex_program: spl_token::ID,
+            input_mint: s.share_mint,
+            input_feed: Pubkey::new_unique(),
+        }
+        .to_account_metas(None),
+        data: aegis_vault::instruction::SetSwapRoute {
+            max_slippage_bps: 50,
+        }
+        .data(),
+    };
+    assert_vault_error(
+        send(&mut s.ctx, &[ix], &[]).await,
+        ErrorCode::UnsupportedTokenProgram,
+    );
+}
@@ -0,0 +1,140 @@
+use aegis_vault::logic::{max_peg_deviation_bps, twap, Twap};
+use aegis_vault::state::{Observation, PegObservations};
+
+fn buffer() -> PegObservations {
+    PegObservations {
+        vault: Default::default(),
+        head: 0,
+        len: 0,
+        samples: [Observation::default(); PegObservations::CAPACITY],
+        bump: 0,
+    }
+}
+
+fn sample(slot: u64, price: i64, conf: u64) -> Observation {
+    Observation { slot, price, conf }
+}
+
+#[test]
+fn ring_keeps_the_newest_samples_once_full() {
+    let mut observations = buffer();
+    let extra = 5;
+    for i in 0..(PegObservations::CAPACITY + extra) as u64 {
+        observations.push(sample(i * 10, 1_000_000 + i as i64, 0));
+    }
+
+    assert_eq!(observations.len as usize, PegObservations::CAPACITY);
+    let slots: Vec<u64> = observations.iter().map(|o| o.slot).collect();
+    let expected: Vec<u64> = (extra..PegObservations::CAPACITY + extra)
+        .map(|i| i as u64 * 10)
+        .collect();
+    assert_eq!(slots, expected);
+    assert_eq!(
+        observations.latest().unwrap().slot,
+        (PegObservations::CAPACITY + extra - 1) as u64 * 10
+    );
+}
+
+#[test]
+fn samples_are_gated_by_the_interval() {
+    let mut observations = buffer();
+    assert!(observations.is_due(0, 25));
+
+    observations.push(sample(100, 1_000_000, 0));
+    assert!(!observations.is_due(124, 25));
+    assert!(observations.is_due(125, 25));
+    assert!(observations.is_due(100, 0));
+}
+
+#[test]
+fn twap_weights_samples_by_time_in_effect() {
+    let mut observations = buffer();
+    observations.push(sample(0, 1_000_000, 300));
+    observations.push(sample(100, 990_000, 600));
+
+    // Window [0, 200]: 100 slots at each price
+    assert_eq!(
+        twap(&observations, 200, 200),
+        Some(Twap {
+            price: 995_000,
+            conf: 450
+        })
+    );
+}
+
+#[test]
+fn twap_truncates_the_oldest_sample_at_the_window_start() {
+    let mut observations = buffer();
+    observations.push(sample(0, 1_000_000, 0));
+    observations.push(sample(100, 990_000, 0));
+
+    // Window [50, 200]: 50 slots at $1.00, 100 at $0.99
+    assert_eq!(twap(&observations, 200, 150).unwrap().price, 993_333);
+    // Window [150, 200]: only the latest sample is in effect
+    assert_eq!(twap(&observations, 200, 50).unwrap().price, 990_000);
+}
+
+#[test]
+fn twap_falls_back_to_the_latest_sample_with_no_elapsed_time() {
+    let mut observations = buffer();
+    assert_eq!(twap(&observations, 100, 750), None);
+
+    observations.push(sample(100, 998_000, 200));
+    assert_eq!(
+        twap(&observations, 100, 750),
+        Some(Twap {
+            price: 998_000,
+            conf: 200
+        })
+    );
+}
+
+#[test]
+fn twap_only_sees_samples_still_in_the_ring() {
+    let mut observations = buffer();
+    // A depeg that has been overwritten no longer counts, even in a wide window
+    observations.push(sample(0, 900_000, 0));
+    for i in 1..=PegObservations::CAPACITY as u64 {
+        observations.push(sample(i * 10, 1_000_000, 0));
+    }
+
+    let now = PegObservations::CAPACITY as u64 * 10 + 10;
+    assert_eq!(twap(&observations, now, now).unwrap().price, 1_000_000);
+    assert_eq!(
+        max_peg_deviation_bps(&observations, now, now, false),
+        Some(0)
+    );
+}
+
+#[test]
+fn window_max_ignores_samples_replaced_before_the_window() {
+    let mut observations = buffer();
+    observations.push(sample(0, 990_000, 0));
+    observations.push(sample(10, 1_000_000, 0));
+
+    // The $0.99 sample was in effect for slots [0, 10]
+    assert_eq!(
+        max_peg_deviation_bps(&observations, 500, 100, false),
+        Some(0)
+    );
+    assert_eq!(
+        max_peg_deviation_bps(&observations, 500, 495, false),
+        Some(100)
+    );
+}
+
+#[test]
+fn window_max_includes_the_latest_sample_and_conf_edge() {
+    let mut observations = buffer();
+    assert_eq!(max_peg_deviation_bps(&observations, 0, 750, false), None);
+
+    observations.push(sample(100, 999_000, 1_500));
+    assert_eq!(
+        max_peg_deviation_bps(&observations, 100, 750, false),
+        Some(10)
+    );
+    assert_eq!(
+        max_peg_deviation_bps(&observations, 100, 750, true),
+        Some(25)
+    );
+}
@@ -0,0 +1,103 @@
+// Withdrawals paying a recipient other than the signer, and withdrawals sized
+// by the USDC amount instead of the shares burned
+mod harness;
+
+use aegis_vault::errors::ErrorCode;
+use anchor_lang::InstructionData;
+use harness::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+/// Redeem `holder`'s shares with the USDC paid to `recipient`
+async fn withdraw_to(
+    s: &mut Scenario,
+    holder: &Holder,
+    recipient: Pubkey,
+    shares: u64,
+) -> Result<(), BanksClientError> {
+    let position = s.existing(holder.position()).await;
+    let ix = Instruction {
+        program_id: aegis_vault::ID,
+        accounts: s.withdraw_accounts(holder, position, recipient),
+        data: aegis_vault::instruction::Withdraw { shares }.data(),
+    };
+    send(&mut s.ctx, &[ix], &[&holder.wallet]).await
+}
+
+/// Withdraw exactly `usdc_amount` to `holder`, burning at most `max_shares_in`
+async fn withdraw_assets_as(
+    s: &mut Scenario,
+    holder: &Holder,
+    usdc_amount: u64,
+    max_shares_in: u64,
+) -> Result<(), BanksClientError> {
+    let position = s.existing(holder.position()).await;
+    let ix = Instruction {
+        program_id: aegis_vault::ID,
+        accounts: s.withdraw_accounts(holder, position, holder.usdc),
+        data: aegis_vault::instruction::WithdrawAssets {
+            usdc_amount,
+            max_shares_in,
+        }
+        .data(),
+    };
+    send(&mut s.ctx, &[ix], &[&holder.wallet]).await
+}
+
+#[tokio::test]
+async fn withdrawal_pays_any_usdc_account() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    let exchange = s.add_holder(0).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+
+    withdraw_to(&mut s, &alice, exchange.usdc, DEPOSIT / 2)
+        .await
+        .unwrap();
+    assert_eq!(s.token_balance(exchange.usdc).await, DEPOSIT / 2);
+    assert_eq!(s.token_balance(alice.usdc).await, 0);
+    assert_eq!(s.token_balance(alice.shares).await, DEPOSIT / 2);
+    // The position is the share owner's, not the recipient's
+    assert_eq!(
+        s.fetch_position(&alice).await.unwrap().total_withdrawn,
+        DEPOSIT / 2
+    );
+    assert!(s.fetch_position(&exchange).await.is_none());
+
+    // The recipient must hold the vault's USDC mint
+    let shares_account = exchange.shares;
+    assert!(withdraw_to(&mut s, &alice, shares_account, DEPOSIT / 2)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn withdraw_assets_burns_shares_rounded_up() {
+    let mut s = Scenario::new().await;
+    let alice = s.add_holder(DEPOSIT).await;
+    s.deposit_as(&alice, DEPOSIT).await.unwrap();
+    s.set_nav_bps(11_000).await;
+
+    withdraw_assets_as(&mut s, &alice, 110_000_000, 100_000_000)
+        .await
+        .unwrap();
+    assert_eq!(s.token_balance(alice.usdc).await, 110_000_000);
+    assert_eq!(s.token_balance(alice.shares).await, DEPOSIT - 100_000_000);
+
+    // 100 USDC at 2,090 / 1,900 is 90.9090909 shares: one more than the floor
+    let result = withdraw_assets_as(&mut s, &alice, 100_000_000, 90_909_090).await;
+    assert_vault_error(result, ErrorCode::MaxSharesExceeded);
+    withdraw_assets_as(&mut s, &alice, 100_000_000, 90_909_091)
+        .await
+        .unwrap();
+    assert_eq!(s.token_balance(alice.usdc).await, 210_000_000);
+    assert_eq!(
+        s.token_balance(alice.shares).await,
+        DEPOSIT - 100_000_000 - 90_909_091
+    );
+
+    // Asking for more than the shares held fails
+    let result = withdraw_assets_as(&mut s, &alice, DEPOSIT, u64::MAX).await;
+    assert_vault_error(result, ErrorCode::InsufficientEquity);
+}
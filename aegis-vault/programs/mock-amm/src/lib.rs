@@ -0,0 +1,218 @@
+// Local-only AMM used to exercise the aegis-vault DEX adapters.
+// Exposes the Orca Whirlpool `swap` instruction (same discriminator, argument
+// and account order) backed by a fixed, settable exchange rate, so tests can
+// point the Whirlpool adapter at it and simulate bad fills with `set_price`.
+// NOT for deployment.
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("F7dAznuyKybZkbiLDcckCTtnKLJmXYk9h6tnC6fGjS3N");
+
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    pub fn init_pool(ctx: Context<InitPool>, price_a_to_b_e6: u64) -> Result<()> {
+        require!(price_a_to_b_e6 > 0, MockAmmError::InvalidPrice);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.token_mint_a = ctx.accounts.token_mint_a.key();
+        pool.token_mint_b = ctx.accounts.token_mint_b.key();
+        pool.token_vault_a = ctx.accounts.token_vault_a.key();
+        pool.token_vault_b = ctx.accounts.token_vault_b.key();
+        pool.price_a_to_b_e6 = price_a_to_b_e6;
+        pool.bump = ctx.bumps.pool;
+        Ok(())
+    }
+
+    pub fn set_price(ctx: Context<SetPrice>, price_a_to_b_e6: u64) -> Result<()> {
+        require!(price_a_to_b_e6 > 0, MockAmmError::InvalidPrice);
+        ctx.accounts.pool.price_a_to_b_e6 = price_a_to_b_e6;
+        Ok(())
+    }
+
+    /// Whirlpool-compatible swap. Only exact-input swaps are supported;
+    /// `sqrt_price_limit` and the tick array / oracle accounts are ignored.
+    pub fn swap(
+        ctx: Context<Swap>,
+        amount: u64,
+        other_amount_threshold: u64,
+        _sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+    ) -> Result<()> {
+        require!(amount_specified_is_input, MockAmmError::ExactOutUnsupported);
+
+        let pool = &ctx.accounts.whirlpool;
+        let amount_out = if a_to_b {
+            amount as u128 * pool.price_a_to_b_e6 as u128 / 1_000_000
+        } else {
+            amount as u128 * 1_000_000 / pool.price_a_to_b_e6 as u128
+        };
+        let amount_out = u64::try_from(amount_out).map_err(|_| error!(MockAmmError::MathOverflow))?;
+        require!(
+            amount_out >= other_amount_threshold,
+            MockAmmError::AmountOutBelowMinimum
+        );
+
+        let (owner_in, vault_in, vault_out, owner_out) = if a_to_b {
+            (
+                &ctx.accounts.token_owner_account_a,
+                &ctx.accounts.token_vault_a,
+                &ctx.accounts.token_vault_b,
+                &ctx.accounts.token_owner_account_b,
+            )
+        } else {
+            (
+                &ctx.accounts.token_owner_account_b,
+                &ctx.accounts.token_vault_b,
+                &ctx.accounts.token_vault_a,
+                &ctx.accounts.token_owner_account_a,
+            )
+        };
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: owner_in.to_account_info(),
+                    to: vault_in.to_account_info(),
+                    authority: ctx.accounts.token_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let seeds = &[
+            b"pool".as_ref(),
+            pool.token_mint_a.as_ref(),
+            pool.token_mint_b.as_ref(),
+            &[pool.bump],
+        ];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault_out.to_account_info(),
+                    to: owner_out.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount_out,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitPool<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Pool::LEN,
+        seeds = [b"pool", token_mint_a.key().as_ref(), token_mint_b.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub token_mint_a: Account<'info, Mint>,
+    pub token_mint_b: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault-a", pool.key().as_ref()],
+        bump,
+        token::mint = token_mint_a,
+        token::authority = pool
+    )]
+    pub token_vault_a: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault-b", pool.key().as_ref()],
+        bump,
+        token::mint = token_mint_b,
+        token::authority = pool
+    )]
+    pub token_vault_b: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Account order matches `whirlpool::instructions::Swap`.
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    pub token_program: Program<'info, Token>,
+
+    pub token_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub whirlpool: Account<'info, Pool>,
+
+    #[account(mut, token::mint = whirlpool.token_mint_a)]
+    pub token_owner_account_a: Account<'info, TokenAccount>,
+
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = whirlpool.token_mint_b)]
+    pub token_owner_account_b: Account<'info, TokenAccount>,
+
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Account<'info, TokenAccount>,
+
+    /// CHECK: Ignored by the mock.
+    #[account(mut)]
+    pub tick_array_0: UncheckedAccount<'info>,
+
+    /// CHECK: Ignored by the mock.
+    #[account(mut)]
+    pub tick_array_1: UncheckedAccount<'info>,
+
+    /// CHECK: Ignored by the mock.
+    #[account(mut)]
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    /// CHECK: Ignored by the mock.
+    pub oracle: UncheckedAccount<'info>,
+}
+
+#[account]
+pub struct Pool {
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+    /// Units of token B paid per unit of token A, scaled by 1e6
+    pub price_a_to_b_e6: u64,
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1;
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("Price must be positive")]
+    InvalidPrice,
+    #[msg("Only exact-input swaps are supported")]
+    ExactOutUnsupported,
+    #[msg("Amount out below minimum threshold")]
+    AmountOutBelowMinimum,
+    #[msg("Math overflow")]
+    MathOverflow,
+}
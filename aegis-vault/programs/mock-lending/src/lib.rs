@@ -0,0 +1,256 @@
+// Local-only lending venue used to exercise the aegis-vault leverage engine.
+// Implements just enough of a Kamino-style reserve/obligation model to
+// supply, borrow, repay, withdraw and take single-reserve flash loans.
+// NOT for deployment: flash loans are not enforced to be repaid in the same
+// transaction (the real venue uses instruction introspection for that).
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("xqkhKYFbifkR964mNHsWCLrFbvpjRqyTqhp1vVLWKqJ");
+
+#[program]
+pub mod mock_lending {
+    use super::*;
+
+    pub fn init_reserve(ctx: Context<InitReserve>, ltv_bps: u16, flash_fee_bps: u16) -> Result<()> {
+        require!(ltv_bps < 10_000, MockLendingError::InvalidParameter);
+
+        let reserve = &mut ctx.accounts.reserve;
+        reserve.mint = ctx.accounts.mint.key();
+        reserve.liquidity = ctx.accounts.liquidity.key();
+        reserve.ltv_bps = ltv_bps;
+        reserve.flash_fee_bps = flash_fee_bps;
+        reserve.flash_outstanding = 0;
+        reserve.bump = ctx.bumps.reserve;
+        Ok(())
+    }
+
+    pub fn init_obligation(ctx: Context<InitObligation>) -> Result<()> {
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.reserve = ctx.accounts.reserve.key();
+        obligation.owner = ctx.accounts.owner.key();
+        obligation.deposited = 0;
+        obligation.borrowed = 0;
+        obligation.bump = ctx.bumps.obligation;
+        Ok(())
+    }
+
+    pub fn flash_borrow(ctx: Context<VenueOp>, amount: u64) -> Result<()> {
+        require!(
+            ctx.accounts.reserve.flash_outstanding == 0,
+            MockLendingError::FlashLoanActive
+        );
+        transfer_out(&ctx, amount)?;
+        ctx.accounts.reserve.flash_outstanding = amount;
+        Ok(())
+    }
+
+    pub fn flash_repay(ctx: Context<VenueOp>, amount: u64) -> Result<()> {
+        let reserve = &ctx.accounts.reserve;
+        require!(reserve.flash_outstanding > 0, MockLendingError::NoFlashLoan);
+        require!(
+            amount >= reserve.flash_outstanding + reserve.flash_fee(reserve.flash_outstanding),
+            MockLendingError::FlashRepayTooSmall
+        );
+        transfer_in(&ctx, amount)?;
+        ctx.accounts.reserve.flash_outstanding = 0;
+        Ok(())
+    }
+
+    pub fn supply(ctx: Context<VenueOp>, amount: u64) -> Result<()> {
+        transfer_in(&ctx, amount)?;
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.deposited = obligation
+            .deposited
+            .checked_add(amount)
+            .ok_or(MockLendingError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn borrow(ctx: Context<VenueOp>, amount: u64) -> Result<()> {
+        let ltv_bps = ctx.accounts.reserve.ltv_bps;
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.borrowed = obligation
+            .borrowed
+            .checked_add(amount)
+            .ok_or(MockLendingError::MathOverflow)?;
+        require!(obligation.is_healthy(ltv_bps), MockLendingError::Unhealthy);
+        transfer_out(&ctx, amount)
+    }
+
+    pub fn repay(ctx: Context<VenueOp>, amount: u64) -> Result<()> {
+        let amount = amount.min(ctx.accounts.obligation.borrowed);
+        transfer_in(&ctx, amount)?;
+        ctx.accounts.obligation.borrowed -= amount;
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<VenueOp>, amount: u64) -> Result<()> {
+        let ltv_bps = ctx.accounts.reserve.ltv_bps;
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.deposited = obligation
+            .deposited
+            .checked_sub(amount)
+            .ok_or(MockLendingError::InsufficientCollateral)?;
+        require!(obligation.is_healthy(ltv_bps), MockLendingError::Unhealthy);
+        transfer_out(&ctx, amount)
+    }
+}
+
+fn transfer_in(ctx: &Context<VenueOp>, amount: u64) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token.to_account_info(),
+                to: ctx.accounts.liquidity.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+fn transfer_out(ctx: &Context<VenueOp>, amount: u64) -> Result<()> {
+    let reserve = &ctx.accounts.reserve;
+    let seeds = &[b"reserve".as_ref(), reserve.mint.as_ref(), &[reserve.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.liquidity.to_account_info(),
+                to: ctx.accounts.user_token.to_account_info(),
+                authority: reserve.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
+
+#[derive(Accounts)]
+pub struct InitReserve<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Reserve::LEN,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump
+    )]
+    pub reserve: Account<'info, Reserve>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"liquidity", reserve.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = reserve
+    )]
+    pub liquidity: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitObligation<'info> {
+    pub reserve: Account<'info, Reserve>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Obligation::LEN,
+        seeds = [b"obligation", reserve.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub obligation: Account<'info, Obligation>,
+
+    /// CHECK: Obligation owner; may be a PDA of the calling program.
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Shared account layout for every venue operation, so that callers can use
+/// one account list for flash loans and obligation updates alike.
+#[derive(Accounts)]
+pub struct VenueOp<'info> {
+    #[account(mut)]
+    pub reserve: Account<'info, Reserve>,
+
+    #[account(mut, has_one = reserve, has_one = owner)]
+    pub obligation: Account<'info, Obligation>,
+
+    #[account(mut, address = reserve.liquidity)]
+    pub liquidity: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = reserve.mint)]
+    pub user_token: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+pub struct Reserve {
+    pub mint: Pubkey,
+    pub liquidity: Pubkey,
+    pub ltv_bps: u16,
+    pub flash_fee_bps: u16,
+    pub flash_outstanding: u64,
+    pub bump: u8,
+}
+
+impl Reserve {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 2 + 8 + 1;
+
+    pub fn flash_fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.flash_fee_bps as u128).div_ceil(10_000) as u64
+    }
+}
+
+#[account]
+pub struct Obligation {
+    pub reserve: Pubkey,
+    pub owner: Pubkey,
+    pub deposited: u64,
+    pub borrowed: u64,
+    pub bump: u8,
+}
+
+impl Obligation {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+
+    pub fn is_healthy(&self, ltv_bps: u16) -> bool {
+        self.borrowed as u128 * 10_000 <= self.deposited as u128 * ltv_bps as u128
+    }
+}
+
+#[error_code]
+pub enum MockLendingError {
+    #[msg("Invalid reserve parameter")]
+    InvalidParameter,
+    #[msg("A flash loan is already outstanding")]
+    FlashLoanActive,
+    #[msg("No flash loan outstanding")]
+    NoFlashLoan,
+    #[msg("Flash repayment does not cover principal plus fee")]
+    FlashRepayTooSmall,
+    #[msg("Obligation would exceed the reserve LTV")]
+    Unhealthy,
+    #[msg("Insufficient collateral")]
+    InsufficientCollateral,
+    #[msg("Math overflow")]
+    MathOverflow,
+}
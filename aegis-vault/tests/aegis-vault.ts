@@ -2,7 +2,14 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { AegisVault } from "../target/types/aegis_vault";
 import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo } from "@solana/spl-token";
+import {
+    TOKEN_PROGRAM_ID,
+    createMint,
+    createAccount,
+    getAccount,
+    getAssociatedTokenAddressSync,
+    mintTo,
+} from "@solana/spl-token";
 import { assert } from "chai";
 
 describe("aegis-vault", () => {
@@ -12,6 +19,9 @@ describe("aegis-vault", () => {
 
     const program = anchor.workspace.AegisVault as Program<AegisVault>;
 
+    // Mirrors aegis_core::DEAD_SHARES, locked by the first deposit
+    const DEAD_SHARES = 1_000;
+
     let usdcMint: PublicKey;
     let shareMint: PublicKey;
     let vaultPda: PublicKey;
@@ -20,6 +30,7 @@ describe("aegis-vault", () => {
     let userUsdcAccount: PublicKey;
     let userSharesAccount: PublicKey;
     let userPositionPda: PublicKey;
+    let pegObservationsPda: PublicKey;
 
     // Mock oracle accounts (in production, use real Pyth/Switchboard feeds)
     let pythFeed: Keypair;
@@ -63,11 +74,22 @@ describe("aegis-vault", () => {
             program.programId
         );
 
+        [shareMint] = PublicKey.findProgramAddressSync(
+            [Buffer.from("share-mint")],
+            program.programId
+        );
+        userSharesAccount = getAssociatedTokenAddressSync(shareMint, provider.wallet.publicKey);
+
         [userPositionPda] = PublicKey.findProgramAddressSync(
             [Buffer.from("user-position"), provider.wallet.publicKey.toBuffer()],
             program.programId
         );
 
+        [pegObservationsPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("peg-observations"), vaultPda.toBuffer()],
+            program.programId
+        );
+
         // Create mock oracle accounts
         pythFeed = Keypair.generate();
         switchboardFeed = Keypair.generate();
@@ -89,6 +111,7 @@ describe("aegis-vault", () => {
                     switchboardUsdcFeed: switchboardFeed.publicKey,
                     systemProgram: SystemProgram.programId,
                     tokenProgram: TOKEN_PROGRAM_ID,
+                    shareTokenProgram: TOKEN_PROGRAM_ID,
                 })
                 .rpc();
 
@@ -100,13 +123,39 @@ describe("aegis-vault", () => {
             assert.equal(vault.totalBorrowed.toNumber(), 0);
             assert.equal(vault.totalShares.toNumber(), 0);
             assert.equal(vault.oracleStaleSlots.toNumber(), 150);
+            assert.equal(vault.oracleMaxAgeSec.toNumber(), 60);
+            assert.equal(vault.staleGraceSlots.toNumber(), 750);
+            assert.equal(vault.lastGoodPrice.toNumber(), 0);
             assert.equal(vault.pegWarnBps, 10);
             assert.equal(vault.pegExitBps, 25);
             assert.equal(vault.pegPanicBps, 50);
+            assert.equal(vault.maxConfBps, 100);
+            assert.equal(vault.pegUseConfEdge, true);
             assert.equal(vault.cooldownSlots.toNumber(), 30000);
             assert.equal(vault.reexpansionDelaySec.toNumber(), 30000);
         });
 
+        it("Initializes the peg observation buffer", async () => {
+            await program.methods
+                .initObservations()
+                .accounts({
+                    vault: vaultPda,
+                    pegObservations: pegObservationsPda,
+                    payer: provider.wallet.publicKey,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            const observations = await program.account.pegObservations.fetch(pegObservationsPda);
+            assert.ok(observations.vault.equals(vaultPda));
+            assert.equal(observations.len, 0);
+            assert.equal(observations.samples.length, 32);
+
+            const vault = await program.account.vault.fetch(vaultPda);
+            assert.equal(vault.twapWindowSlots.toNumber(), 750);
+            assert.equal(vault.observationIntervalSlots.toNumber(), 25);
+        });
+
         it("Rejects invalid leverage parameters", async () => {
             try {
                 await program.methods
@@ -120,6 +169,7 @@ describe("aegis-vault", () => {
                         switchboardUsdcFeed: switchboardFeed.publicKey,
                         systemProgram: SystemProgram.programId,
                         tokenProgram: TOKEN_PROGRAM_ID,
+                        shareTokenProgram: TOKEN_PROGRAM_ID,
                     })
                     .rpc();
 
@@ -130,8 +180,59 @@ describe("aegis-vault", () => {
         });
     });
 
+    describe("Oracle config", () => {
+        it("Updates confidence settings", async () => {
+            await program.methods
+                .setOracleConfig(40, false, new anchor.BN(300), new anchor.BN(120), new anchor.BN(1500))
+                .accounts({
+                    vault: vaultPda,
+                    authority: provider.wallet.publicKey,
+                })
+                .rpc();
+
+            const vault = await program.account.vault.fetch(vaultPda);
+            assert.equal(vault.maxConfBps, 40);
+            assert.equal(vault.pegUseConfEdge, false);
+            assert.equal(vault.oracleStaleSlots.toNumber(), 300);
+            assert.equal(vault.oracleMaxAgeSec.toNumber(), 120);
+            assert.equal(vault.staleGraceSlots.toNumber(), 1500);
+
+            // Restore defaults for later tests
+            await program.methods
+                .setOracleConfig(100, true, new anchor.BN(150), new anchor.BN(60), new anchor.BN(750))
+                .accounts({ vault: vaultPda, authority: provider.wallet.publicKey })
+                .rpc();
+        });
+
+        it("Rejects a zero staleness window", async () => {
+            try {
+                await program.methods
+                    .setOracleConfig(100, true, new anchor.BN(0), new anchor.BN(60), new anchor.BN(750))
+                    .accounts({ vault: vaultPda, authority: provider.wallet.publicKey })
+                    .rpc();
+
+                assert.fail("Should have rejected zero max age");
+            } catch (err) {
+                assert.include(err.message, "InvalidStaleness");
+            }
+        });
+
+        it("Rejects a max confidence above 1%", async () => {
+            try {
+                await program.methods
+                    .setOracleConfig(101, true, new anchor.BN(150), new anchor.BN(60), new anchor.BN(750))
+                    .accounts({ vault: vaultPda, authority: provider.wallet.publicKey })
+                    .rpc();
+
+                assert.fail("Should have rejected max confidence above 1%");
+            } catch (err) {
+                assert.include(err.message, "InvalidConfidence");
+            }
+        });
+    });
+
     describe("Deposits", () => {
-        it("Handles first deposit (1:1 shares)", async () => {
+        it("Handles first deposit (1:1 shares, less the dead shares)", async () => {
             const depositAmount = new anchor.BN(1000_000_000); // 1000 USDC
 
             const tx = await program.methods
@@ -140,26 +241,34 @@ describe("aegis-vault", () => {
                     vault: vaultPda,
                     userPosition: userPositionPda,
                     user: provider.wallet.publicKey,
+                    usdcMint: usdcMint,
                     userUsdc: userUsdcAccount,
                     vaultUsdc: vaultUsdcPda,
                     systemProgram: SystemProgram.programId,
                     tokenProgram: TOKEN_PROGRAM_ID,
+                    shareTokenProgram: TOKEN_PROGRAM_ID,
                 })
                 .rpc();
 
             const userPosition = await program.account.userPosition.fetch(userPositionPda);
             const vault = await program.account.vault.fetch(vaultPda);
 
-            assert.equal(userPosition.shares.toNumber(), depositAmount.toNumber());
+            assert.equal(userPosition.shares.toNumber(), depositAmount.toNumber() - DEAD_SHARES);
+            assert.equal(userPosition.costBasis.toNumber(), depositAmount.toNumber());
+            assert.equal(userPosition.totalDeposited.toNumber(), depositAmount.toNumber());
             assert.equal(vault.totalShares.toNumber(), depositAmount.toNumber());
         });
 
         it("Handles subsequent deposits with correct share calculation", async () => {
             const depositAmount = new anchor.BN(500_000_000); // 500 USDC
 
+            // NAV: venue equity plus the USDC idle in the vault
             const vaultBefore = await program.account.vault.fetch(vaultPda);
-            const equity = vaultBefore.totalSupplied.sub(vaultBefore.totalBorrowed);
-            const expectedShares = depositAmount.mul(vaultBefore.totalShares).div(equity);
+            const idle = (await getAccount(provider.connection, vaultUsdcPda)).amount;
+            const totalAssets = vaultBefore.totalSupplied
+                .sub(vaultBefore.totalBorrowed)
+                .add(new anchor.BN(idle.toString()));
+            const expectedShares = depositAmount.mul(vaultBefore.totalShares).div(totalAssets);
 
             await program.methods
                 .deposit(depositAmount)
@@ -167,16 +276,18 @@ describe("aegis-vault", () => {
                     vault: vaultPda,
                     userPosition: userPositionPda,
                     user: provider.wallet.publicKey,
+                    usdcMint: usdcMint,
                     userUsdc: userUsdcAccount,
                     vaultUsdc: vaultUsdcPda,
                     systemProgram: SystemProgram.programId,
                     tokenProgram: TOKEN_PROGRAM_ID,
+                    shareTokenProgram: TOKEN_PROGRAM_ID,
                 })
                 .rpc();
 
             const userPosition = await program.account.userPosition.fetch(userPositionPda);
 
-            // Shares should be proportional to equity
+            // Shares should be proportional to NAV
             assert.approximately(
                 userPosition.shares.toNumber(),
                 expectedShares.toNumber(),
@@ -185,6 +296,60 @@ describe("aegis-vault", () => {
         });
     });
 
+    describe("Deposits for a beneficiary", () => {
+        it("Mints shares to the beneficiary and credits the referrer", async () => {
+            const beneficiary = Keypair.generate().publicKey;
+            const referrer = Keypair.generate().publicKey;
+            const depositAmount = new anchor.BN(100_000_000); // 100 USDC
+
+            const [referrerStatsPda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("referrer-stats"), vaultPda.toBuffer(), referrer.toBuffer()],
+                program.programId
+            );
+            const [beneficiaryPositionPda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("user-position"), beneficiary.toBuffer()],
+                program.programId
+            );
+
+            await program.methods
+                .initReferrer(referrer)
+                .accounts({
+                    vault: vaultPda,
+                    referrerStats: referrerStatsPda,
+                    payer: provider.wallet.publicKey,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            await program.methods
+                .depositFor(depositAmount)
+                .accounts({
+                    vault: vaultPda,
+                    userPosition: beneficiaryPositionPda,
+                    payer: provider.wallet.publicKey,
+                    beneficiary: beneficiary,
+                    usdcMint: usdcMint,
+                    payerUsdc: userUsdcAccount,
+                    vaultUsdc: vaultUsdcPda,
+                    beneficiaryShares: getAssociatedTokenAddressSync(shareMint, beneficiary, true),
+                    referrerStats: referrerStatsPda,
+                    systemProgram: SystemProgram.programId,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    shareTokenProgram: TOKEN_PROGRAM_ID,
+                })
+                .rpc();
+
+            const position = await program.account.userPosition.fetch(beneficiaryPositionPda);
+            const stats = await program.account.referrerStats.fetch(referrerStatsPda);
+
+            assert.ok(position.owner.equals(beneficiary));
+            assert.equal(position.totalDeposited.toNumber(), depositAmount.toNumber());
+            assert.equal(stats.depositCount.toNumber(), 1);
+            assert.equal(stats.totalDeposited.toNumber(), depositAmount.toNumber());
+            assert.equal(stats.totalShares.toNumber(), position.shares.toNumber());
+        });
+    });
+
     describe("Withdrawals", () => {
         it("Handles withdrawal with share burning", async () => {
             const userPositionBefore = await program.account.userPosition.fetch(userPositionPda);
@@ -196,9 +361,11 @@ describe("aegis-vault", () => {
                     vault: vaultPda,
                     userPosition: userPositionPda,
                     user: provider.wallet.publicKey,
-                    userUsdc: userUsdcAccount,
+                    usdcMint: usdcMint,
+                    recipientUsdc: userUsdcAccount,
                     vaultUsdc: vaultUsdcPda,
                     tokenProgram: TOKEN_PROGRAM_ID,
+                    shareTokenProgram: TOKEN_PROGRAM_ID,
                 })
                 .rpc();
 
@@ -210,6 +377,56 @@ describe("aegis-vault", () => {
             );
         });
 
+        it("Withdraws an exact USDC amount to another account", async () => {
+            const recipient = await createAccount(
+                provider.connection,
+                provider.wallet.payer,
+                usdcMint,
+                Keypair.generate().publicKey
+            );
+            const usdcAmount = new anchor.BN(10_000_000); // 10 USDC
+
+            await program.methods
+                .withdrawAssets(usdcAmount, new anchor.BN(11_000_000))
+                .accounts({
+                    vault: vaultPda,
+                    userPosition: userPositionPda,
+                    user: provider.wallet.publicKey,
+                    usdcMint: usdcMint,
+                    recipientUsdc: recipient,
+                    vaultUsdc: vaultUsdcPda,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    shareTokenProgram: TOKEN_PROGRAM_ID,
+                })
+                .rpc();
+
+            const balance = await provider.connection.getTokenAccountBalance(recipient);
+            assert.equal(balance.value.amount, usdcAmount.toString());
+        });
+
+        it("Reports position PnL at current NAV", async () => {
+            const userPosition = await program.account.userPosition.fetch(userPositionPda);
+
+            const pnl = await program.methods
+                .positionPnl()
+                .accounts({
+                    vault: vaultPda,
+                    userPosition: userPositionPda,
+                    ownerShares: userSharesAccount,
+                    vaultUsdc: vaultUsdcPda,
+                })
+                .view();
+
+            assert.equal(pnl.shares.toNumber(), userPosition.shares.toNumber());
+            assert.equal(pnl.costBasis.toNumber(), userPosition.costBasis.toNumber());
+            assert.equal(pnl.totalDeposited.toNumber(), 1500_000_000);
+            assert.equal(
+                pnl.unrealizedPnl.toNumber(),
+                pnl.value.toNumber() - pnl.costBasis.toNumber()
+            );
+            assert.equal(pnl.realizedPnl.toNumber(), userPosition.realizedPnl.toNumber());
+        });
+
         it("Rejects withdrawal exceeding shares", async () => {
             const userPosition = await program.account.userPosition.fetch(userPositionPda);
             const excessiveShares = userPosition.shares.add(new anchor.BN(1));
@@ -221,9 +438,11 @@ describe("aegis-vault", () => {
                         vault: vaultPda,
                         userPosition: userPositionPda,
                         user: provider.wallet.publicKey,
-                        userUsdc: userUsdcAccount,
+                        usdcMint: usdcMint,
+                        recipientUsdc: userUsdcAccount,
                         vaultUsdc: vaultUsdcPda,
                         tokenProgram: TOKEN_PROGRAM_ID,
+                        shareTokenProgram: TOKEN_PROGRAM_ID,
                     })
                     .rpc();
 
@@ -232,6 +451,26 @@ describe("aegis-vault", () => {
                 assert.include(err.message, "InsufficientEquity");
             }
         });
+
+        it("Refuses to close a position that still holds shares", async () => {
+            try {
+                await program.methods
+                    .closePosition(true)
+                    .accounts({
+                        vault: vaultPda,
+                        userPosition: userPositionPda,
+                        user: provider.wallet.publicKey,
+                        shareMint: shareMint,
+                        userShares: userSharesAccount,
+                        shareTokenProgram: TOKEN_PROGRAM_ID,
+                    })
+                    .rpc();
+
+                assert.fail("Should have rejected closing a non-empty position");
+            } catch (err) {
+                assert.include(err.message, "PositionNotEmpty");
+            }
+        });
     });
 
     describe("State Machine", () => {
@@ -252,11 +491,6 @@ describe("aegis-vault", () => {
             // Mock: peg at $0.974 (26 bps deviation)
             // Expected: EXIT state
         });
-
-        it("Should determine PANIC state on oracle failure", async () => {
-            // Mock: stale oracle (>150 slots)
-            // Expected: PANIC state
-        });
     });
 
     describe("Rebalancing", () => {
@@ -269,6 +503,15 @@ describe("aegis-vault", () => {
                     keeper: provider.wallet.publicKey,
                     pythFeed: pythFeed.publicKey,
                     switchboardFeed: switchboardFeed.publicKey,
+                    vaultUsdc: vaultUsdcPda,
+                    pegObservations: pegObservationsPda,
+                    oracleRegistry: null,
+                    debtFeed: null,
+                    lendingProgram: null,
+                    lendingReserve: null,
+                    lendingObligation: null,
+                    lendingLiquidity: null,
+                    tokenProgram: TOKEN_PROGRAM_ID,
                 })
                 .rpc();
 
@@ -281,6 +524,15 @@ describe("aegis-vault", () => {
                         keeper: provider.wallet.publicKey,
                         pythFeed: pythFeed.publicKey,
                         switchboardFeed: switchboardFeed.publicKey,
+                        vaultUsdc: vaultUsdcPda,
+                        pegObservations: pegObservationsPda,
+                        oracleRegistry: null,
+                        debtFeed: null,
+                        lendingProgram: null,
+                        lendingReserve: null,
+                        lendingObligation: null,
+                        lendingLiquidity: null,
+                        tokenProgram: TOKEN_PROGRAM_ID,
                     })
                     .rpc();
 
@@ -290,4 +542,177 @@ describe("aegis-vault", () => {
             }
         });
     });
+
+    describe("Oracle registry", () => {
+        let registryPda: PublicKey;
+
+        before(() => {
+            [registryPda] = PublicKey.findProgramAddressSync(
+                [Buffer.from("oracle-registry"), vaultPda.toBuffer()],
+                program.programId
+            );
+        });
+
+        it("Initializes the registry without pinning it yet", async () => {
+            await program.methods
+                .initOracleRegistry(2, 20)
+                .accounts({
+                    vault: vaultPda,
+                    oracleRegistry: registryPda,
+                    authority: provider.wallet.publicKey,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+
+            const registry = await program.account.oracleRegistry.fetch(registryPda);
+            assert.equal(registry.minQuorum, 2);
+            assert.equal(registry.maxDisagreementBps, 20);
+            assert.equal(registry.len, 0);
+
+            const vault = await program.account.vault.fetch(vaultPda);
+            assert.ok(vault.oracleRegistry.equals(PublicKey.default));
+        });
+
+        it("Registers Pyth and Switchboard feeds, pinning the registry at quorum", async () => {
+            await program.methods
+                .addOracleFeed({ pyth: {} }, 2, new anchor.BN(150), new anchor.BN(60), 100)
+                .accounts({
+                    vault: vaultPda,
+                    oracleRegistry: registryPda,
+                    feed: pythFeed.publicKey,
+                    authority: provider.wallet.publicKey,
+                })
+                .rpc();
+            let vault = await program.account.vault.fetch(vaultPda);
+            assert.ok(vault.oracleRegistry.equals(PublicKey.default));
+
+            await program.methods
+                .addOracleFeed({ switchboard: {} }, 1, new anchor.BN(300), new anchor.BN(120), 50)
+                .accounts({
+                    vault: vaultPda,
+                    oracleRegistry: registryPda,
+                    feed: switchboardFeed.publicKey,
+                    authority: provider.wallet.publicKey,
+                })
+                .rpc();
+
+            const registry = await program.account.oracleRegistry.fetch(registryPda);
+            assert.equal(registry.len, 2);
+            assert.ok(registry.feeds[0].feed.equals(pythFeed.publicKey));
+            assert.deepEqual(registry.feeds[1].kind, { switchboard: {} });
+            assert.equal(registry.feeds[1].maxConfBps, 50);
+
+            vault = await program.account.vault.fetch(vaultPda);
+            assert.ok(vault.oracleRegistry.equals(registryPda));
+        });
+
+        it("Rejects a duplicate feed", async () => {
+            try {
+                await program.methods
+                    .addOracleFeed({ pyth: {} }, 1, new anchor.BN(150), new anchor.BN(60), 100)
+                    .accounts({
+                        vault: vaultPda,
+                        oracleRegistry: registryPda,
+                        feed: pythFeed.publicKey,
+                        authority: provider.wallet.publicKey,
+                    })
+                    .rpc();
+
+                assert.fail("Should have rejected duplicate feed");
+            } catch (err) {
+                assert.include(err.message, "InvalidOracleFeed");
+            }
+        });
+
+        it("Refuses to remove a feed below quorum", async () => {
+            try {
+                await program.methods
+                    .removeOracleFeed(pythFeed.publicKey)
+                    .accounts({
+                        vault: vaultPda,
+                        oracleRegistry: registryPda,
+                        authority: provider.wallet.publicKey,
+                    })
+                    .rpc();
+
+                assert.fail("Should have kept the registry at quorum");
+            } catch (err) {
+                assert.include(err.message, "InvalidQuorum");
+            }
+
+            const registry = await program.account.oracleRegistry.fetch(registryPda);
+            assert.equal(registry.len, 2);
+        });
+
+        it("Requires the registry once one is configured", async () => {
+            try {
+                await program.methods
+                    .rebalance()
+                    .accounts({
+                        vault: vaultPda,
+                        keeper: provider.wallet.publicKey,
+                        pythFeed: pythFeed.publicKey,
+                        vaultUsdc: vaultUsdcPda,
+                        pegObservations: pegObservationsPda,
+                        oracleRegistry: null,
+                        debtFeed: null,
+                        lendingProgram: null,
+                        lendingReserve: null,
+                        lendingObligation: null,
+                        lendingLiquidity: null,
+                        tokenProgram: TOKEN_PROGRAM_ID,
+                    })
+                    .rpc();
+
+                assert.fail("Should have required the oracle registry");
+            } catch (err) {
+                assert.include(err.message, "OracleFeedMismatch");
+            }
+        });
+    });
+
+    describe("Debt asset", () => {
+        it("Configures a borrowed asset distinct from the collateral", async () => {
+            const usdtMint = await createMint(
+                provider.connection,
+                provider.wallet.payer,
+                provider.wallet.publicKey,
+                null,
+                6
+            );
+            const usdtFeed = Keypair.generate();
+
+            await program.methods
+                .setDebtAsset()
+                .accounts({
+                    vault: vaultPda,
+                    authority: provider.wallet.publicKey,
+                    debtMint: usdtMint,
+                    debtFeed: usdtFeed.publicKey,
+                })
+                .rpc();
+
+            const vault = await program.account.vault.fetch(vaultPda);
+            assert.ok(vault.debtMint.equals(usdtMint));
+            assert.ok(vault.debtFeed.equals(usdtFeed.publicKey));
+        });
+
+        it("Rejects the collateral mint as the debt asset", async () => {
+            try {
+                await program.methods
+                    .setDebtAsset()
+                    .accounts({
+                        vault: vaultPda,
+                        authority: provider.wallet.publicKey,
+                        debtMint: usdcMint,
+                        debtFeed: Keypair.generate().publicKey,
+                    })
+                    .rpc();
+
+                assert.fail("Should have rejected the collateral mint");
+            } catch (err) {
+                assert.include(err.message, "DebtAssetMismatch");
+            }
+        });
+    });
 });
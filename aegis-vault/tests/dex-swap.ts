@@ -0,0 +1,158 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { AegisVault } from "../target/types/aegis_vault";
+import { MockAmm } from "../target/types/mock_amm";
+import { PublicKey, Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("dex-swap", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const vaultProgram = anchor.workspace.AegisVault as Program<AegisVault>;
+    const amm = anchor.workspace.MockAmm as Program<MockAmm>;
+
+    let mintA: PublicKey; // e.g. USDT
+    let mintB: PublicKey; // e.g. USDC
+    let poolPda: PublicKey;
+    let vaultA: PublicKey;
+    let vaultB: PublicKey;
+    let ownerA: PublicKey;
+    let ownerB: PublicKey;
+
+    // Tick arrays and oracle are ignored by the mock but must be writable slots
+    const tickArrays = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const whirlpoolOracle = Keypair.generate();
+
+    const swapAccounts = () => ({
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenAuthority: provider.wallet.publicKey,
+        whirlpool: poolPda,
+        tokenOwnerAccountA: ownerA,
+        tokenVaultA: vaultA,
+        tokenOwnerAccountB: ownerB,
+        tokenVaultB: vaultB,
+        tickArray0: tickArrays[0].publicKey,
+        tickArray1: tickArrays[1].publicKey,
+        tickArray2: tickArrays[2].publicKey,
+        oracle: whirlpoolOracle.publicKey,
+    });
+
+    before(async () => {
+        const payer = provider.wallet.payer;
+        const me = provider.wallet.publicKey;
+        mintA = await createMint(provider.connection, payer, me, null, 6);
+        mintB = await createMint(provider.connection, payer, me, null, 6);
+
+        [poolPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+            amm.programId
+        );
+        [vaultA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("vault-a"), poolPda.toBuffer()],
+            amm.programId
+        );
+        [vaultB] = PublicKey.findProgramAddressSync(
+            [Buffer.from("vault-b"), poolPda.toBuffer()],
+            amm.programId
+        );
+
+        await amm.methods
+            .initPool(new anchor.BN(999_000)) // 1 A = 0.999 B
+            .accounts({
+                pool: poolPda,
+                tokenMintA: mintA,
+                tokenMintB: mintB,
+                tokenVaultA: vaultA,
+                tokenVaultB: vaultB,
+                payer: me,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .rpc();
+
+        await mintTo(provider.connection, payer, mintA, vaultA, me, 1_000_000_000_000);
+        await mintTo(provider.connection, payer, mintB, vaultB, me, 1_000_000_000_000);
+
+        ownerA = await createAccount(provider.connection, payer, mintA, me);
+        ownerB = await createAccount(provider.connection, payer, mintB, me);
+        await mintTo(provider.connection, payer, mintA, ownerA, me, 1_000_000_000);
+    });
+
+    it("Swaps exact input A→B through the Whirlpool interface", async () => {
+        const amountIn = new anchor.BN(100_000_000); // 100 A
+
+        await amm.methods
+            .swap(amountIn, new anchor.BN(99_000_000), new anchor.BN(0), true, true)
+            .accounts(swapAccounts())
+            .rpc();
+
+        const received = (await getAccount(provider.connection, ownerB)).amount;
+        assert.equal(received, BigInt(99_900_000));
+    });
+
+    it("Reverts when output is below the minimum", async () => {
+        // Pool now pays 0.98 B per A; an oracle bound at 0.997 must reject it
+        await amm.methods.setPrice(new anchor.BN(980_000)).accounts({ pool: poolPda }).rpc();
+
+        try {
+            await amm.methods
+                .swap(new anchor.BN(100_000_000), new anchor.BN(99_700_000), new anchor.BN(0), true, true)
+                .accounts(swapAccounts())
+                .rpc();
+            assert.fail("Should have rejected swap below minimum out");
+        } catch (err) {
+            assert.include(err.toString(), "AmountOutBelowMinimum");
+        }
+    });
+
+    it("Pins the swap route on the vault", async () => {
+        const [vaultPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("vault")],
+            vaultProgram.programId
+        );
+        const inputFeed = Keypair.generate();
+
+        await vaultProgram.methods
+            .setSwapRoute(50)
+            .accounts({
+                vault: vaultPda,
+                authority: provider.wallet.publicKey,
+                dexProgram: amm.programId,
+                inputMint: mintA,
+                inputFeed: inputFeed.publicKey,
+            })
+            .rpc();
+
+        const vault = await vaultProgram.account.vault.fetch(vaultPda);
+        assert.ok(vault.dexProgram.equals(amm.programId));
+        assert.ok(vault.swapInputMint.equals(mintA));
+        assert.ok(vault.swapInputFeed.equals(inputFeed.publicKey));
+        assert.equal(vault.maxSlippageBps, 50);
+    });
+
+    it("Rejects an excessive max slippage", async () => {
+        const [vaultPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("vault")],
+            vaultProgram.programId
+        );
+
+        try {
+            await vaultProgram.methods
+                .setSwapRoute(501)
+                .accounts({
+                    vault: vaultPda,
+                    authority: provider.wallet.publicKey,
+                    dexProgram: amm.programId,
+                    inputMint: mintA,
+                    inputFeed: Keypair.generate().publicKey,
+                })
+                .rpc();
+            assert.fail("Should have rejected max slippage above 5%");
+        } catch (err) {
+            assert.include(err.message, "InvalidSlippage");
+        }
+    });
+});
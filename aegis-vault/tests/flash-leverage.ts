@@ -0,0 +1,208 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { AegisVault } from "../target/types/aegis_vault";
+import { MockLending } from "../target/types/mock_lending";
+import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("flash-leverage", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const vaultProgram = anchor.workspace.AegisVault as Program<AegisVault>;
+    const lending = anchor.workspace.MockLending as Program<MockLending>;
+
+    const LTV_BPS = 8000;
+    const FLASH_FEE_BPS = 9;
+    const usdc = (n: number) => new anchor.BN(n * 1_000_000);
+
+    let mint: PublicKey;
+    let reservePda: PublicKey;
+    let liquidityPda: PublicKey;
+    let obligationPda: PublicKey;
+    let userToken: PublicKey;
+
+    // Mirrors lending::flash_loan::flash_fee (rounded up)
+    const flashFee = (amount: anchor.BN) =>
+        amount.muln(FLASH_FEE_BPS).addn(9_999).divn(10_000);
+
+    const venueAccounts = () => ({
+        reserve: reservePda,
+        obligation: obligationPda,
+        liquidity: liquidityPda,
+        userToken,
+        owner: provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    before(async () => {
+        mint = await createMint(
+            provider.connection,
+            provider.wallet.payer,
+            provider.wallet.publicKey,
+            null,
+            6
+        );
+
+        [reservePda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("reserve"), mint.toBuffer()],
+            lending.programId
+        );
+        [liquidityPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("liquidity"), reservePda.toBuffer()],
+            lending.programId
+        );
+        [obligationPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("obligation"), reservePda.toBuffer(), provider.wallet.publicKey.toBuffer()],
+            lending.programId
+        );
+
+        await lending.methods
+            .initReserve(LTV_BPS, FLASH_FEE_BPS)
+            .accounts({
+                reserve: reservePda,
+                mint,
+                liquidity: liquidityPda,
+                payer: provider.wallet.publicKey,
+                systemProgram: SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .rpc();
+
+        await lending.methods
+            .initObligation()
+            .accounts({
+                reserve: reservePda,
+                obligation: obligationPda,
+                owner: provider.wallet.publicKey,
+                payer: provider.wallet.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+        // Seed venue liquidity and the "vault" wallet
+        await mintTo(provider.connection, provider.wallet.payer, mint, liquidityPda,
+            provider.wallet.publicKey, usdc(1_000_000).toNumber());
+        userToken = await createAccount(provider.connection, provider.wallet.payer, mint,
+            provider.wallet.publicKey);
+        await mintTo(provider.connection, provider.wallet.payer, mint, userToken,
+            provider.wallet.publicKey, usdc(1_000).toNumber());
+    });
+
+    it("Levers 1000 USDC to 1.50x in four instructions", async () => {
+        // calculate_target_borrow(1500, 15000) = 500
+        const flash = usdc(500);
+        const fee = flashFee(flash);
+
+        const tx = new Transaction().add(
+            await lending.methods.flashBorrow(flash).accounts(venueAccounts()).instruction(),
+            await lending.methods.supply(usdc(1_000).add(flash)).accounts(venueAccounts()).instruction(),
+            await lending.methods.borrow(flash.add(fee)).accounts(venueAccounts()).instruction(),
+            await lending.methods.flashRepay(flash.add(fee)).accounts(venueAccounts()).instruction(),
+        );
+        await provider.sendAndConfirm(tx);
+
+        const obligation = await lending.account.obligation.fetch(obligationPda);
+        const reserve = await lending.account.reserve.fetch(reservePda);
+        assert.equal(obligation.deposited.toString(), usdc(1_500).toString());
+        assert.equal(obligation.borrowed.toString(), flash.add(fee).toString());
+        assert.equal(reserve.flashOutstanding.toNumber(), 0);
+        assert.equal((await getAccount(provider.connection, userToken)).amount, BigInt(0));
+    });
+
+    it("Rejects a flash repayment that omits the fee", async () => {
+        const flash = usdc(10);
+        const tx = new Transaction().add(
+            await lending.methods.flashBorrow(flash).accounts(venueAccounts()).instruction(),
+            await lending.methods.flashRepay(flash).accounts(venueAccounts()).instruction(),
+        );
+
+        try {
+            await provider.sendAndConfirm(tx);
+            assert.fail("Should have rejected short flash repayment");
+        } catch (err) {
+            assert.include(err.toString(), "FlashRepayTooSmall");
+        }
+    });
+
+    it("Unwinds to 1.00x in four instructions", async () => {
+        const before = await lending.account.obligation.fetch(obligationPda);
+        const flash = before.borrowed;
+        const fee = flashFee(flash);
+
+        const tx = new Transaction().add(
+            await lending.methods.flashBorrow(flash).accounts(venueAccounts()).instruction(),
+            await lending.methods.repay(flash).accounts(venueAccounts()).instruction(),
+            await lending.methods.withdraw(before.deposited).accounts(venueAccounts()).instruction(),
+            await lending.methods.flashRepay(flash.add(fee)).accounts(venueAccounts()).instruction(),
+        );
+        await provider.sendAndConfirm(tx);
+
+        const after = await lending.account.obligation.fetch(obligationPda);
+        assert.equal(after.deposited.toNumber(), 0);
+        assert.equal(after.borrowed.toNumber(), 0);
+
+        // Equity returned less two flash fees
+        const balance = (await getAccount(provider.connection, userToken)).amount;
+        const expected = before.deposited.sub(before.borrowed).sub(fee);
+        assert.equal(balance.toString(), expected.toString());
+    });
+
+    it("Pins the lending venue on the vault", async () => {
+        const [vaultPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("vault")],
+            vaultProgram.programId
+        );
+        const [vaultObligation] = PublicKey.findProgramAddressSync(
+            [Buffer.from("obligation"), reservePda.toBuffer(), vaultPda.toBuffer()],
+            lending.programId
+        );
+
+        await lending.methods
+            .initObligation()
+            .accounts({
+                reserve: reservePda,
+                obligation: vaultObligation,
+                owner: vaultPda,
+                payer: provider.wallet.publicKey,
+                systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+
+        try {
+            await vaultProgram.methods
+                .setLendingVenue(FLASH_FEE_BPS - 1)
+                .accounts({
+                    vault: vaultPda,
+                    authority: provider.wallet.publicKey,
+                    lendingProgram: lending.programId,
+                    lendingReserve: reservePda,
+                    lendingObligation: vaultObligation,
+                })
+                .rpc();
+
+            assert.fail("Should have rejected a fee below the reserve's");
+        } catch (err) {
+            assert.include(err.message, "FlashFeeMismatch");
+        }
+
+        await vaultProgram.methods
+            .setLendingVenue(FLASH_FEE_BPS)
+            .accounts({
+                vault: vaultPda,
+                authority: provider.wallet.publicKey,
+                lendingProgram: lending.programId,
+                lendingReserve: reservePda,
+                lendingObligation: vaultObligation,
+            })
+            .rpc();
+
+        const vault = await vaultProgram.account.vault.fetch(vaultPda);
+        assert.ok(vault.lendingProgram.equals(lending.programId));
+        assert.ok(vault.lendingReserve.equals(reservePda));
+        assert.ok(vault.lendingObligation.equals(vaultObligation));
+        assert.equal(vault.flashFeeBps, FLASH_FEE_BPS);
+    });
+});